@@ -7,7 +7,7 @@
 
 use std::path::PathBuf;
 
-use clap::{ArgGroup, Parser, Subcommand};
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
 
 pub type Base64Address = String;
 pub type Base64Hash = String;
@@ -16,7 +16,119 @@ pub type Base64String = String;
 /// A CLI for submitting Transactions to, and querying data from, the ParallelChain.  
 #[derive(Debug, Parser)]
 #[clap(name = "ParallelChain Client CLI", about = "ParallelChain Client CLI (`pchain_client`) is a command-line tool for you to connect and interact with the ParallelChain Mainnet/Testnet.", author = "<ParallelChain Lab>", long_about = None, version)]
-pub(crate) enum PChainCLI {
+#[clap(arg_required_else_help = true)]
+pub(crate) struct PChainCLI {
+    #[clap(flatten)]
+    pub global_options: GlobalOptions,
+
+    #[clap(subcommand)]
+    pub command: PChainCommand,
+}
+
+/// Options which can be specified together with any subcommand.
+#[derive(Debug, Args)]
+pub(crate) struct GlobalOptions {
+    /// Group digits of amounts, balances and stake powers with commas, e.g. 1,000,000. Output is not grouped by default.
+    #[clap(long = "group-digits", global = true, display_order = 100)]
+    pub group_digits: bool,
+
+    /// Print the elapsed time of each request to Fullnode RPC, in milliseconds, on stderr.
+    #[clap(long = "timing", global = true, display_order = 102)]
+    pub timing: bool,
+
+    /// Print the untouched body of each response from Fullnode RPC, Base64url encoded, on stderr before it is decoded. Useful for reporting bugs.
+    #[clap(long = "raw-response", global = true, display_order = 103)]
+    pub raw_response: bool,
+
+    /// Abbreviate addresses to their first and last 4 characters, e.g. `kRPL…Pv_A`, in lists and tables meant for reading,
+    /// such as `keys list` and `config contacts list`. Output of `--json`, including validator sets, keeps full addresses.
+    #[clap(long = "compact-address", global = true, display_order = 104)]
+    pub compact_address: bool,
+
+    /// Show amounts, balances and stake powers in XPLL rounded to this many fractional digits (0 to 8), next to the exact amount in Grays.
+    #[clap(
+        long = "precision",
+        value_name = "DIGITS",
+        global = true,
+        display_order = 110,
+        value_parser = clap::value_parser!(u32).range(0..=8)
+    )]
+    pub precision: Option<u32>,
+
+    /// Hard limit, in seconds, on the total time the whole command may take, including every request to Fullnode RPC,
+    /// retry and poll. Pending requests are cancelled once it is exceeded, and the program exits with code 124.
+    #[clap(
+        long = "deadline",
+        value_name = "SECS",
+        global = true,
+        display_order = 106
+    )]
+    pub deadline: Option<u64>,
+
+    /// Send requests to Fullnode RPC through this HTTP(S) proxy. Overrides the proxy saved in the config file. With `config setup`,
+    /// the proxy is saved to the config file and used by later commands. An empty value uses no proxy, and removes the saved one with
+    /// `config setup`. The `HTTPS_PROXY` environment variable is honored otherwise.
+    #[clap(
+        long = "proxy",
+        value_name = "PROXY_URL",
+        global = true,
+        display_order = 107
+    )]
+    pub proxy: Option<String>,
+
+    /// Trust the CA certificates in this PEM bundle, instead of the system ones, when connecting to Fullnode RPC over HTTPS.
+    /// Overrides the bundle saved in the config file. With `config setup`, the path is saved to the config file and used by later commands.
+    /// An empty value uses the system CA certificates, and removes the saved bundle with `config setup`.
+    #[clap(
+        long = "ca-cert",
+        value_name = "PEM_FILE",
+        global = true,
+        display_order = 108
+    )]
+    pub ca_cert: Option<String>,
+
+    /// Print a final `STATUS ok` or `STATUS error code=<EXIT_CODE>` line on stderr when the command exits, whichever
+    /// way it succeeds or fails.
+    #[clap(long = "status-line", global = true, display_order = 109)]
+    pub status_line: bool,
+
+    /// Read the password of the keypair file as one line from stdin instead of prompting for it, e.g.
+    /// `echo "$PASSWORD" | pchain_client transaction submit ... --password-stdin`.
+    #[clap(long = "password-stdin", global = true, display_order = 111)]
+    pub password_stdin: bool,
+
+    /// Treat warnings as errors and exit with a non-zero code, e.g. when a transaction has a max base fee below the
+    /// minimum or an unsupported CBI version, or the password is weak. Overrides `--allow-low-base-fee` and `--force`.
+    #[clap(long = "abort-on-warning", global = true, display_order = 112)]
+    pub abort_on_warning: bool,
+
+    /// Print the return values of Call receipts and view calls as plain Base64url, without the "(Base64 encoded)"
+    /// label, so that scripts can decode them directly.
+    #[clap(long = "raw-return-values", global = true, display_order = 114)]
+    pub raw_return_values: bool,
+}
+
+/// Options which decide what happens when an item of a batch fails, shared by the subcommands which
+/// process more than one item in a run.
+#[derive(Debug, Args)]
+pub struct BatchOptions {
+    /// [Optional] Stop at the first item which fails. This is the default of `transaction create`, whose transactions
+    /// depend on each other by nonce.
+    #[clap(
+        long = "fail-fast",
+        display_order = 90,
+        conflicts_with = "collect-errors"
+    )]
+    pub fail_fast: bool,
+
+    /// [Optional] Process every item even if some fail, then report a summary of the failed items and exit with non-zero code.
+    /// This is the default of `query batch` and `keys import-all`.
+    #[clap(long = "collect-errors", display_order = 91)]
+    pub collect_errors: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum PChainCommand {
     /// Construct and submit Transactions to ParallelChain network.
     #[clap(display_order = 1)]
     Transaction {
@@ -29,6 +141,43 @@ pub(crate) enum PChainCLI {
     Query {
         #[clap(subcommand)]
         query_subcommand: Query,
+
+        /// Print the Fullnode RPC Provider the query runs against on stderr, before the query is sent.
+        #[clap(long = "show-context", global = true, display_order = 101)]
+        show_context: bool,
+
+        /// Save committed blocks, transactions and receipts to a cache under the pchain_client home directory, and
+        /// answer `block`, `block-header`, `tx`, `receipt` and `tx-gas` queries for the same hash from the cache.
+        /// Each Fullnode RPC URL has its own cache.
+        /// `view` calls are also cached until the highest committed block changes.
+        #[clap(
+            long = "cache",
+            global = true,
+            overrides_with = "no-cache",
+            display_order = 104
+        )]
+        cache: bool,
+
+        /// Do not read or write the query cache. This is the default.
+        #[clap(
+            long = "no-cache",
+            global = true,
+            overrides_with = "cache",
+            display_order = 105
+        )]
+        no_cache: bool,
+
+        /// Send a request to Fullnode RPC again, up to this many times, if it fails with a server error (5xx) or without a
+        /// response, e.g. when the connection is reset. Client errors (4xx) are not retried. Waits 1 second before the first
+        /// retry and twice as long before each later one.
+        #[clap(
+            long = "retries",
+            value_name = "N",
+            global = true,
+            default_value = "0",
+            display_order = 113
+        )]
+        retries: u32,
     },
 
     /// Locally store and manage account keypairs you created. (Password required)
@@ -53,12 +202,48 @@ pub(crate) enum PChainCLI {
     },
 }
 
+impl PChainCommand {
+    /// Whether the command runs without the password and keypair file, i.e. all `query`, `parse` and `config list`.
+    /// First-run password setup is skipped for these so that users who only query are not prompted for it.
+    pub(crate) fn is_read_only(&self) -> bool {
+        match self {
+            PChainCommand::Query { .. } => true,
+            PChainCommand::Parse { parse_subcommand } => !matches!(
+                parse_subcommand,
+                Parse::ContractAddress {
+                    version: ContractAddressVersion::FromTx {
+                        keypair_name: Some(_),
+                        ..
+                    },
+                    ..
+                }
+            ),
+            PChainCommand::Config { config_subcommand } => {
+                matches!(
+                    config_subcommand,
+                    ConfigCommand::List | ConfigCommand::Paths | ConfigCommand::Contacts { .. }
+                )
+            }
+            PChainCommand::Keys { crypto_subcommand } => matches!(
+                crypto_subcommand,
+                Keys::Create { dry_run: true, .. }
+                    | Keys::Import { dry_run: true, .. }
+                    | Keys::Verify { .. }
+                    | Keys::Address { .. }
+            ),
+            PChainCommand::Transaction { tx_subcommand } => {
+                matches!(tx_subcommand, Transaction::Show { .. })
+            }
+        }
+    }
+}
+
 #[derive(Debug, Subcommand)]
 pub enum Transaction {
     /// Create new Transaction with command and save to a JSON file.
-    /// You are required to specify the transaction version.
+    /// You are required to specify the transaction version, or let it be detected from the network with `--auto-version`.
     #[clap(display_order = 1)]
-    #[clap(group(ArgGroup::new("version").required(true).multiple(false).args(&["v1", "v2"])))]
+    #[clap(group(ArgGroup::new("version").required(true).multiple(false).args(&["v1", "v2", "auto-version"])))]
     Create {
         /// [Optional] Destination path of the output Transaction file. If not provided, default save file to current directory with filename `tx.json`.
         /// File with same name will be OVERWRITTEN. Directory provided has to exist.
@@ -73,21 +258,65 @@ pub enum Transaction {
         #[clap(long = "v2", display_order = 3)]
         v2: bool,
 
+        /// [One of] Use the transaction version of the network, detected from the latest block of the Fullnode RPC.
+        #[clap(long = "auto-version", display_order = 4)]
+        auto_version: bool,
+
         /// Number of Transactions originating from the Account so far in the ParallelChain network.
-        #[clap(long = "nonce", display_order = 4)]
+        #[clap(long = "nonce", display_order = 5)]
         nonce: u64,
 
         /// The maximum number of gas units that can be used in executing this transaction.
-        #[clap(long = "gas-limit", display_order = 5)]
-        gas_limit: u64,
+        /// Defaults to the value saved by `config set-defaults`.
+        #[clap(long = "gas-limit", display_order = 6)]
+        gas_limit: Option<u64>,
 
         /// The maximum number of Grays that you are willing to burn for the gas unit used in this transaction.
-        #[clap(long = "max-base-fee-per-gas", display_order = 6)]
-        max_base_fee_per_gas: u64,
+        /// Defaults to the value saved by `config set-defaults`.
+        #[clap(long = "max-base-fee-per-gas", display_order = 7)]
+        max_base_fee_per_gas: Option<u64>,
 
         /// The number of Grays that you are willing to pay the block proposer for including this transaction in a block.
-        #[clap(long = "priority-fee-per-gas", display_order = 7)]
-        priority_fee_per_gas: u64,
+        /// Defaults to the value saved by `config set-defaults`.
+        #[clap(long = "priority-fee-per-gas", display_order = 8)]
+        priority_fee_per_gas: Option<u64>,
+
+        /// [Optional] Minimum `max-base-fee-per-gas` accepted. Transactions below it are refused unless `--allow-low-base-fee` is given.
+        #[clap(long = "min-base-fee-per-gas", display_order = 9, default_value = "1")]
+        min_base_fee_per_gas: u64,
+
+        /// [Optional] Create the transaction even if `max-base-fee-per-gas` is below `min-base-fee-per-gas`.
+        #[clap(long = "allow-low-base-fee", display_order = 10)]
+        allow_low_base_fee: bool,
+
+        /// [Optional] Sign the transaction with this keypair and save it in borsh-serialized form instead of JSON.
+        /// The default filename becomes `tx.signed`. (Password required)
+        #[clap(long = "sign-with", display_order = 11)]
+        sign_with: Option<String>,
+
+        /// [Optional] A note for your own bookkeeping, saved in the Transaction file. It is not submitted to ParallelChain.
+        #[clap(
+            long = "note",
+            display_order = 12,
+            value_name = "TEXT",
+            conflicts_with = "sign-with"
+        )]
+        note: Option<String>,
+
+        /// [Optional] Name of another keypair which signs the transaction, saved in the Transaction file. Can be given
+        /// multiple times. Reserved for multi-signer transactions: the keypairs are checked when signing, but the
+        /// transaction is refused as a ParallelChain transaction can only carry one signature at present.
+        #[clap(
+            long = "co-signer",
+            display_order = 13,
+            value_name = "KEYPAIR_NAME",
+            multiple_occurrences = true,
+            conflicts_with = "sign-with"
+        )]
+        co_signers: Vec<String>,
+
+        #[clap(flatten)]
+        batch_options: BatchOptions,
 
         #[clap(subcommand)]
         create_tx_subcommand: CreateTx,
@@ -113,6 +342,204 @@ pub enum Transaction {
         /// This is used to sign the transaction as it proves 'you' are authorized to make this transaction.
         #[clap(long = "keypair-name", display_order = 2)]
         keypair_name: String,
+
+        /// [Optional] Only print the transaction hash (Base64 encoded) on success. Cannot be combined with `--wait-receipt`.
+        #[clap(long = "hash-only", display_order = 3, conflicts_with = "wait-receipt")]
+        hash_only: bool,
+
+        /// [Optional] Minimum `max_base_fee_per_gas` accepted. Transactions below it are refused unless `--allow-low-base-fee` is given.
+        #[clap(long = "min-base-fee-per-gas", display_order = 4, default_value = "1")]
+        min_base_fee_per_gas: u64,
+
+        /// [Optional] Submit the transaction even if `max_base_fee_per_gas` is below `min-base-fee-per-gas`.
+        #[clap(long = "allow-low-base-fee", display_order = 5)]
+        allow_low_base_fee: bool,
+
+        /// [Optional] Refuse to submit unless the nonce in the transaction file equals this value.
+        #[clap(long = "expected-nonce", display_order = 6)]
+        expected_nonce: Option<u64>,
+
+        /// [Optional] Refuse to submit unless the nonce in the transaction file equals the signer's nonce on chain, e.g. to avoid submitting a stale transaction file.
+        #[clap(long = "assert-nonce", display_order = 7)]
+        assert_nonce: bool,
+
+        /// [Optional] Refuse to sign unless the keypair given by `--keypair-name` has this address, or the address of `@<NAME>` in the
+        /// address book. Guards against signing with the wrong keypair.
+        #[clap(
+            long = "expected-signer",
+            display_order = 12,
+            value_name = "ADDRESS",
+            allow_hyphen_values(true)
+        )]
+        expected_signer: Option<Base64Address>,
+
+        /// [Optional] After the transaction is accepted, wait until it is committed and print its receipt.
+        #[clap(long = "wait-receipt", display_order = 9)]
+        wait_receipt: bool,
+
+        /// [Optional] Relative/absolute path to a JSON schema file used to decode the return value of each `Call` command in the receipt.
+        /// The schema format is the same as `parse call-result --schema-file`.
+        #[clap(
+            long = "decode-with",
+            display_order = 10,
+            value_name = "SCHEMA_FILE",
+            requires = "wait-receipt"
+        )]
+        decode_with: Option<PathBuf>,
+
+        /// [Optional] Seconds to wait for the receipt before giving up.
+        #[clap(
+            long = "timeout",
+            display_order = 11,
+            default_value = "300",
+            requires = "wait-receipt"
+        )]
+        timeout: u64,
+
+        /// [Optional] Describe what the transaction will do, i.e. its signer, fees and commands, and exit without submitting.
+        /// (Password required, to read the address of the signer)
+        #[clap(long = "explain", display_order = 13)]
+        explain: bool,
+
+        /// [Optional] Refuse to submit a transaction whose hash is in the local ledger of submitted transactions, e.g. when a
+        /// script is re-run after a crash, and record the hash in the ledger once the transaction is accepted.
+        #[clap(long = "idempotent", display_order = 14)]
+        idempotent: bool,
+    },
+    /// Replace or cancel a pending Transaction by submitting another Transaction with the same nonce. (Password required)
+    /// Whether the pending Transaction is replaced depends on the replacement rules of the mempool of the Fullnode,
+    /// which may require a higher fee than the pending one.
+    #[clap(arg_required_else_help = true, display_order = 4)]
+    Replace {
+        /// Name of the keypair which signed the pending Transaction.
+        #[clap(long = "keypair-name", display_order = 1)]
+        keypair_name: String,
+
+        /// [Optional] Cancel with a TransactionV1 instead of TransactionV2. A replacement from `--file` keeps the version of the file.
+        #[clap(long = "v1", display_order = 2, conflicts_with = "file")]
+        v1: bool,
+
+        /// [Optional] Nonce of the pending Transaction. Defaults to the current nonce of the signer account,
+        /// which is the nonce of its earliest pending Transaction.
+        #[clap(long = "nonce", display_order = 3)]
+        nonce: Option<u64>,
+
+        /// [Optional] Relative/absolute path to a JSON file of Transaction, whose commands replace the pending Transaction.
+        /// If not provided, the pending Transaction is cancelled by a transfer of 0 to the signer itself.
+        #[clap(long = "file", display_order = 4)]
+        file: Option<String>,
+
+        /// The maximum number of gas units that can be used in executing the replacement transaction.
+        #[clap(long = "gas-limit", display_order = 5)]
+        gas_limit: u64,
+
+        /// The maximum number of Grays that you are willing to burn for the gas unit used in the replacement transaction.
+        #[clap(long = "max-base-fee-per-gas", display_order = 6)]
+        max_base_fee_per_gas: u64,
+
+        /// The number of Grays that you are willing to pay the block proposer for including the replacement transaction in a block.
+        #[clap(long = "priority-fee-per-gas", display_order = 7)]
+        priority_fee_per_gas: u64,
+
+        /// [Optional] Minimum `max-base-fee-per-gas` accepted. Transactions below it are refused unless `--allow-low-base-fee` is given.
+        #[clap(long = "min-base-fee-per-gas", display_order = 8, default_value = "1")]
+        min_base_fee_per_gas: u64,
+
+        /// [Optional] Submit the replacement even if `max-base-fee-per-gas` is below `min-base-fee-per-gas`.
+        #[clap(long = "allow-low-base-fee", display_order = 9)]
+        allow_low_base_fee: bool,
+    },
+    /// Suggest `max-base-fee-per-gas` and `priority-fee-per-gas` from the fees of recent blocks.
+    #[clap(display_order = 5)]
+    SuggestFees {
+        /// [Optional] Number of recent blocks to sample.
+        #[clap(long = "blocks", display_order = 1, default_value = "10")]
+        blocks: u64,
+    },
+    /// Append all commands of a Transaction file to another Transaction file. Both files have to be of the same transaction version.
+    #[clap(arg_required_else_help = true, display_order = 6)]
+    Merge {
+        /// Relative/absolute path to the JSON file of Transaction which commands are appended to. The file is updated in place.
+        #[clap(long = "into", display_order = 1)]
+        into: String,
+
+        /// Relative/absolute path to the JSON file of Transaction which commands are taken from. Nonce and fees of this file are ignored.
+        #[clap(long = "from", display_order = 2)]
+        from: String,
+    },
+    /// Show the content of a Transaction file, including its note.
+    #[clap(arg_required_else_help = true, display_order = 7)]
+    Show {
+        /// Relative/absolute path to a JSON file of Transaction.
+        #[clap(long = "file", display_order = 1)]
+        file: String,
+    },
+    /// Create a Transaction with one command, then sign and submit it at once without writing a Transaction file. (Password required)
+    #[clap(arg_required_else_help = true, display_order = 8)]
+    Send {
+        /// Name of the keypair which signs the transaction.
+        #[clap(long = "keypair-name", display_order = 1)]
+        keypair_name: String,
+
+        /// [Optional] Send TransactionV1 instead of TransactionV2.
+        #[clap(long = "v1", display_order = 2)]
+        v1: bool,
+
+        /// [Optional] Number of Transactions originating from the Account so far in the ParallelChain network.
+        /// Defaults to the current nonce of the signer account.
+        #[clap(long = "nonce", display_order = 3)]
+        nonce: Option<u64>,
+
+        /// The maximum number of gas units that can be used in executing this transaction.
+        /// Defaults to the value saved by `config set-defaults`.
+        #[clap(long = "gas-limit", display_order = 4)]
+        gas_limit: Option<u64>,
+
+        /// The maximum number of Grays that you are willing to burn for the gas unit used in this transaction.
+        /// Defaults to the value saved by `config set-defaults`.
+        #[clap(long = "max-base-fee-per-gas", display_order = 5)]
+        max_base_fee_per_gas: Option<u64>,
+
+        /// The number of Grays that you are willing to pay the block proposer for including this transaction in a block.
+        /// Defaults to the value saved by `config set-defaults`.
+        #[clap(long = "priority-fee-per-gas", display_order = 6)]
+        priority_fee_per_gas: Option<u64>,
+
+        /// [Optional] Minimum `max-base-fee-per-gas` accepted. Transactions below it are refused unless `--allow-low-base-fee` is given.
+        #[clap(long = "min-base-fee-per-gas", display_order = 7, default_value = "1")]
+        min_base_fee_per_gas: u64,
+
+        /// [Optional] Send the transaction even if `max-base-fee-per-gas` is below `min-base-fee-per-gas`.
+        #[clap(long = "allow-low-base-fee", display_order = 8)]
+        allow_low_base_fee: bool,
+
+        /// [Optional] Only print the transaction hash (Base64 encoded) on success.
+        #[clap(long = "hash-only", display_order = 9)]
+        hash_only: bool,
+
+        /// [Optional] Also save the transaction to this JSON file before submitting it. File with same name will be OVERWRITTEN.
+        #[clap(long = "save", display_order = 10, value_name = "PATH")]
+        save: Option<String>,
+
+        #[clap(subcommand)]
+        create_tx_subcommand: CreateTx,
+    },
+    /// Sign a Transaction file and save the signed transaction in borsh-serialized form, i.e. the bytes that Fullnode RPC accepts,
+    /// for tools which do not read the JSON Transaction file, e.g. relays or batch submitters. (Password required)
+    #[clap(arg_required_else_help = true, display_order = 9)]
+    ExportBinary {
+        /// Relative/absolute path to a JSON file of Transaction.
+        #[clap(long = "file", display_order = 1)]
+        file: String,
+
+        /// Name of the keypair which signs the transaction.
+        #[clap(long = "keypair-name", display_order = 2)]
+        keypair_name: String,
+
+        /// [Optional] Destination path of the signed transaction. If not provided, default save file to current directory with filename `tx.signed`.
+        /// File with same name will be OVERWRITTEN. Directory provided has to exist.
+        #[clap(long = "destination", display_order = 3)]
+        destination: Option<String>,
     },
 }
 
@@ -121,17 +548,35 @@ pub enum Query {
     /// Query an Account's balance (in Grays).
     #[clap(arg_required_else_help = true, display_order = 1)]
     Balance {
-        /// Address of the External or Contract Account you'd like to query.
+        /// Address of the External or Contract Account you'd like to query, or `@<NAME>` of an entry in the address book.
         #[clap(long = "address", display_order = 1, allow_hyphen_values(true))]
         address: Base64Address,
+
+        /// [Optional] Query again every SECS seconds and replace the printed balance, until interrupted (e.g. Ctrl+C).
+        #[clap(
+            long = "refresh",
+            value_name = "SECS",
+            display_order = 2,
+            value_parser = clap::value_parser!(u64).range(1..)
+        )]
+        refresh: Option<u64>,
     },
 
     /// Query the number of Transactions originating from an External Account that has been included on ParallelChain (a.k.a., the nonce).
     #[clap(arg_required_else_help = true, display_order = 2)]
     Nonce {
-        /// Address of the External Account you'd like to query.
+        /// Address of the External Account you'd like to query, or `@<NAME>` of an entry in the address book.
         #[clap(long = "address", display_order = 1, allow_hyphen_values(true))]
         address: Base64Address,
+
+        /// [Optional] Query again every SECS seconds and replace the printed nonce, until interrupted (e.g. Ctrl+C).
+        #[clap(
+            long = "refresh",
+            value_name = "SECS",
+            display_order = 2,
+            value_parser = clap::value_parser!(u64).range(1..)
+        )]
+        refresh: Option<u64>,
     },
 
     /// Query a Contract Account's Contract Byte Code (Base64 encoded).
@@ -160,6 +605,7 @@ pub enum Query {
     },
 
     /// Trigger the Contract's view method.
+    /// The gas limit of a view call is fixed by the Fullnode and cannot be set from the client.
     #[clap(arg_required_else_help = true, display_order = 5)]
     View {
         /// The address of the target contract
@@ -175,10 +621,10 @@ pub enum Query {
         arguments: Option<String>,
     },
 
-    /// Query block information. Search the block either by block height, block hash or tx hash.
+    /// Query block information. Search the block either by block height, block hash or tx hash, or follow new blocks.
     /// You are required to specify one of the optional parameter.
     #[clap(arg_required_else_help = true, display_order = 6)]
-    #[clap(group(ArgGroup::new("block").required(true).multiple(false).args(&["block-height", "block-hash", "tx-hash", "latest"])))]
+    #[clap(group(ArgGroup::new("block").required(true).multiple(false).args(&["block-height", "block-hash", "tx-hash", "latest", "follow"])))]
     Block {
         /// [Optional] Block height of the Block you'd like to query.
         #[clap(long = "block-height", display_order = 1)]
@@ -195,6 +641,21 @@ pub enum Query {
         /// [Optional]: Specify this flag to query from the latest block
         #[clap(long = "latest", display_order = 4)]
         latest: bool,
+
+        /// [Optional]: Whether to include the receipts of the transactions in the Block. Set to false for leaner output.
+        #[clap(
+            long = "with-receipts",
+            display_order = 5,
+            value_name = "BOOL",
+            default_value_t = true,
+            action = clap::ArgAction::Set
+        )]
+        with_receipts: bool,
+
+        /// [Optional]: Keep printing new blocks as they are committed, until interrupted. Blocks are pushed through a
+        /// subscription over the WebSocket endpoint set up by `config setup --ws-url`, instead of polling.
+        #[clap(long = "follow", display_order = 6)]
+        follow: bool,
     },
 
     /// Query block header only. Search the block either by block height, block hash or tx hash.
@@ -225,6 +686,14 @@ pub enum Query {
         /// Transaction hash of the Transaction you'd like to query.
         #[clap(long = "hash", display_order = 1, allow_hyphen_values(true))]
         tx_hash: Base64Hash,
+
+        /// [Optional] Include the hash of the containing block and the position of the Transaction in it.
+        #[clap(long = "full", display_order = 2)]
+        full: bool,
+
+        /// [Optional] Only display exit code and gas used of each command in the receipt.
+        #[clap(long = "summary", display_order = 3)]
+        summary: bool,
     },
 
     /// Query Transaction Receipt by tx hash.
@@ -233,50 +702,129 @@ pub enum Query {
         /// Transaction hash of the Transaction you'd like to query.
         #[clap(long = "hash", display_order = 2, allow_hyphen_values(true))]
         tx_hash: Base64Hash,
+
+        /// [Optional] Only display exit code and gas used of each command in the receipt.
+        #[clap(long = "summary", display_order = 3)]
+        summary: bool,
+
+        /// [Optional] Display the receipt and its total gas used together as one JSON object, e.g. for scripts.
+        #[clap(long = "json", display_order = 4, conflicts_with = "summary")]
+        json: bool,
     },
 
-    /// Query information related to Deposit
+    /// Query a consolidated report of a Transaction: whether it is known, its position on chain,
+    /// exit codes of its receipt and the time its block was proposed.
     #[clap(arg_required_else_help = true, display_order = 10)]
+    TxDetail {
+        /// Transaction hash of the Transaction you'd like to query.
+        #[clap(long = "hash", display_order = 1, allow_hyphen_values(true))]
+        tx_hash: Base64Hash,
+    },
+
+    /// Query whether a Transaction is committed. Optionally keep polling until it is committed.
+    #[clap(arg_required_else_help = true, display_order = 11)]
+    TxStatus {
+        /// Transaction hash of the Transaction you'd like to query.
+        #[clap(long = "hash", display_order = 1, allow_hyphen_values(true))]
+        tx_hash: Base64Hash,
+
+        /// [Optional] Keep polling until the Transaction is committed or the timeout is reached.
+        #[clap(long = "watch", display_order = 2)]
+        watch: bool,
+
+        /// [Optional] Initial number of seconds between polls in watch mode. The interval doubles after every poll, up to 8 times the initial value. Default is 2.
+        #[clap(
+            long = "interval",
+            display_order = 3,
+            default_value = "2",
+            requires = "watch"
+        )]
+        interval: u64,
+
+        /// [Optional] Number of seconds to wait before giving up in watch mode. Default is 300.
+        #[clap(
+            long = "timeout",
+            display_order = 4,
+            default_value = "300",
+            requires = "watch"
+        )]
+        timeout: u64,
+    },
+
+    /// Query information related to Deposit
+    #[clap(arg_required_else_help = true, display_order = 12)]
     Deposit {
-        /// Address of the operator account of a stake pool.
+        /// Address of the operator account of a stake pool, or `@<NAME>` of an entry in the address book.
         #[clap(long = "operator", display_order = 1, allow_hyphen_values(true))]
         operator: Base64Address,
 
-        /// Address of the owner account that submitted a stake.
+        /// Address of the owner account that submitted a stake, or `@<NAME>` of an entry in the address book.
         #[clap(long = "owner", display_order = 2, allow_hyphen_values(true))]
         owner: Base64Address,
     },
 
     /// Query information related to Pools
-    #[clap(arg_required_else_help = true, display_order = 11)]
+    #[clap(arg_required_else_help = true, display_order = 13)]
     Pool {
-        /// Address of the operator account of a stake pool.
+        /// Address of the operator account of a stake pool, or `@<NAME>` of an entry in the address book.
         #[clap(long = "operator", display_order = 1)]
         operator: Base64Address,
 
         /// [Optional] Include stakes in result.
         #[clap(long = "with-stakes", display_order = 2)]
         with_stakes: bool,
+
+        /// [Optional] Display the pool with flattened fields: operator, commission_rate, power, operator_stake_power
+        /// and delegator_count. delegator_count is null unless `--with-stakes` is given.
+        #[clap(long = "json", display_order = 3)]
+        json: bool,
     },
 
     /// Query information related to Stakes
-    #[clap(arg_required_else_help = true, display_order = 12)]
+    #[clap(arg_required_else_help = true, display_order = 14)]
     Stake {
-        /// Address of the operator account of a stake pool.
+        /// Address of the operator account of a stake pool, or `@<NAME>` of an entry in the address book.
         #[clap(long = "operator", display_order = 1, allow_hyphen_values(true))]
         operator: Base64Address,
 
-        /// Address of the owner account that submitted a stake.
+        /// Address of the owner account that submitted a stake, or `@<NAME>` of an entry in the address book.
         #[clap(long = "owner", display_order = 2, allow_hyphen_values(true))]
         owner: Base64Address,
     },
 
     /// Query Validator Sets
-    #[clap(arg_required_else_help = true, display_order = 13)]
+    #[clap(arg_required_else_help = true, display_order = 15)]
     Validators {
         #[clap(subcommand)]
         validator_subcommand: Validators,
     },
+
+    /// Query the total gas used by all commands of a Transaction, and print it as a single number.
+    #[clap(arg_required_else_help = true, display_order = 16)]
+    TxGas {
+        /// Transaction hash of the Transaction you'd like to query.
+        #[clap(long = "hash", display_order = 1, allow_hyphen_values(true))]
+        tx_hash: Base64Hash,
+    },
+
+    /// Query the current epoch from the network state, together with the latest committed block it was read at.
+    #[clap(display_order = 17)]
+    Epoch,
+
+    /// Run several queries listed in a JSON file and display their results together as one JSON object, keyed by the
+    /// name of each query. Results which are not JSON are kept as strings. Each query is run as `pchain_client query`
+    /// with `--cache`, `--retries` and the global options of this command. Errors are reported on stderr.
+    #[clap(arg_required_else_help = true, display_order = 18)]
+    Batch {
+        /// Relative/absolute path to a JSON file of queries, e.g.
+        /// '[{"name": "alice", "query": ["balance", "--address", "@alice"]}, {"name": "latest", "query": ["block-header", "--latest"]}]'.
+        /// `batch`, `--refresh`, `--watch` and `--follow` are not allowed in the queries.
+        #[clap(long = "file", display_order = 1)]
+        file: String,
+
+        #[clap(flatten)]
+        batch_options: BatchOptions,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -285,19 +833,105 @@ pub enum ConfigCommand {
     #[clap(arg_required_else_help = true, display_order = 2)]
     Setup {
         /// The HTTP/HTTPS URL of Fullnode RPC to submit and query information from ParallelChain.
-        #[clap(long = "url", required = true, display_order = 1)]
-        url: String,
+        #[clap(long = "url", required_unless_present = "ws-url", display_order = 1)]
+        url: Option<String>,
+
+        /// [Optional] The WS/WSS URL of the WebSocket endpoint of Fullnode RPC, which `query block --follow` subscribes
+        /// to new blocks through. Set it to empty to remove the saved one.
+        #[clap(long = "ws-url", display_order = 2)]
+        ws_url: Option<String>,
     },
     /// Show RPC url configuration with status.
     #[clap(display_order = 3)]
     List,
+
+    /// Export current configuration to a file, which can be imported on another machine.
+    #[clap(display_order = 4)]
+    Export {
+        /// [Optional] Destination path of the exported configuration file. If not provided, default save file to current directory with filename `pchain_client_config.toml`.
+        /// File with same name will be OVERWRITTEN. Directory provided has to exist.
+        #[clap(long = "destination", display_order = 1)]
+        destination: Option<String>,
+    },
+
+    /// Import configuration from a file exported by `config export`. The RPC url in the file has to be active.
+    #[clap(arg_required_else_help = true, display_order = 5)]
+    Import {
+        /// Relative / absolute path of the configuration file.
+        #[clap(long = "file", display_order = 1)]
+        file: String,
+    },
+
+    /// Manage the address book of named accounts. Use `@<NAME>` in place of the address of a recipient, target contract or operator,
+    /// or of an account to query.
+    #[clap(arg_required_else_help = true, display_order = 6)]
+    Contacts {
+        #[clap(subcommand)]
+        contacts_subcommand: ContactsCommand,
+    },
+
+    /// Show where pchain_client reads its files from: the home directory, and the config, keypair, password hash and address book files.
+    #[clap(display_order = 7)]
+    Paths,
+
+    /// Save default gas parameters, which `transaction create` and `transaction send` use when the corresponding
+    /// option is not given. Parameters not given here keep their saved defaults.
+    #[clap(arg_required_else_help = true, display_order = 8)]
+    SetDefaults {
+        /// [Optional] Default maximum number of gas units that can be used in executing a transaction.
+        #[clap(long = "gas-limit", display_order = 1)]
+        gas_limit: Option<u64>,
+
+        /// [Optional] Default maximum number of Grays that you are willing to burn for the gas unit used in a transaction.
+        #[clap(long = "max-base-fee-per-gas", display_order = 2)]
+        max_base_fee_per_gas: Option<u64>,
+
+        /// [Optional] Default number of Grays that you are willing to pay the block proposer for including a transaction in a block.
+        #[clap(long = "priority-fee-per-gas", display_order = 3)]
+        priority_fee_per_gas: Option<u64>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ContactsCommand {
+    /// Add an address to the address book under a name.
+    #[clap(arg_required_else_help = true, display_order = 1)]
+    Add {
+        /// Name of the entry. Letters, digits, `-` and `_` only.
+        #[clap(long = "name", display_order = 1)]
+        name: String,
+
+        /// Address of the account.
+        #[clap(long = "address", display_order = 2, allow_hyphen_values(true))]
+        address: Base64Address,
+    },
+
+    /// List the entries of the address book.
+    #[clap(display_order = 2)]
+    List,
+
+    /// Remove an entry from the address book.
+    #[clap(arg_required_else_help = true, display_order = 3)]
+    Remove {
+        /// Name of the entry.
+        #[clap(long = "name", display_order = 1)]
+        name: String,
+    },
 }
 
 #[derive(Debug, Subcommand)]
 pub enum Keys {
     /// List the Keypairs that you added to pchain_client.
     #[clap(arg_required_else_help = false, display_order = 1)]
-    List,
+    List {
+        /// [Optional] Only list the Keypairs whose name contains this string.
+        #[clap(long = "filter", display_order = 1)]
+        filter: Option<String>,
+
+        /// [Optional] Display the full name and public key of each Keypair in JSON format instead of a table.
+        #[clap(long = "json", display_order = 2)]
+        json: bool,
+    },
 
     /// Generate and save an ed25519 Keypair.
     #[clap(display_order = 2)]
@@ -305,6 +939,20 @@ pub enum Keys {
         /// [Optional] The name to identify the Keypair that you are generating.
         #[clap(long = "keypair-name", display_order = 1)]
         keypair_name: Option<String>,
+
+        /// [Optional] Display the name and public key of the created Keypair in JSON format.
+        #[clap(long = "json", display_order = 2)]
+        json: bool,
+
+        /// [Optional] Generate the Keypair and display what would be saved, without saving it. The name is not checked against saved Keypairs.
+        #[clap(long = "dry-run", display_order = 3)]
+        dry_run: bool,
+
+        /// [Optional] Derive the Keypair from a new 24-word BIP39 mnemonic phrase, which backs up the Keypair.
+        /// The phrase is displayed once and never saved. It restores the Keypair only with `keys import-mnemonic`, as
+        /// wallets which derive keys along a BIP44 path get another Keypair from the same phrase.
+        #[clap(long = "mnemonic", display_order = 4, conflicts_with = "dry-run")]
+        mnemonic: bool,
     },
 
     /// Import an existing keypair.
@@ -321,6 +969,15 @@ pub enum Keys {
         /// The name to identify the Keypair.
         #[clap(long = "keypair-name", display_order = 3, allow_hyphen_values(true))]
         keypair_name: String,
+
+        /// [Optional] Display the name and public key of the imported Keypair in JSON format.
+        #[clap(long = "json", display_order = 4)]
+        json: bool,
+
+        /// [Optional] Check that the keys form a valid Keypair and display what would be saved, without saving it.
+        /// The name is not checked against saved Keypairs.
+        #[clap(long = "dry-run", display_order = 5)]
+        dry_run: bool,
     },
 
     /// Export existing keypair to JSON file
@@ -337,15 +994,169 @@ pub enum Keys {
     },
 
     /// Sign a message using registered Keypair and return Base64 encoded ciphertext.
+    /// The message is given in Base64, as a file, or as plaintext.
     #[clap(arg_required_else_help = true, display_order = 5)]
+    #[clap(group(ArgGroup::new("sign-input").required(true).multiple(false).args(&["message", "file", "plaintext"])))]
     Sign {
         /// A message to sign, encoded in Base64.
         #[clap(long = "message", display_order = 1, allow_hyphen_values(true))]
-        message: String,
+        message: Option<String>,
+
+        /// Path to a file whose raw bytes are signed.
+        #[clap(long = "file", display_order = 2)]
+        file: Option<String>,
+
+        /// A message to sign as it is, e.g. "hello". Its UTF-8 bytes are signed.
+        #[clap(long = "plaintext", display_order = 3, allow_hyphen_values(true))]
+        plaintext: Option<String>,
+
+        /// The name to identify the Keypair.
+        #[clap(long = "keypair-name", display_order = 4)]
+        keypair_name: String,
+
+        /// [Optional] Write the signature to this file instead of printing it. File with same name will be OVERWRITTEN.
+        #[clap(long = "output-file", display_order = 5)]
+        output_file: Option<String>,
+
+        /// [Optional] Write raw signature bytes to the output file instead of a Base64 encoded string.
+        #[clap(long = "raw", display_order = 6, requires = "output-file")]
+        raw: bool,
+
+        /// [Optional] Encoding of the printed signature.
+        #[clap(
+            long = "output-encoding",
+            display_order = 7,
+            value_enum,
+            default_value = "base64url",
+            conflicts_with = "raw"
+        )]
+        output_encoding: OutputEncoding,
+    },
+
+    /// Add a Keypair whose private key is kept outside pchain_client, e.g. in an HSM. Transactions and messages
+    /// are signed by running the given signer command instead of with a private key in the keypair file.
+    #[clap(arg_required_else_help = true, display_order = 7)]
+    ImportExternal {
+        /// The public key of your ParallelChain account.
+        #[clap(long = "public", display_order = 1, allow_hyphen_values(true))]
+        public_key: Base64Address,
+
+        /// Command run by the shell to sign. It reads the message from stdin as a Base64url string followed by a newline,
+        /// and prints the Ed25519 signature as a Base64url string. The public key is given in the environment variable
+        /// `PCHAIN_SIGNER_PUBLIC_KEY`.
+        #[clap(long = "signer-command", display_order = 2)]
+        signer_command: String,
+
+        /// The name to identify the Keypair.
+        #[clap(long = "keypair-name", display_order = 3, allow_hyphen_values(true))]
+        keypair_name: String,
+    },
+
+    /// Sign a Transaction file using registered Keypair without submitting it, and return the Base64 encoded transaction hash and signature.
+    #[clap(arg_required_else_help = true, display_order = 6)]
+    SignTransaction {
+        /// Relative/absolute path to a JSON file of Transaction.
+        #[clap(long = "file", display_order = 1)]
+        file: String,
 
         /// The name to identify the Keypair.
         #[clap(long = "keypair-name", display_order = 2)]
         keypair_name: String,
+
+        /// [Optional] Write the whole signed transaction (borsh serialized) to this file. File with same name will be OVERWRITTEN.
+        #[clap(long = "output-file", display_order = 3)]
+        output_file: Option<String>,
+    },
+
+    /// Restore a Keypair created by `keys create --mnemonic` from its 24-word mnemonic phrase. Phrases of other
+    /// wallets give another Keypair, as they derive along a BIP44 path.
+    #[clap(arg_required_else_help = true, display_order = 10)]
+    ImportMnemonic {
+        /// The 24-word mnemonic phrase. Please wrap the phrase with quotation marks like "word1 word2 ... word24".
+        #[clap(long = "phrase", display_order = 1)]
+        phrase: String,
+
+        /// The name to identify the Keypair.
+        #[clap(long = "keypair-name", display_order = 2, allow_hyphen_values(true))]
+        keypair_name: String,
+    },
+
+    /// Verify that a signature, e.g. one made by `keys sign`, is a valid Ed25519 signature of a message by a public key.
+    #[clap(arg_required_else_help = true, display_order = 9)]
+    Verify {
+        /// The signed message, encoded in Base64.
+        #[clap(long = "message", display_order = 1, allow_hyphen_values(true))]
+        message: String,
+
+        /// The signature to verify, encoded in Base64.
+        #[clap(long = "signature", display_order = 2, allow_hyphen_values(true))]
+        signature: String,
+
+        /// The public key of the signer.
+        #[clap(long = "public", display_order = 3, allow_hyphen_values(true))]
+        public_key: Base64Address,
+    },
+
+    /// Change the password which protects the keypair file. The current password is verified, then the keypair file
+    /// is re-encrypted with the new password. With `--password-stdin`, the current and the new password are read as
+    /// consecutive lines of stdin.
+    #[clap(display_order = 8)]
+    ChangePassword,
+
+    /// Export all keypairs to one JSON file, e.g. to back up the keystore. The file contains every private key in
+    /// plain text unless `--encrypted` is given.
+    #[clap(display_order = 11)]
+    ExportAll {
+        /// [Optional] Destination path of the output file. If not provided, default save file to current directory with filename `keypairs.json`.
+        /// File with same name will be OVERWRITTEN. Directory provided has to exist.
+        #[clap(long = "destination", display_order = 1)]
+        destination: Option<String>,
+
+        /// [Optional] Encrypt the exported file with the password of pchain_client, as the keypair file is.
+        #[clap(long = "encrypted", display_order = 2)]
+        encrypted: bool,
+    },
+
+    /// Import all keypairs of a file exported by `keys export-all`, e.g. to move the keystore to another machine.
+    /// Keypairs whose names already exist are skipped.
+    #[clap(arg_required_else_help = true, display_order = 12)]
+    ImportAll {
+        /// Relative / absolute path of the exported file. A file exported with `--encrypted` is decrypted with the
+        /// password of pchain_client.
+        #[clap(long = "file", display_order = 1)]
+        file: String,
+
+        #[clap(flatten)]
+        batch_options: BatchOptions,
+    },
+
+    /// Show the full name and public key of one Keypair. The private key is never shown.
+    #[clap(arg_required_else_help = true, display_order = 13)]
+    Show {
+        /// The name to identify the Keypair.
+        #[clap(long = "keypair-name", display_order = 1)]
+        keypair_name: String,
+
+        /// [Optional] Also print the public address of the Keypair as a QR code, e.g. to share it as a receiving address.
+        #[clap(long = "qr", display_order = 2)]
+        qr: bool,
+
+        /// [Optional] Also save the public address of the Keypair as a QR code in this PNG file. File with same name will be OVERWRITTEN.
+        #[clap(long = "qr-png", display_order = 3, value_name = "PNG_FILE")]
+        qr_png: Option<String>,
+    },
+
+    /// Check every Keypair for problems: public keys shared by several names, keypairs which cannot be reconstructed,
+    /// private or public keys which do not match the keypair, and weak public keys. Exits with an error if any is found.
+    #[clap(display_order = 14)]
+    Audit,
+
+    /// Display the public key (address) of a private key, without adding a Keypair to pchain_client.
+    #[clap(arg_required_else_help = true, display_order = 15)]
+    Address {
+        /// The private key of a ParallelChain account.
+        #[clap(long = "private", display_order = 1)]
+        private_key: String,
     },
 }
 
@@ -369,6 +1180,7 @@ pub enum Parse {
     },
 
     /// Parse the return value from a Contract call and display them in human-readable form.
+    /// To decode other borsh serialized values with a schema, use `parse decode`.
     #[clap(arg_required_else_help = true, display_order = 2)]
     CallResult {
         /// The returned Base64 string from result of contract call.
@@ -377,11 +1189,12 @@ pub enum Parse {
 
         /// Common acceptable data types includes i32, i64, u8, u32, u64, bool, String, [u8;32], [u8;64].
         /// Details of supported data types can be found on https://github.com/parallelchain-io/pchain-client-cli/blob/master/example/arguments.json
+        /// Run `parse list-types` to list all supported data types.
         /// Notes:
         /// - Example values in Vec or slice: [0,1,2].
         /// - When decoding [u8;32] or [u8;64], one should wrap the type with quotation marks like "[u8;32]"
         ///
-        /// This argument cannot be used together with "schema-file".
+        /// This argument cannot be used together with "schema-file" or "schema-json".
         #[clap(
             long = "data-type",
             display_order = 2,
@@ -392,7 +1205,7 @@ pub enum Parse {
 
         /// Path to schema file for decoding the call result.
         ///
-        /// This argument cannot be used together with "data-type".
+        /// This argument cannot be used together with "data-type" or "schema-json".
         #[clap(
             long = "schema-file",
             display_order = 3,
@@ -400,6 +1213,27 @@ pub enum Parse {
             required = true
         )]
         schema_file: Option<PathBuf>,
+
+        /// Inline schema JSON for decoding the call result, e.g. '{"argument_name": "balance", "argument_type": "u64"}'.
+        ///
+        /// This argument cannot be used together with "data-type" or "schema-file".
+        #[clap(
+            long = "schema-json",
+            display_order = 4,
+            group = "gp-data-type",
+            required = true
+        )]
+        schema_json: Option<String>,
+
+        /// [Optional] Format of a result decoded by a schema.
+        #[clap(
+            long = "output-format",
+            display_order = 5,
+            value_enum,
+            default_value = "flat",
+            conflicts_with = "data-type"
+        )]
+        output_format: CallResultFormat,
     },
 
     /// Compute the contract address of a Contract in transaction.
@@ -407,9 +1241,116 @@ pub enum Parse {
     ContractAddress {
         #[clap(subcommand)]
         version: ContractAddressVersion,
+
+        /// [Optional] Encoding of the printed contract addresses.
+        #[clap(
+            long = "output-encoding",
+            global = true,
+            display_order = 90,
+            value_enum,
+            default_value = "base64url"
+        )]
+        output_encoding: OutputEncoding,
+    },
+
+    /// Serialize contract call arguments and display the resulting bytes of each argument.
+    #[clap(arg_required_else_help = true, display_order = 4)]
+    CallArguments {
+        /// Relative / absolute path of the JSON file that specifies call arguments, in the same format as `transaction create call`.
+        #[clap(long = "file", display_order = 1)]
+        file: String,
+    },
+
+    /// Inspect a raw 64-byte Ed25519 keypair and display its public key.
+    #[clap(arg_required_else_help = true, display_order = 5)]
+    KeypairInfo {
+        /// The Base64 encoded 64-byte keypair (32-byte private key followed by 32-byte public key).
+        #[clap(long = "keypair", display_order = 1, allow_hyphen_values(true))]
+        keypair: Base64String,
+
+        /// [Optional] Encoding of the printed public key.
+        #[clap(
+            long = "output-encoding",
+            display_order = 2,
+            value_enum,
+            default_value = "base64url"
+        )]
+        output_encoding: OutputEncoding,
+    },
+
+    /// Decode a borsh serialized signed Transaction (V1 or V2) and display it, without querying ParallelChain.
+    #[clap(arg_required_else_help = true, display_order = 6)]
+    Transaction {
+        /// The Base64 encoded bytes of the signed Transaction.
+        #[clap(long = "value", display_order = 1, allow_hyphen_values(true))]
+        value: Base64String,
+    },
+
+    /// List all data types supported in call arguments and call results.
+    #[clap(arg_required_else_help = false, display_order = 7)]
+    ListTypes,
+
+    /// Decode any borsh serialized value, e.g. a serialized config or a storage value, with a schema and display it in
+    /// human-readable form. It works the same as `parse call-result` with a schema, for values which are not call results.
+    #[clap(arg_required_else_help = true, display_order = 8)]
+    Decode {
+        /// The Base64 encoded borsh serialized value.
+        #[clap(long = "value", display_order = 1, allow_hyphen_values(true))]
+        value: Base64String,
+
+        /// Path to schema file describing the fields of the value, in the same format as `parse call-result --schema-file`.
+        ///
+        /// This argument cannot be used together with "schema-json".
+        #[clap(
+            long = "schema-file",
+            display_order = 2,
+            group = "gp-schema",
+            required = true
+        )]
+        schema_file: Option<PathBuf>,
+
+        /// Inline schema JSON describing the fields of the value, e.g. '{"argument_name": "balance", "argument_type": "u64"}'.
+        ///
+        /// This argument cannot be used together with "schema-file".
+        #[clap(
+            long = "schema-json",
+            display_order = 3,
+            group = "gp-schema",
+            required = true
+        )]
+        schema_json: Option<String>,
+
+        /// [Optional] Format of the decoded value.
+        #[clap(
+            long = "output-format",
+            display_order = 4,
+            value_enum,
+            default_value = "flat"
+        )]
+        output_format: CallResultFormat,
     },
 }
 
+/// Encoding of binary outputs such as signatures, public keys and contract addresses.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum OutputEncoding {
+    /// URL-safe Base64 without padding, as used throughout ParallelChain.
+    Base64url,
+    /// Standard Base64 with padding.
+    Base64,
+    /// Lowercase hexadecimal.
+    Hex,
+}
+
+/// Format of a call result decoded by a schema.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CallResultFormat {
+    /// One line per value, named by its dotted path in the schema, e.g. `Person.friends[0]: ...`.
+    Flat,
+    /// JSON of the same shape as the schema, e.g. `{"Person": {"friends": [...]}}`.
+    Nested,
+}
+
 pub enum Base64Encode {
     Encode,
     Decode,
@@ -439,6 +1380,12 @@ pub enum Validators {
         /// [Optional] Include delegator set in result.
         #[clap(long = "with-delegator", display_order = 1)]
         with_delegator: bool,
+
+        /// [Optional] Display each pool of the set with its rank, address, commission_rate, power and power_share,
+        /// i.e. its fraction of the total power of the next validator set. Delegators are not listed, so it cannot be
+        /// combined with `--with-delegator`.
+        #[clap(long = "json", display_order = 2, conflicts_with = "with-delegator")]
+        json: bool,
     },
 }
 
@@ -471,6 +1418,20 @@ pub enum ContractAddressVersion {
         #[clap(long = "deploy_cmd_index", display_order = 3)]
         index: u32,
     },
+
+    /// Parse the addresses of contracts deployed by every deploy command in a transaction file.
+    #[clap(arg_required_else_help = true, display_order = 3)]
+    FromTx {
+        /// Relative/absolute path to a Transaction file. Either a JSON file created by `transaction create`,
+        /// or a signed transaction created by `transaction create --sign-with`.
+        #[clap(long = "file", display_order = 1)]
+        file: String,
+
+        /// [Optional] Name of the keypair which signs the transaction. Required for JSON transaction files, which do not
+        /// record the signer. (Password required)
+        #[clap(long = "keypair-name", display_order = 2)]
+        keypair_name: Option<String>,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -478,7 +1439,7 @@ pub enum CreateTx {
     /// Transfer Balance from transaction signer to recipient.
     #[clap(arg_required_else_help = true, display_order = 1)]
     Transfer {
-        /// Address of the Recipient you'd like to transfer to.
+        /// Address of the Recipient you'd like to transfer to, or `@<NAME>` of an entry in the address book.
         #[clap(long = "recipient", display_order = 1, allow_hyphen_values(true))]
         recipient: Base64Address,
 
@@ -497,12 +1458,21 @@ pub enum CreateTx {
         /// Version of Contract Binary Interface.
         #[clap(long = "cbi-version", display_order = 2)]
         cbi_version: u32,
+
+        /// [Optional] Check that the CBI version is supported by ParallelChain runtime before creating the command.
+        /// Fullnode RPC does not expose the supported versions, so the check uses the versions known to this release of `pchain_client`.
+        #[clap(long = "cbi-check", display_order = 3)]
+        cbi_check: bool,
+
+        /// [Optional] Create the command even if `--cbi-check` finds the CBI version unsupported.
+        #[clap(long = "force", display_order = 4, requires = "cbi-check")]
+        force: bool,
     },
 
     /// Trigger method call of a deployed smart contract.
     #[clap(arg_required_else_help = true, display_order = 3)]
     Call {
-        /// The address of the target contract.
+        /// The address of the target contract, or `@<NAME>` of an entry in the address book.
         #[clap(long = "target", display_order = 1, allow_hyphen_values(true))]
         target: Base64Address,
 
@@ -511,12 +1481,19 @@ pub enum CreateTx {
         method: String,
 
         /// [Optional] Relative / absolute path of the JSON file that specifies arguments to be supplied to the invoked method.
+        /// In `transaction create`, this can also be a directory of JSON files. One transaction is created per file, in
+        /// order of file name, with nonces incrementing from `--nonce`. Each is saved as `<FILE_NAME>_tx.json` in the directory
+        /// given by `--destination` (default current directory).
         #[clap(long = "arguments", display_order = 3)]
         arguments: Option<String>,
 
         /// [Optional] The amount of XPLL/TXPLL (in Grays) sending to the target contract.
         #[clap(long = "amount", display_order = 4)]
         amount: Option<u64>,
+
+        /// [Optional] Also display each argument as it is understood: its type, value and serialized bytes.
+        #[clap(long = "verbose", display_order = 5)]
+        verbose: bool,
     },
 
     /// Deposit balance into a network account.
@@ -569,7 +1546,7 @@ pub enum DepositTx {
     /// Instantiation of a Deposit in an existing Pool.
     #[clap(arg_required_else_help = true, display_order = 1)]
     Create {
-        /// The address of operator of the target pool.
+        /// The address of operator of the target pool, or `@<NAME>` of an entry in the address book.
         #[clap(long = "operator", display_order = 1, allow_hyphen_values(true))]
         operator: Base64Address,
 
@@ -585,7 +1562,7 @@ pub enum DepositTx {
     /// Increase balance of an existing Deposit.
     #[clap(arg_required_else_help = true, display_order = 2)]
     TopUp {
-        /// The address of operator of the target pool.
+        /// The address of operator of the target pool, or `@<NAME>` of an entry in the address book.
         #[clap(long = "operator", display_order = 1, allow_hyphen_values(true))]
         operator: Base64Address,
 
@@ -597,7 +1574,7 @@ pub enum DepositTx {
     /// Withdraw balance from an existing Deposit.
     #[clap(arg_required_else_help = true, display_order = 3)]
     Withdraw {
-        /// The address of operator of the target pool.
+        /// The address of operator of the target pool, or `@<NAME>` of an entry in the address book.
         #[clap(long = "operator", display_order = 1, allow_hyphen_values(true))]
         operator: Base64Address,
 
@@ -611,7 +1588,7 @@ pub enum DepositTx {
     /// Update settings of an existing Deposit.
     #[clap(arg_required_else_help = true, display_order = 4)]
     UpdateSettings {
-        /// The address of operator of the target pool.
+        /// The address of operator of the target pool, or `@<NAME>` of an entry in the address book.
         #[clap(long = "operator", display_order = 1, allow_hyphen_values(true))]
         operator: Base64Address,
 
@@ -626,7 +1603,7 @@ pub enum StakeTx {
     /// Increase stakes in an existing Pool.
     #[clap(arg_required_else_help = true, display_order = 8)]
     Stake {
-        /// The address of operator of the target pool.
+        /// The address of operator of the target pool, or `@<NAME>` of an entry in the address book.
         #[clap(long = "operator", display_order = 1, allow_hyphen_values(true))]
         operator: Base64Address,
 
@@ -640,7 +1617,7 @@ pub enum StakeTx {
     /// Remove stakes from an existing Pool.
     #[clap(arg_required_else_help = true, display_order = 9)]
     Unstake {
-        /// The address of operator of the target pool.
+        /// The address of operator of the target pool, or `@<NAME>` of an entry in the address book.
         #[clap(long = "operator", display_order = 1, allow_hyphen_values(true))]
         operator: Base64Address,
 
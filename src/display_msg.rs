@@ -7,6 +7,7 @@ use pchain_types::rpc::SubmitTransactionErrorV2;
 use std::{fmt, path::PathBuf};
 
 use crate::command::{Base64Address, Base64Hash, Base64String};
+use crate::{result, utils};
 
 pub type IdentityName = String;
 pub type FileName = String;
@@ -22,6 +23,7 @@ pub enum DisplayMsg {
     FailToDecodeBase64Address(IdentityName, Base64Address, ErrorMsg),
     FailToDecodeBase64Hash(IdentityName, Base64Hash, ErrorMsg),
     FailToDecodeBase64String(IdentityName, Base64String, ErrorMsg),
+    FailToDecodeBase64Secret(IdentityName, ErrorMsg),
 
     //////////////////////////////
     // File Encode/Decode Error //
@@ -31,16 +33,21 @@ pub enum DisplayMsg {
     FailToTOMLEncode(FileName, PathBuf, ErrorMsg),
     FailToEncodeJson(FileName, PathBuf, ErrorMsg),
     FailToDecodeJson(FileName, PathBuf, ErrorMsg),
+    InvalidWasmModule(PathBuf, ErrorMsg),
+    NoCertificateInCABundle(PathBuf),
 
     ////////////////////////
     // Cli argument error //
     ///////////////////////
     IncorrectFormatForSuppliedArgument(ErrorMsg),
+    AbortedOnWarning,
+    BatchFailed(usize, usize, Vec<String>),
 
     ////////////////
     // Query Msg //
     ///////////////
     CannotFindLatestBlock,
+    CannotFindCurrentEpoch,
     CannotFindRelevantBlock,
     CannotFindRelevantBlockHeader,
     CannotFindRelevantTransaction,
@@ -50,6 +57,12 @@ pub enum DisplayMsg {
     CannotFindOperatorOwnerPair,
     CannotFindValidatorSet,
     CannotFindRelevantContractCode,
+    TransactionCommitted(Base64Hash, Base64Hash, u32),
+    TransactionNotCommitted(Base64Hash),
+    WatchTimeout(Base64Hash, u64),
+    ReceiptWaitTimeout(Base64Hash, u64),
+    FailToSubscribe(URL, ErrorMsg),
+    SubscriptionClosed(URL),
 
     /////////////////////
     // Transaction Msg //
@@ -58,26 +71,84 @@ pub enum DisplayMsg {
     FailSubmitTx(SubmitTransactionErrorV2),
     FailToParseCallArguments(ErrorMsg),
     FailToParseCallResult(ErrorMsg),
+    FailToDecodeWithSchema(ErrorMsg),
+    InvalidCallResultSchema(String, ErrorMsg),
     InvalidTxCommand(ErrorMsg),
+    FailToDecodeSignedTx(PathBuf, ErrorMsg),
+    FailToDeserializeTransaction(ErrorMsg),
+    SignerNotFound(PathBuf),
+    NoSigner,
+    DuplicateSigner(IdentityName),
+    MultipleSignersNotSupported(usize),
+    NoDeployCommand(PathBuf),
+    UnsupportedCBIVersion(u32, ErrorMsg),
+    BaseFeeBelowMinimum(u64, u64),
+    ForcedBaseFeeBelowMinimum(u64, u64),
+    MaxFeeOverflow(u64, u64, u64),
+    TxCostOverflow(u64, u64),
+    NonceOverflow(u64, u64),
+    MismatchedTransactionVersion(PathBuf, PathBuf),
+    UnexpectedTxNonce(u64, u64),
+    StaleTxNonce(u64, u64),
+    UnexpectedSigner(String, Base64Address, Base64Address),
+    InvalidTxAddresses(Vec<String>),
+    TxAlreadySubmitted(Base64Hash, PathBuf),
+    EmptyTransaction,
 
     ////////////////
     // Config Msg //
     ////////////////
     PChainCliHomeNotSet(URL),
     InavtiveRPCProvider(URL),
+    UnsupportedWebSocketRPCProvider(URL),
+    NotWebSocketRPCProvider(URL),
     ActiveRPCProvider(URL),
     ListRPCProvider(URL),
-    NotYetSetRPCProvider,
+    ListWebSocketRPCProvider(URL),
+    ListProxy(URL),
+    ListCACert(PathBuf),
+    ListPath(String, PathBuf, bool),
+    NotYetSetRPCProvider(PathBuf),
+    NotYetSetWebSocketRPCProvider(PathBuf),
+    ListGasDefault(String, u64),
+    SuccessSetGasDefaults,
+    MissingGasParameter(String),
+    DetectedTxVersion(String),
+    InvalidQueryBatch(PathBuf, ErrorMsg),
+    FailToRunQueryBatch(String, ErrorMsg),
+    QueryContext(URL),
+    SuccessAddContact(IdentityName, Base64Address),
+    SuccessRemoveContact(IdentityName),
+    ContactAlreadyExists(IdentityName),
+    ContactNotFound(IdentityName),
+    InvalidContactName(IdentityName),
 
     /////////////////
     // keypair msg //
     /////////////////
     SuccessCreateKey(IdentityName, Base64Address),
     SuccessAddKey(IdentityName),
+    DryRunKeypair(IdentityName, Base64Address),
+    MnemonicShownOnce(String),
+    InvalidMnemonic(ErrorMsg),
     KeypairAlreadyExists(IdentityName),
     KeypairNotFound(IdentityName),
     InvalidEd25519Keypair(ErrorMsg),
     FailToSignMessage(ErrorMsg),
+    FailToRunSignerCommand(String, ErrorMsg),
+    ExternalKeypairNotExportable(IdentityName),
+    PlaintextPrivateKeysExported,
+    SuccessExportAll(usize, String),
+    SkipExistingKeypair(IdentityName),
+    InvalidImportedKeypair(IdentityName, ErrorMsg),
+    SuccessImportAll(usize, usize, usize),
+    KeypairAuditIssue(IdentityName, ErrorMsg),
+    DuplicatePublicKey(Vec<IdentityName>, Base64Address),
+    KeypairAuditPassed(usize),
+    KeypairAuditFailed(usize, usize),
+    ValidSignature,
+    InvalidSignature(Base64Address),
+    IncorrectSignatureLength,
     ParseKeypairFailure(serde_json::Error),
 
     /////////////////
@@ -86,23 +157,38 @@ pub enum DisplayMsg {
     FailToOpenOrReadFile(FileName, PathBuf, ErrorMsg),
     FailToWriteFile(FileName, PathBuf, ErrorMsg),
     FailToCreateDir(IdentityName, PathBuf, ErrorMsg),
+    HomeDirNotWritable(PathBuf, String, ErrorMsg),
     FailToCreateFile(FileName, PathBuf, ErrorMsg),
     IncorrectFilePath(FileName, PathBuf, ErrorMsg),
     SuccessCreateFile(FileName, PathBuf),
     SuccessUpdateFile(FileName, PathBuf),
+    FailToVerifyExportedKeypair(PathBuf, ErrorMsg),
+    FailToCreateQRCode(ErrorMsg),
 
     ////////////////////
     // HTTP Error Msg //
     ////////////////////
     RespnoseWithHTTPError(ErrorMsg),
+    RequestTiming(String, u128),
+    RawResponse(String, Base64String),
+    RetryingRequest(String, String, u32, u32),
+    DeadlineExceeded(u64),
+    StatusOk,
+    StatusError(i32),
 
     //////////////////
     // Password Msg //
     //////////////////
     WrongPassword,
+    FailToReadPasswordFromStdin(ErrorMsg),
     PasswordFilesContaminated,
     SuccessSetupPassword,
+    SuccessChangePassword,
+    CompletedPasswordChange,
+    UndonePasswordChange,
     PasswordNotMatch,
+    WeakPassword(ErrorMsg),
+    WeakPasswordNotConfirmed,
     FailToSetupPassword(ErrorMsg),
     FailtoEncrypt(ErrorMsg),
     FailtoDecrypt(ErrorMsg),
@@ -131,6 +217,8 @@ impl fmt::Display for DisplayMsg {
                 write!(f, "Error: Fail to decode \"{identity}\" hash \"{hash}\" from a Base64URL string. {error}"),
             DisplayMsg::FailToDecodeBase64String(identity, base64_string, error) =>
                 write!(f, "Error: Fail to decode \"{identity}\" \"{base64_string}\" from a Base64URL string. {error}"),
+            DisplayMsg::FailToDecodeBase64Secret(identity, error) =>
+                write!(f, "Error: Fail to decode \"{identity}\" from a Base64URL string. {error}"),
 
             //////////////////////////////
             // File Encode/Decode Error //
@@ -145,24 +233,46 @@ impl fmt::Display for DisplayMsg {
                 write!(f, "Error: Cannot encode {file_name} file at {:?} to json format. {error}", path),
             DisplayMsg::FailToDecodeJson(file_name, path, error) =>
                 write!(f, "Error: Cannot decode provided {file_name} json file at {:?} to desired shape. {error}", path),
+            DisplayMsg::InvalidWasmModule(path, error) =>
+                write!(f, "Error: Provided contract file at {:?} is not a valid WebAssembly module. {error}", path),
+            DisplayMsg::NoCertificateInCABundle(path) =>
+                write!(f, "Error: Provided CA certificate file at {:?} does not contain any PEM certificate.", path),
 
             ////////////////////////
             // Cli argument error //
             ///////////////////////
             DisplayMsg::IncorrectFormatForSuppliedArgument(error) =>
             write!(f, "Error: Supplied argument is of incorrect format. It should be in form of (\"{}\" ).", error),
+            DisplayMsg::AbortedOnWarning =>
+                write!(f, "Error: Aborted on the warning above because of `--abort-on-warning`."),
+            DisplayMsg::BatchFailed(failed, total, items) =>
+                write!(f, "Error: {failed} of {total} items failed: {}.", items.join(", ")),
 
             ////////////////
             // Query Msg //
             ///////////////
             DisplayMsg::CannotFindLatestBlock =>
                 write!(f, "Error: Cannot find latest block."),
+            DisplayMsg::CannotFindCurrentEpoch =>
+                write!(f, "Error: Cannot find the current epoch in the network state."),
             DisplayMsg::CannotFindRelevantBlock =>
                 write!(f, "Error: Cannot find relevant block."),
             DisplayMsg::CannotFindRelevantBlockHeader =>
                 write!(f, "Error: Cannot find relevant block header"),
             DisplayMsg::CannotFindRelevantTransaction =>
                 write!(f, "Error: Cannot find relevant transaction."),
+            DisplayMsg::TransactionCommitted(tx_hash, block_hash, position) =>
+                write!(f, "Transaction {tx_hash} is committed in block {block_hash} at position {position}."),
+            DisplayMsg::TransactionNotCommitted(tx_hash) =>
+                write!(f, "Transaction {tx_hash} is not yet committed."),
+            DisplayMsg::WatchTimeout(tx_hash, timeout) =>
+                write!(f, "Error: Transaction {tx_hash} is not committed after {timeout} seconds."),
+            DisplayMsg::ReceiptWaitTimeout(tx_hash, timeout) =>
+                write!(f, "Error: No receipt for transaction {tx_hash} after {timeout} seconds. Check it later with `./pchain_client query receipt --hash {tx_hash}`."),
+            DisplayMsg::FailToSubscribe(url, error) =>
+                write!(f, "Error: Fail to subscribe to new blocks from <{url}>. {error}"),
+            DisplayMsg::SubscriptionClosed(url) =>
+                write!(f, "Error: The subscription to new blocks from <{url}> is closed."),
             DisplayMsg::CannotFindRelevantReceipt =>
                 write!(f, "Error: Cannot find relevant receipt."),
             DisplayMsg::CannotFindRelevantState =>
@@ -182,20 +292,65 @@ impl fmt::Display for DisplayMsg {
             DisplayMsg::SuccessSubmitTx =>
                 write!(f, "Transaction is submitted to ParallelChain but not completely get through yet. Check explorer or wallet for updated status."),
             DisplayMsg::FailSubmitTx(error) => {
-                match error {
-                    SubmitTransactionErrorV2::NonceLTCommitted => write!(f, "Error: Submit Transaction Fail. Nonce is lower than the committed nonce."),
-                    SubmitTransactionErrorV2::BaseFeePerGasTooLow => write!(f, "Error: Submit Transaction Fail. Base fee is too low."),
-                    SubmitTransactionErrorV2::MempoolIsFull => write!(f, "Error: Submit Transaction Fail. Mempool is full."),
-                    SubmitTransactionErrorV2::TransactionVersionTooOld => write!(f, "Error: Submit Transaction Fail. Transaction version is too old."),
-                    SubmitTransactionErrorV2::Other => write!(f, "Error: Submit Transaction Fail. Please ensure gas limit, nonce, or transaction size is within range."),
-                    }
+                let reason = match error {
+                    SubmitTransactionErrorV2::NonceLTCommitted => "Nonce is lower than the committed nonce.",
+                    SubmitTransactionErrorV2::BaseFeePerGasTooLow => "Base fee is too low.",
+                    SubmitTransactionErrorV2::MempoolIsFull => "Mempool is full.",
+                    SubmitTransactionErrorV2::TransactionVersionTooOld => "Transaction version is too old.",
+                    SubmitTransactionErrorV2::Other => "Please ensure gas limit, nonce, or transaction size is within range.",
+                };
+                write!(f, "Error: Submit Transaction Fail. {}\nNode error: {:?}", reason, error)
             },
             DisplayMsg::FailToParseCallArguments(e) =>
                 write!(f, "Error: Cannot parse contract call arguments of the transaction. {}", e),
             DisplayMsg::FailToParseCallResult(e) =>
                 write!(f, "Error: Cannot parse call result. {}", e),
+            DisplayMsg::FailToDecodeWithSchema(e) =>
+                write!(f, "Error: Cannot decode the value with the schema. {}", e),
+            DisplayMsg::InvalidCallResultSchema(path, e) =>
+                write!(f, "Error: Invalid call result schema at `{}`. {}", path, e),
             DisplayMsg::InvalidTxCommand(error) =>
                 write!(f, "Error: Invalid transaction command. {}", error),
+            DisplayMsg::FailToDecodeSignedTx(path, e) =>
+                write!(f, "Error: Fail to decode signed transaction from <{:?}>. {e}", path),
+            DisplayMsg::FailToDeserializeTransaction(error) =>
+                write!(f, "Error: Fail to deserialize the bytes into a signed transaction. {error}"),
+            DisplayMsg::NoSigner =>
+                write!(f, "Error: No keypair is given to sign the transaction."),
+            DisplayMsg::DuplicateSigner(keypair_name) =>
+                write!(f, "Error: Keypair {keypair_name} signs the transaction more than once. Please give each signer only once."),
+            DisplayMsg::MultipleSignersNotSupported(signers) =>
+                write!(f, "Error: All {signers} keypairs which sign the transaction are valid, but a ParallelChain transaction can only carry one signature at present. Transaction is not signed."),
+            DisplayMsg::SignerNotFound(path) =>
+                write!(f, "Error: Transaction file <{:?}> does not record the signer. Please specify the signer with `--keypair-name`.", path),
+            DisplayMsg::NoDeployCommand(path) =>
+                write!(f, "Error: Transaction file <{:?}> does not contain any deploy command.", path),
+            DisplayMsg::UnsupportedCBIVersion(cbi_version, supported) =>
+                write!(f, "Warning: CBI version {cbi_version} is not supported by ParallelChain runtime (supported: {supported}). The deploy command will fail on chain. Use `--force` to create the transaction anyway."),
+            DisplayMsg::BaseFeeBelowMinimum(max_base_fee_per_gas, min_base_fee_per_gas) =>
+                write!(f, "Error: max_base_fee_per_gas ({max_base_fee_per_gas}) is below the minimum of {min_base_fee_per_gas} Grays. The transaction would never be included in a block. Use `--allow-low-base-fee` to proceed anyway."),
+            DisplayMsg::ForcedBaseFeeBelowMinimum(max_base_fee_per_gas, min_base_fee_per_gas) =>
+                write!(f, "Warning: max_base_fee_per_gas ({max_base_fee_per_gas}) is below the minimum of {min_base_fee_per_gas} Grays. The transaction may never be included in a block."),
+            DisplayMsg::MaxFeeOverflow(gas_limit, max_base_fee_per_gas, priority_fee_per_gas) =>
+                write!(f, "Error: The maximum fee, gas_limit ({gas_limit}) * (max_base_fee_per_gas ({max_base_fee_per_gas}) + priority_fee_per_gas ({priority_fee_per_gas})), does not fit in 64 bits. The transaction could never be paid for."),
+            DisplayMsg::TxCostOverflow(amount, max_fee) =>
+                write!(f, "Error: The amount ({amount}) plus the maximum fee ({max_fee}) is larger than any balance can be. The transaction could never be paid for."),
+            DisplayMsg::NonceOverflow(nonce, offset) =>
+                write!(f, "Error: The nonce ({nonce}) plus the position of the transaction in the batch ({offset}) does not fit in 64 bits."),
+            DisplayMsg::MismatchedTransactionVersion(into, from) =>
+                write!(f, "Error: Transaction files <{:?}> and <{:?}> are of different transaction versions and cannot be merged.", into, from),
+            DisplayMsg::UnexpectedTxNonce(nonce, expected_nonce) =>
+                write!(f, "Error: Nonce of the transaction ({nonce}) is not the expected nonce ({expected_nonce}). Transaction is not submitted."),
+            DisplayMsg::StaleTxNonce(nonce, committed_nonce) =>
+                write!(f, "Error: Nonce of the transaction ({nonce}) does not match the signer's nonce on chain ({committed_nonce}). Transaction is not submitted."),
+            DisplayMsg::TxAlreadySubmitted(tx_hash, ledger_path) =>
+                write!(f, "Transaction {tx_hash} is already submitted according to the ledger at <{:?}>. It is not submitted again.", ledger_path),
+            DisplayMsg::InvalidTxAddresses(addresses) =>
+                write!(f, "Error: The transaction file contains invalid addresses. Transaction is not submitted.\n{}", addresses.join("\n")),
+            DisplayMsg::EmptyTransaction =>
+                write!(f, "Error: The transaction has no commands, and would only spend gas. Add a command with `transaction append` first."),
+            DisplayMsg::UnexpectedSigner(keypair_name, signer, expected_signer) =>
+                write!(f, "Error: Keypair <{keypair_name}> has address {signer}, not the expected signer {expected_signer}. Transaction is not signed."),
 
             ////////////////
             // Config Msg //
@@ -204,12 +359,50 @@ impl fmt::Display for DisplayMsg {
                 write!(f, "enviroment variable ${home} isn't set. Please specify the home folder of ParallelChain Client CLI"),
             DisplayMsg::InavtiveRPCProvider(url) =>
                 write!(f, "Warning: The chosen provider <{}> is currently not active. Please switch to another active provider by `setup` command.", url),
+            DisplayMsg::UnsupportedWebSocketRPCProvider(url) =>
+                write!(f, "Error: <{}> is a WebSocket endpoint, which only serves subscriptions. Please set up an http:// or https:// URL with `--url`, and the WebSocket endpoint with `--ws-url`.", url),
+            DisplayMsg::NotWebSocketRPCProvider(url) =>
+                write!(f, "Error: <{}> is not a WebSocket endpoint. Please set up a ws:// or wss:// URL with `--ws-url`.", url),
             DisplayMsg::ActiveRPCProvider(url) =>
                 write!(f, "Provider <{url}> is Active"),
             DisplayMsg::ListRPCProvider(url) =>
                 write!(f, "Fullnode RPC Provider is <{url}>"),
-            DisplayMsg::NotYetSetRPCProvider =>
-                write!(f, "Warning: Fullnode RPC url is not setup. \nPlease use command `./pchain_client config setup --url <URL>` to specify the node to connect."),
+            DisplayMsg::ListWebSocketRPCProvider(url) =>
+                write!(f, "Fullnode RPC subscriptions are received from <{url}>"),
+            DisplayMsg::ListProxy(url) =>
+                write!(f, "Requests are sent through proxy <{url}>"),
+            DisplayMsg::ListCACert(path) =>
+                write!(f, "HTTPS connections trust CA certificates in <{:?}>", path),
+            DisplayMsg::ListPath(name, path, exists) =>
+                write!(f, "{name}: {}{}", path.display(), if *exists { "" } else { " (not found)" }),
+            DisplayMsg::NotYetSetRPCProvider(config_path) =>
+                write!(f, "Warning: Fullnode RPC url is not setup in config file <{:?}>. \nPlease use command `./pchain_client config setup --url <URL>` to specify the node to connect.", config_path),
+            DisplayMsg::NotYetSetWebSocketRPCProvider(config_path) =>
+                write!(f, "Error: WebSocket RPC url is not setup in config file <{:?}>. \nPlease use command `./pchain_client config setup --ws-url <URL>` to specify the node to subscribe to.", config_path),
+            DisplayMsg::ListGasDefault(name, value) =>
+                write!(f, "Default --{name}: {value}"),
+            DisplayMsg::SuccessSetGasDefaults =>
+                write!(f, "Successfully save the default gas parameters."),
+            DisplayMsg::MissingGasParameter(name) =>
+                write!(f, "Error: `--{name}` is not given and has no default. Please give it, or save a default by `./pchain_client config set-defaults --{name} <VALUE>`."),
+            DisplayMsg::DetectedTxVersion(version) =>
+                write!(f, "Creating Transaction {version}, the version used by the latest block of the network."),
+            DisplayMsg::InvalidQueryBatch(path, error) =>
+                write!(f, "Error: Invalid query batch file <{:?}>. {error}", path),
+            DisplayMsg::FailToRunQueryBatch(name, error) =>
+                write!(f, "Error: Fail to run query `{name}` of the batch. {error}"),
+            DisplayMsg::QueryContext(url) =>
+                write!(f, "Context: querying Fullnode RPC Provider <{url}>"),
+            DisplayMsg::SuccessAddContact(name, address) =>
+                write!(f, "Successfully add @{name} with address <{address}> to the address book."),
+            DisplayMsg::SuccessRemoveContact(name) =>
+                write!(f, "Successfully remove @{name} from the address book."),
+            DisplayMsg::ContactAlreadyExists(name) =>
+                write!(f, "Error: @{name} already exists in the address book. Please remove it first by `./pchain_client config contacts remove --name {name}`."),
+            DisplayMsg::ContactNotFound(name) =>
+                write!(f, "Error: @{name} is not found in the address book. Please add it by `./pchain_client config contacts add --name {name} --address <ADDRESS>`."),
+            DisplayMsg::InvalidContactName(name) =>
+                write!(f, "Error: Invalid address book name \"{name}\". Only letters, digits, `-` and `_` are allowed."),
             /////////////////
             // keypair msg //
             /////////////////
@@ -217,6 +410,12 @@ impl fmt::Display for DisplayMsg {
                 write!(f, "Successfully create {keypair_name} with public key <{pk}>" ),
             DisplayMsg::SuccessAddKey(keypair_name) =>
                 write!(f, "Successfully add keypair with name {keypair_name}." ),
+            DisplayMsg::DryRunKeypair(keypair_name, pk) =>
+                write!(f, "Dry run: {keypair_name} with public key <{pk}> is valid. It is not saved."),
+            DisplayMsg::MnemonicShownOnce(phrase) =>
+                write!(f, "Mnemonic phrase: {phrase}\nWarning: The mnemonic phrase is shown only this once and is not saved. Write it down and keep it safe. Anyone with it can restore the keypair."),
+            DisplayMsg::InvalidMnemonic(error) =>
+                write!(f, "Error: Invalid mnemonic phrase. {error}"),
             DisplayMsg::KeypairAlreadyExists(keypair_name) =>
                 write!(f, "Error: Keypair with name {keypair_name} already exists."), 
             DisplayMsg::KeypairNotFound(keypair_name) =>
@@ -228,6 +427,34 @@ impl fmt::Display for DisplayMsg {
             `./pchain_client keys add --private-key <PRIVATE_KEY> --public-key <PUBLIC_KEY> --keypair-name <KEYPAIR_NAME>` to re-import your keys"),
             DisplayMsg::FailToSignMessage(error) =>
                 write!(f, "Error: Fail to sign message by provided keypair. {error}"),
+            DisplayMsg::FailToRunSignerCommand(command, error) =>
+                write!(f, "Error: Fail to sign by the signer command <{command}> of the external keypair. {error}"),
+            DisplayMsg::ExternalKeypairNotExportable(keypair_name) =>
+                write!(f, "Error: Keypair {keypair_name} is signed by an external signer command and has no private key to export."),
+            DisplayMsg::PlaintextPrivateKeysExported =>
+                write!(f, "Warning: The exported file contains the private keys of all keypairs in plain text. Anyone who reads it controls these accounts. Please keep it safe, or export with `--encrypted`."),
+            DisplayMsg::SuccessExportAll(exported, saved_path) =>
+                write!(f, "{exported} keypairs are saved at {saved_path}"),
+            DisplayMsg::SkipExistingKeypair(keypair_name) =>
+                write!(f, "Warning: Keypair with name {keypair_name} already exists. It is skipped."),
+            DisplayMsg::InvalidImportedKeypair(keypair_name, error) =>
+                write!(f, "Error: Keypair with name {keypair_name} is invalid. {error}"),
+            DisplayMsg::SuccessImportAll(imported, existing, invalid) =>
+                write!(f, "Imported {imported}, skipped {existing} (already exist) and {invalid} (invalid)."),
+            DisplayMsg::KeypairAuditIssue(keypair_name, error) =>
+                write!(f, "Keypair {keypair_name}: {error}"),
+            DisplayMsg::DuplicatePublicKey(keypair_names, public_key) =>
+                write!(f, "Keypairs {} have the same public key {public_key}.", keypair_names.join(", ")),
+            DisplayMsg::KeypairAuditPassed(keypairs) =>
+                write!(f, "No issue is found in {keypairs} keypairs."),
+            DisplayMsg::KeypairAuditFailed(issues, keypairs) =>
+                write!(f, "Error: {issues} issue(s) are found in {keypairs} keypairs."),
+            DisplayMsg::ValidSignature =>
+                write!(f, "Signature is valid."),
+            DisplayMsg::InvalidSignature(public_key) =>
+                write!(f, "Error: Signature verification failed. It is not a signature of the message by public key <{public_key}>."),
+            DisplayMsg::IncorrectSignatureLength =>
+                write!(f, "Error: Incorrect length of signature. Correct length should be 64 bytes long."),
             /////////////////
             // File IO Msg //
             /////////////////
@@ -239,18 +466,36 @@ impl fmt::Display for DisplayMsg {
                 write!(f, "Error: Fail to write {file_name} file at <{:?}> although file is found. {:#?}", path, error),
             DisplayMsg::FailToCreateDir(file_name, path, error) =>
                 write!(f, "Error: Fail to create necessary directory for {file_name} file at <{:?}>. {:#?}", path, error),
+            DisplayMsg::HomeDirNotWritable(path, env_key, error) =>
+                write!(f, "Error: ParallelChain Client home directory <{:?}> is not writable. {error}\nPlease set ${env_key} to a writable directory, e.g. a volume mounted read-write in a container.", path),
             DisplayMsg::FailToCreateFile(file_name, path, error) =>
                 write!(f, "Error: Fail to create {file_name} file at <{:?}>. {:#?}", path, error),
             DisplayMsg::SuccessCreateFile(file_name, path) =>
                 write!(f, "Successfully create {file_name} file at <{:?}>.", path),
             DisplayMsg::SuccessUpdateFile(file_name, path) =>
                 write!(f, "Successfully update {file_name} file at <{:?}>.", path),
+            DisplayMsg::FailToVerifyExportedKeypair(path, error) =>
+                write!(f, "Error: Keypair file written at <{:?}> cannot be read back as the exported keypair. Please do not rely on it as a backup. {error}", path),
+            DisplayMsg::FailToCreateQRCode(error) =>
+                write!(f, "Error: Fail to create QR code. {}", error),
 
             ////////////////////
             // HTTP Error Msg //
             ////////////////////
             DisplayMsg::RespnoseWithHTTPError(error) =>
                 write!(f, "{error}"),
+            DisplayMsg::RequestTiming(request, elapsed_ms) =>
+                write!(f, "Timing: {request} took {elapsed_ms} ms"),
+            DisplayMsg::RawResponse(request, response) =>
+                write!(f, "Raw response of {request} (Base64url): {response}"),
+            DisplayMsg::RetryingRequest(request, error, attempt, retries) =>
+                write!(f, "Warning: {request} failed ({error}). Retrying ({attempt}/{retries})..."),
+            DisplayMsg::DeadlineExceeded(deadline) =>
+                write!(f, "Error: Command did not finish within the deadline of {deadline} seconds. Pending requests are cancelled."),
+            DisplayMsg::StatusOk =>
+                write!(f, "STATUS ok"),
+            DisplayMsg::StatusError(code) =>
+                write!(f, "STATUS error code={code}"),
 
 
             //////////////////
@@ -258,12 +503,24 @@ impl fmt::Display for DisplayMsg {
             //////////////////
             DisplayMsg::WrongPassword =>
                 write!(f, "Error: Wrong password. Fail to login."),
+            DisplayMsg::FailToReadPasswordFromStdin(error) =>
+                write!(f, "Error: Fail to read password from stdin. {error}"),
             DisplayMsg::PasswordFilesContaminated =>
                 write!(f, "Error: Irrecoverable error. Password files contaminted."),
             DisplayMsg::PasswordNotMatch =>
                 write!(f, "Error: Password not match"),
+            DisplayMsg::WeakPassword(reasons) =>
+                write!(f, "Warning: The password is weak. It is {reasons}."),
+            DisplayMsg::WeakPasswordNotConfirmed =>
+                write!(f, "Error: Password setup is cancelled. Please run the command again and choose a stronger password."),
             DisplayMsg::SuccessSetupPassword =>
                 write!(f, "Password is set. Please keep your password safe. You will be required to provide this password to submit transaction and manage keypairs later."),
+            DisplayMsg::SuccessChangePassword =>
+                write!(f, "Password is changed. The keypair file is re-encrypted with the new password."),
+            DisplayMsg::CompletedPasswordChange =>
+                write!(f, "An interrupted password change is completed. Please use the new password."),
+            DisplayMsg::UndonePasswordChange =>
+                write!(f, "An interrupted password change is undone. Please keep using the current password."),
             DisplayMsg::FailToSetupPassword(error) =>
                 write!(f, "Error: Fail to setup your password. {:#?}", error),
             DisplayMsg::FailtoEncrypt(error) =>
@@ -291,3 +548,74 @@ impl fmt::Display for DisplayMsg {
         }
     }
 }
+
+impl DisplayMsg {
+    /// The exit code of the CLI when it stops with this message. Things that cannot be found on ParallelChain
+    /// exit with [NOT_FOUND_EXIT_CODE], so that scripts can tell them apart from failed commands, which exit with 1.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            DisplayMsg::CannotFindLatestBlock
+            | DisplayMsg::CannotFindCurrentEpoch
+            | DisplayMsg::CannotFindRelevantBlock
+            | DisplayMsg::CannotFindRelevantBlockHeader
+            | DisplayMsg::CannotFindRelevantTransaction
+            | DisplayMsg::CannotFindRelevantReceipt
+            | DisplayMsg::CannotFindRelevantState
+            | DisplayMsg::CannotFindOperator
+            | DisplayMsg::CannotFindOperatorOwnerPair
+            | DisplayMsg::CannotFindValidatorSet
+            | DisplayMsg::CannotFindRelevantContractCode
+            | DisplayMsg::TransactionNotCommitted(_) => NOT_FOUND_EXIT_CODE,
+            _ => 1,
+        }
+    }
+
+    /// Print the message and exit with its [exit code](DisplayMsg::exit_code).
+    /// Within `query batch`, the message is handed back to the batch as the error of its query instead.
+    pub fn exit(self) -> ! {
+        if result::is_capturing_results() {
+            std::panic::resume_unwind(Box::new(self));
+        }
+        println!("{}", self);
+        utils::exit(self.exit_code())
+    }
+}
+
+/// Exit code of a command which finds nothing on ParallelChain, e.g. an unknown block or transaction. Code 2 is
+/// taken by clap for usage errors.
+pub const NOT_FOUND_EXIT_CODE: i32 = 3;
+
+#[cfg(test)]
+mod test {
+    use super::{DisplayMsg, NOT_FOUND_EXIT_CODE};
+    use pchain_types::rpc::SubmitTransactionErrorV2;
+
+    #[test]
+    fn test_exit_code() {
+        assert_eq!(DisplayMsg::CannotFindRelevantTransaction.exit_code(), 3);
+        assert_eq!(
+            DisplayMsg::CannotFindOperatorOwnerPair.exit_code(),
+            NOT_FOUND_EXIT_CODE
+        );
+        assert_eq!(
+            DisplayMsg::TransactionNotCommitted(String::from("AAAA")).exit_code(),
+            NOT_FOUND_EXIT_CODE
+        );
+        assert_eq!(
+            DisplayMsg::RespnoseWithHTTPError("connection refused".to_string()).exit_code(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_fail_submit_tx_shows_node_error() {
+        assert_eq!(
+            DisplayMsg::FailSubmitTx(SubmitTransactionErrorV2::Other).to_string(),
+            "Error: Submit Transaction Fail. Please ensure gas limit, nonce, or transaction size is within range.\nNode error: Other"
+        );
+        assert_eq!(
+            DisplayMsg::FailSubmitTx(SubmitTransactionErrorV2::MempoolIsFull).to_string(),
+            "Error: Submit Transaction Fail. Mempool is full.\nNode error: MempoolIsFull"
+        );
+    }
+}
@@ -5,41 +5,43 @@
 
 //! Data structures which convert pchain_types::Block to a form which can be displayed on the terminal.
 
-use crate::display_types::{CommandReceipt, QuorumCertificate, Transaction};
+use crate::display_types::{QuorumCertificate, Transaction};
 use serde::Serialize;
 
-use super::Receipt;
+use super::{receipt_from_v1, Receipt};
 
 /// [Block] denotes a display_type equivalent of pchain_types::blockchain::Block
 #[derive(Serialize, Debug)]
 pub struct Block {
     pub header: BlockHeader,
     pub transactions: Vec<Transaction>,
-    pub receipts: Vec<Receipt>,
+    /// None if the receipts are left out of the output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipts: Option<Vec<Receipt>>,
 }
 
 impl From<pchain_types::blockchain::BlockV1> for Block {
     fn from(block: pchain_types::blockchain::BlockV1) -> Block {
-        let txs_beautified: Vec<Transaction> = block
-            .transactions
-            .into_iter()
-            .map(From::<pchain_types::blockchain::TransactionV1>::from)
-            .collect();
+        let transactions = &block.transactions;
         let receipt_beautified: Vec<Receipt> = block
             .receipts
             .into_iter()
-            .map(|protocol_type_receipt| {
-                protocol_type_receipt
-                    .into_iter()
-                    .map(CommandReceipt::from)
-                    .collect()
+            .enumerate()
+            .map(|(index, protocol_type_receipt)| {
+                let commands = transactions.get(index).map(|tx| &tx.commands[..]);
+                receipt_from_v1(protocol_type_receipt, commands)
             })
             .collect();
+        let txs_beautified: Vec<Transaction> = block
+            .transactions
+            .into_iter()
+            .map(From::<pchain_types::blockchain::TransactionV1>::from)
+            .collect();
 
         Block {
             header: From::<pchain_types::blockchain::BlockHeaderV1>::from(block.header),
             transactions: txs_beautified,
-            receipts: receipt_beautified,
+            receipts: Some(receipt_beautified),
         }
     }
 }
@@ -66,7 +68,7 @@ impl From<pchain_types::blockchain::BlockV2> for Block {
         Block {
             header: From::<pchain_types::blockchain::BlockHeaderV2>::from(block.header),
             transactions: txs_beautified,
-            receipts: receipt_beautified,
+            receipts: Some(receipt_beautified),
         }
     }
 }
@@ -121,3 +123,23 @@ impl From<pchain_types::blockchain::BlockHeaderV2> for BlockHeader {
         }
     }
 }
+
+/// [Epoch] denotes the current epoch of the network state, together with the block which it was read at.
+#[derive(Serialize, Debug)]
+pub struct Epoch {
+    pub epoch: u64,
+    pub height: u64,
+    pub block_hash: String,
+    pub timestamp: u32,
+}
+
+impl Epoch {
+    pub fn new(header: BlockHeader, epoch: u64) -> Epoch {
+        Epoch {
+            epoch,
+            height: header.height,
+            block_hash: header.block_hash,
+            timestamp: header.timestamp,
+        }
+    }
+}
@@ -5,9 +5,11 @@
 
 //! Data structures which convert pchain_types::Receipt to a format which can be displayed on the terminal.
 
-use pchain_types::blockchain::{CommandReceiptV2, ExitCodeV2};
+use pchain_types::blockchain::{Command, CommandReceiptV1, CommandReceiptV2, ExitCodeV2};
 use serde::Serialize;
 
+use crate::result::display_options;
+
 /// [Event] denotes a display_types equivalent of pchain_types::blockchain::Log.
 #[derive(Serialize, Debug)]
 pub struct Event {
@@ -17,14 +19,56 @@ pub struct Event {
 
 pub type Receipt = Vec<CommandReceipt>;
 
+/// `total_gas_used` sums up the gas used by every command in a receipt.
+pub fn total_gas_used(receipt: &[CommandReceipt]) -> u64 {
+    receipt.iter().map(CommandReceipt::gas_used).sum()
+}
+
+/// [ReceiptWithTotalGas] is a Receipt together with the total gas used by its commands, displayed as one JSON object.
+#[derive(Serialize, Debug)]
+pub struct ReceiptWithTotalGas {
+    pub receipt: Receipt,
+    pub total_gas_used: u64,
+}
+
+impl From<Receipt> for ReceiptWithTotalGas {
+    fn from(receipt: Receipt) -> ReceiptWithTotalGas {
+        ReceiptWithTotalGas {
+            total_gas_used: total_gas_used(&receipt),
+            receipt,
+        }
+    }
+}
+
 #[derive(Serialize, Debug)]
 pub enum CommandReceipt {
     V1(V1Receipt),
     V2(V2Receipt),
 }
 
+impl CommandReceipt {
+    /// Exit code of the command, as displayed in the receipt.
+    pub fn exit_code(&self) -> &str {
+        match self {
+            CommandReceipt::V1(r) => &r.exit_code,
+            CommandReceipt::V2(r) => &r.exit_code,
+        }
+    }
+
+    /// Gas consumed by the command.
+    pub fn gas_used(&self) -> u64 {
+        match self {
+            CommandReceipt::V1(r) => r.gas_used,
+            CommandReceipt::V2(r) => r.gas_used,
+        }
+    }
+}
+
 #[derive(Serialize, Debug)]
 pub struct V1Receipt {
+    pub command_index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
     pub exit_code: String,
     pub gas_used: u64,
     pub return_values: String,
@@ -44,21 +88,82 @@ impl From<pchain_types::blockchain::CommandReceiptV1> for CommandReceipt {
         let exit_code = format!("{:?}", receipt.exit_code);
 
         CommandReceipt::V1(V1Receipt {
+            command_index: 0,
+            command: None,
             exit_code,
             gas_used: receipt.gas_used,
-            return_values: if !receipt.return_values.is_empty() {
-                format!(
-                    "(Base64 encoded) {}",
-                    base64url::encode(&receipt.return_values)
-                )
-            } else {
-                "".to_string()
-            },
+            return_values: format_return_values(
+                &receipt.return_values,
+                display_options().raw_return_values,
+            ),
             logs: events_beautified,
         })
     }
 }
 
+/// `receipt_from_v1` converts the command receipts of a TransactionV1 to a [Receipt]. V1 command receipts do not
+/// tell which command they belong to, so each one is numbered by its position and, if the commands of the
+/// Transaction are given, named after the command at the same position.
+pub fn receipt_from_v1(
+    command_receipts: Vec<CommandReceiptV1>,
+    commands: Option<&[Command]>,
+) -> Receipt {
+    command_receipts
+        .into_iter()
+        .enumerate()
+        .map(
+            |(index, command_receipt)| match CommandReceipt::from(command_receipt) {
+                CommandReceipt::V1(receipt) => CommandReceipt::V1(V1Receipt {
+                    command_index: index,
+                    command: commands
+                        .and_then(|commands| commands.get(index))
+                        .map(|command| command_name(command).to_string()),
+                    ..receipt
+                }),
+                receipt => receipt,
+            },
+        )
+        .collect()
+}
+
+// `command_name` returns the name of a command, as it is named in the Transaction.
+//  # Arguments
+//  * `command` - command of a Transaction
+//
+fn command_name(command: &Command) -> &'static str {
+    match command {
+        Command::Transfer(_) => "Transfer",
+        Command::Deploy(_) => "Deploy",
+        Command::Call(_) => "Call",
+        Command::CreatePool(_) => "CreatePool",
+        Command::SetPoolSettings(_) => "SetPoolSettings",
+        Command::DeletePool => "DeletePool",
+        Command::CreateDeposit(_) => "CreateDeposit",
+        Command::SetDepositSettings(_) => "SetDepositSettings",
+        Command::TopUpDeposit(_) => "TopUpDeposit",
+        Command::WithdrawDeposit(_) => "WithdrawDeposit",
+        Command::StakeDeposit(_) => "StakeDeposit",
+        Command::UnstakeDeposit(_) => "UnstakeDeposit",
+        Command::NextEpoch => "NextEpoch",
+    }
+}
+
+// `format_return_values` displays the return values of a command as a base64url string, labelled
+//  "(Base64 encoded)" unless `raw` is set. Empty return values are displayed as an empty string.
+//  # Arguments
+//  * `return_values` - return values of a command receipt
+//  * `raw` - whether to leave out the label, e.g. under `--raw-return-values`
+//
+fn format_return_values(return_values: &[u8], raw: bool) -> String {
+    if return_values.is_empty() {
+        String::new()
+    } else if raw {
+        base64url::encode(return_values)
+    } else {
+        format!("(Base64 encoded) {}", base64url::encode(return_values))
+    }
+}
+
 #[derive(Serialize, Debug)]
 pub struct V2Receipt {
     pub exit_code: String,
@@ -83,13 +188,10 @@ impl V2Receipt {
     }
 
     fn return_values(mut self, return_values: Vec<u8>) -> Self {
-        let str = if !return_values.is_empty() {
-            format!("(Base64 encoded) {}", base64url::encode(&return_values))
-        } else {
-            "".to_string()
-        };
-
-        self.return_values = Some(str);
+        self.return_values = Some(format_return_values(
+            &return_values,
+            display_options().raw_return_values,
+        ));
         self
     }
 
@@ -140,3 +242,79 @@ impl From<pchain_types::blockchain::CommandReceiptV2> for CommandReceipt {
         CommandReceipt::V2(receipt)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{format_return_values, receipt_from_v1, CommandReceipt, ReceiptWithTotalGas};
+    use pchain_types::blockchain::{Command, CommandReceiptV1, ExitCodeV1};
+    use pchain_types::runtime::TransferInput;
+
+    #[test]
+    fn test_receipt_from_v1() {
+        let command_receipt = CommandReceiptV1 {
+            exit_code: ExitCodeV1::Success,
+            gas_used: 100,
+            logs: Vec::new(),
+            return_values: Vec::new(),
+        };
+        let commands = [
+            Command::Transfer(TransferInput {
+                recipient: [0u8; 32],
+                amount: 1,
+            }),
+            Command::NextEpoch,
+        ];
+
+        let receipt = receipt_from_v1(
+            vec![command_receipt.clone(), command_receipt.clone()],
+            Some(&commands),
+        );
+        let names: Vec<(usize, Option<String>)> = receipt
+            .iter()
+            .map(|command_receipt| match command_receipt {
+                CommandReceipt::V1(r) => (r.command_index, r.command.clone()),
+                CommandReceipt::V2(_) => unreachable!(),
+            })
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                (0, Some(String::from("Transfer"))),
+                (1, Some(String::from("NextEpoch")))
+            ]
+        );
+
+        let receipt = receipt_from_v1(vec![command_receipt], None);
+        let json = serde_json::to_value(&receipt).unwrap();
+        assert_eq!(json[0]["V1"]["command_index"], 0);
+        assert!(json[0]["V1"].get("command").is_none());
+    }
+
+    #[test]
+    fn test_receipt_with_total_gas() {
+        let command_receipt = CommandReceiptV1 {
+            exit_code: ExitCodeV1::Success,
+            gas_used: 100,
+            logs: Vec::new(),
+            return_values: Vec::new(),
+        };
+        let receipt = receipt_from_v1(vec![command_receipt.clone(), command_receipt], None);
+
+        let json = serde_json::to_value(ReceiptWithTotalGas::from(receipt)).unwrap();
+        assert_eq!(json["total_gas_used"], 200);
+        assert_eq!(json["receipt"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_format_return_values() {
+        let encoded = base64url::encode([1u8, 2, 3]);
+
+        assert_eq!(
+            format_return_values(&[1, 2, 3], false),
+            format!("(Base64 encoded) {}", encoded)
+        );
+        assert_eq!(format_return_values(&[1, 2, 3], true), encoded);
+        assert_eq!(format_return_values(&[], true), "");
+        assert_eq!(format_return_values(&[], false), "");
+    }
+}
@@ -122,12 +122,48 @@ impl From<pchain_types::rpc::PoolWithDelegators> for PoolWithDelegators {
     }
 }
 
+/// [FlatPool] displays a pool with the same fields whether or not its stakes are included.
+/// `delegator_count` is None if the stakes are not included.
+#[derive(Serialize, Debug)]
+pub struct FlatPool {
+    pub operator: Base64String,
+    pub commission_rate: u8,
+    pub power: u64,
+    pub operator_stake_power: Option<u64>,
+    pub delegator_count: Option<usize>,
+}
+
+impl From<pchain_types::rpc::Pool> for FlatPool {
+    fn from(pool: pchain_types::rpc::Pool) -> FlatPool {
+        match pool {
+            pchain_types::rpc::Pool::WithStakes(p) => FlatPool {
+                operator: base64url::encode(p.operator),
+                commission_rate: p.commission_rate,
+                power: p.power,
+                operator_stake_power: p.operator_stake.map(|stake| stake.power),
+                delegator_count: Some(p.delegated_stakes.len()),
+            },
+            pchain_types::rpc::Pool::WithoutStakes(p) => FlatPool {
+                operator: base64url::encode(p.operator),
+                commission_rate: p.commission_rate,
+                power: p.power,
+                operator_stake_power: p.operator_stake.map(|stake| stake.power),
+                delegator_count: None,
+            },
+        }
+    }
+}
+
 /// [NextValidator] displays information of validator selected
 /// for the next epoch on ParallelChain.
-#[derive(Serialize, Debug)]
+/// `rank` starts from 1 for the pool with the most power, and `power_share` is the fraction of the total power of the set.
+#[derive(Serialize, Debug, PartialEq)]
 pub struct NextValidator {
+    pub rank: usize,
     pub address: Base64String,
+    pub commission_rate: u8,
     pub power: u64,
+    pub power_share: f64,
 }
 
 /// [ValidatorSet] displays information of validator set
@@ -160,3 +196,86 @@ impl From<pchain_types::rpc::ValidatorSet> for ValidatorSet {
         }
     }
 }
+
+impl ValidatorSet {
+    /// Pools of the validator set ordered by power, each with its share of the total power of the set.
+    pub fn next_validators(&self) -> Vec<NextValidator> {
+        let mut pools: Vec<(&Base64String, u8, u64)> = match self {
+            ValidatorSet::WithDelegators(pools) => pools
+                .iter()
+                .map(|pool| (&pool.operator, pool.commission_rate, pool.power))
+                .collect(),
+            ValidatorSet::WithoutDelegators(pools) => pools
+                .iter()
+                .map(|pool| (&pool.operator, pool.commission_rate, pool.power))
+                .collect(),
+        };
+        pools.sort_by_key(|(_, _, power)| std::cmp::Reverse(*power));
+
+        let total_power: u128 = pools.iter().map(|(_, _, power)| *power as u128).sum();
+        pools
+            .into_iter()
+            .enumerate()
+            .map(
+                |(index, (operator, commission_rate, power))| NextValidator {
+                    rank: index + 1,
+                    address: operator.clone(),
+                    commission_rate,
+                    power,
+                    power_share: if total_power == 0 {
+                        0.0
+                    } else {
+                        power as f64 / total_power as f64
+                    },
+                },
+            )
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{NextValidator, PoolWithoutDelegators, ValidatorSet};
+
+    #[test]
+    fn test_next_validators() {
+        let pool = |operator: &str, power: u64| PoolWithoutDelegators {
+            operator: operator.to_string(),
+            commission_rate: 5,
+            power,
+            operator_stake: None,
+        };
+        let validator_set =
+            ValidatorSet::WithoutDelegators(vec![pool("a", 100), pool("b", 300), pool("c", 0)]);
+
+        assert_eq!(
+            validator_set.next_validators(),
+            vec![
+                NextValidator {
+                    rank: 1,
+                    address: "b".to_string(),
+                    commission_rate: 5,
+                    power: 300,
+                    power_share: 0.75,
+                },
+                NextValidator {
+                    rank: 2,
+                    address: "a".to_string(),
+                    commission_rate: 5,
+                    power: 100,
+                    power_share: 0.25,
+                },
+                NextValidator {
+                    rank: 3,
+                    address: "c".to_string(),
+                    commission_rate: 5,
+                    power: 0,
+                    power_share: 0.0,
+                },
+            ]
+        );
+        assert!(ValidatorSet::WithoutDelegators(Vec::new())
+            .next_validators()
+            .is_empty());
+    }
+}
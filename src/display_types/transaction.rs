@@ -6,11 +6,13 @@
 //! Data structures which convert pchain_types::Transaction to a format which can be displayed on the terminal.
 
 use dunce;
-use pchain_types::cryptography::SignatureBytes;
+use pchain_types::cryptography::PublicAddress;
+use pchain_types::serialization::Serializable;
 use pchain_types::{blockchain::Command, runtime::*};
 use serde::Serialize;
 use serde_json::json;
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 use std::convert::TryFrom;
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
@@ -19,10 +21,11 @@ use crate::command::Base64String;
 use crate::config::get_keypair_path;
 use crate::display_msg::DisplayMsg;
 use crate::display_types::{Event, TxCommand};
-use crate::keypair::get_keypair_from_json;
+use crate::keypair::{load_existing_keypairs, sign_externally, signing_key, KeypairJSON};
+use crate::parser::{base64url_to_public_address, decode_base64url};
 use crate::utils::{read_file, read_file_to_utf8string};
 
-use super::Receipt;
+use super::{receipt_from_v1, total_gas_used, Receipt};
 
 /// [Transaction] denotes a display_types equivalent of pchain_types::blockchain::Transaction.
 #[derive(Serialize, Debug)]
@@ -199,11 +202,19 @@ pub struct SubmitTx {
     pub gas_limit: u64,
     pub max_base_fee_per_gas: u64,
     pub priority_fee_per_gas: u64,
+    /// Note for bookkeeping, kept in the file only and never part of the signed transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Names of further keypairs which sign the transaction, reserved for multi-signer transactions. They are
+    /// checked when signing, but cannot be signed with yet.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub co_signers: Vec<String>,
 }
 
 impl SubmitTx {
     // `to_json_file` serializes SubmitTx into json format and write to a file
     pub fn to_json_file(&self, file_path: &str) -> Result<String, DisplayMsg> {
+        self.validate_commands()?;
         let path = Path::new(&file_path);
         if path.extension() != Some(OsStr::new("json")) {
             return Err(DisplayMsg::IncorrectFilePath(String::from("transaction json"), path.to_path_buf(), String::from("Path provided should include the file name and file extension. i.e. example.json")));
@@ -251,6 +262,41 @@ impl SubmitTx {
             .unwrap())
     }
 
+    // `validate_commands` checks that the transaction has at least one command. A transaction without
+    // commands does nothing but spend gas.
+    pub fn validate_commands(&self) -> Result<(), DisplayMsg> {
+        if self.commands.is_empty() {
+            Err(DisplayMsg::EmptyTransaction)
+        } else {
+            Ok(())
+        }
+    }
+
+    // `validate_addresses` checks that the address of every command decodes, and reports all invalid
+    // addresses together with the index of their command, e.g. after the file is edited by hand.
+    pub fn validate_addresses(&self) -> Result<(), DisplayMsg> {
+        let invalid_addresses: Vec<String> = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(index, command)| {
+                let (field, address) = command.address()?;
+                let reason = match decode_base64url(address) {
+                    Ok(bytes) if bytes.len() == 32 => return None,
+                    Ok(bytes) => format!("decodes to {} bytes instead of 32", bytes.len()),
+                    Err(_) => String::from("is not valid Base64url"),
+                };
+                Some(format!("Command {index}: {field} <{address}> {reason}"))
+            })
+            .collect();
+
+        if invalid_addresses.is_empty() {
+            Ok(())
+        } else {
+            Err(DisplayMsg::InvalidTxAddresses(invalid_addresses))
+        }
+    }
+
     // `from_json_file` accepts a path to the json file and returns a
     // serde serializable/deserializable struct for processing submission of Transactions
     // to ParallelChain.
@@ -298,40 +344,70 @@ impl SubmitTx {
     //  * `priority_fee_per_gas` - XPLL/TXPLL to tip to the proposing Validator
     //  * `max_base_fee_per_gas` - XPLL/TXPLL you are willing to pay per unit Gas consumed in the execution of your
     //                             transaction (in Grays). This needs to be greater than your Account balance for your transaction to be included in a block
-    //  * `keypair_name` - Name of the keypair
+    //  * `keypair_names` - Names of the keypairs which sign the transaction, followed by the co-signers in the file.
+    //                      Each keypair has to exist and decode, but only a single signer can sign at present.
     pub fn prepare_signed_tx(
         self,
-        keypair_name: &str,
+        keypair_names: &[String],
     ) -> Result<pchain_types::rpc::TransactionV1OrV2, DisplayMsg> {
-        let keypair_json_of_given_user =
-            match get_keypair_from_json(get_keypair_path(), keypair_name) {
-                Ok(Some(s)) => s,
-                Ok(None) => return Err(DisplayMsg::KeypairNotFound(String::from(keypair_name))),
-                Err(e) => {
-                    return Err(e);
-                }
-            };
+        let mut signers = self.validate_signers(keypair_names)?;
+        if signers.len() > 1 {
+            return Err(DisplayMsg::MultipleSignersNotSupported(signers.len()));
+        }
 
-        let keypair_bs = match base64url::decode(&keypair_json_of_given_user.keypair) {
-            Ok(kp) => kp,
-            Err(e) => {
-                return Err(DisplayMsg::FailToDecodeBase64String(
-                    String::from("keypair"),
-                    keypair_json_of_given_user.keypair,
-                    e.to_string(),
-                ));
-            }
-        };
+        match signers.pop() {
+            Some(signer) => self.sign(signer),
+            None => Err(DisplayMsg::NoSigner),
+        }
+    }
 
-        let keypair = match ed25519_dalek::SigningKey::from_keypair_bytes(
-            &SignatureBytes::try_from(keypair_bs).unwrap(),
-        ) {
-            Ok(kp) => kp,
-            Err(e) => {
-                println!("{}", DisplayMsg::InvalidEd25519Keypair(e.to_string()));
-                std::process::exit(1);
+    // `validate_signers` checks that the keypair of every signer, i.e. the given ones and the co-signers in the
+    //  file, exists and decodes, and that no keypair signs twice. The keypair file is read once for all of them.
+    //  # Arguments
+    //  * `keypair_names` - Names of the keypairs which sign the transaction
+    //
+    fn validate_signers(&self, keypair_names: &[String]) -> Result<Vec<TxSigner>, DisplayMsg> {
+        let keypairs = load_existing_keypairs(get_keypair_path())?;
+
+        let mut signers = vec![];
+        let mut public_keys: Vec<&str> = vec![];
+        for keypair_name in keypair_names.iter().chain(&self.co_signers) {
+            let keypair_json = match keypairs.iter().find(|kp| &kp.name == keypair_name) {
+                Some(kp) => kp,
+                None => return Err(DisplayMsg::KeypairNotFound(keypair_name.to_string())),
+            };
+            if public_keys.contains(&keypair_json.public_key.as_str()) {
+                return Err(DisplayMsg::DuplicateSigner(keypair_name.to_string()));
             }
-        };
+            public_keys.push(&keypair_json.public_key);
+            signers.push(signing_keypair(keypair_json.clone())?);
+        }
+        Ok(signers)
+    }
+
+    // `sign_with_keypair` signs the transaction with a keypair already read from the keypair file, e.g. when
+    //  its address is checked before signing, so that the password is asked only once.
+    //  # Arguments
+    //  * `keypair` - the keypair read from the keypair file
+    //
+    pub fn sign_with_keypair(
+        self,
+        keypair: KeypairJSON,
+    ) -> Result<pchain_types::rpc::TransactionV1OrV2, DisplayMsg> {
+        if !self.co_signers.is_empty() {
+            return Err(DisplayMsg::MultipleSignersNotSupported(
+                1 + self.co_signers.len(),
+            ));
+        }
+        self.sign(signing_keypair(keypair)?)
+    }
+
+    // `sign` signs the transaction with the given keypair.
+    //  # Arguments
+    //  * `signer` - keypair of the signer
+    //
+    fn sign(self, signer: TxSigner) -> Result<pchain_types::rpc::TransactionV1OrV2, DisplayMsg> {
+        self.validate_commands()?;
 
         let mut commands = vec![];
         for c in self.commands {
@@ -341,6 +417,26 @@ impl SubmitTx {
             }
         }
 
+        let keypair = match signer {
+            TxSigner::Local(keypair) => keypair,
+            TxSigner::External(keypair, address) => {
+                return Self::sign_by_signer_command(
+                    self.is_v1,
+                    pchain_types::blockchain::TransactionV2 {
+                        signer: address,
+                        nonce: self.nonce,
+                        commands,
+                        gas_limit: self.gas_limit,
+                        max_base_fee_per_gas: self.max_base_fee_per_gas,
+                        priority_fee_per_gas: self.priority_fee_per_gas,
+                        signature: [0u8; 64],
+                        hash: [0u8; 32],
+                    },
+                    &keypair,
+                )
+            }
+        };
+
         if self.is_v1 {
             Ok(pchain_types::rpc::TransactionV1OrV2::V1(
                 pchain_types::blockchain::TransactionV1::new(
@@ -365,6 +461,61 @@ impl SubmitTx {
             ))
         }
     }
+
+    // `sign_by_signer_command` signs an unsigned transaction by the signer command of an external keypair. As in a
+    //  transaction signed by a local keypair, the signature is over the serialized transaction with zeroed signature
+    //  and hash, and the hash is the SHA256 hash of the signature.
+    //  # Arguments
+    //  * `is_v1` - whether to sign the transaction as TransactionV1
+    //  * `tx` - the unsigned transaction
+    //  * `keypair` - external keypair of the signer
+    //
+    fn sign_by_signer_command(
+        is_v1: bool,
+        tx: pchain_types::blockchain::TransactionV2,
+        keypair: &KeypairJSON,
+    ) -> Result<pchain_types::rpc::TransactionV1OrV2, DisplayMsg> {
+        if is_v1 {
+            let mut tx = pchain_types::blockchain::TransactionV1 {
+                signer: tx.signer,
+                nonce: tx.nonce,
+                commands: tx.commands,
+                gas_limit: tx.gas_limit,
+                max_base_fee_per_gas: tx.max_base_fee_per_gas,
+                priority_fee_per_gas: tx.priority_fee_per_gas,
+                signature: tx.signature,
+                hash: tx.hash,
+            };
+            tx.signature = sign_externally(keypair, &tx.serialize())?;
+            tx.hash = Sha256::digest(tx.signature).into();
+            Ok(pchain_types::rpc::TransactionV1OrV2::V1(tx))
+        } else {
+            let mut tx = tx;
+            tx.signature = sign_externally(keypair, &tx.serialize())?;
+            tx.hash = Sha256::digest(tx.signature).into();
+            Ok(pchain_types::rpc::TransactionV1OrV2::V2(tx))
+        }
+    }
+}
+
+/// [TxSigner] signs transactions, either with the private key in the keypair file or, for a keypair added by
+/// `keys import-external`, by its signer command.
+enum TxSigner {
+    Local(ed25519_dalek::SigningKey),
+    External(KeypairJSON, PublicAddress),
+}
+
+// `signing_keypair` decodes a keypair from the keypair file into a key which signs transactions.
+//  # Arguments
+//  * `keypair_json_of_given_user` - the keypair read from the keypair file
+//
+fn signing_keypair(keypair_json_of_given_user: KeypairJSON) -> Result<TxSigner, DisplayMsg> {
+    if keypair_json_of_given_user.signer_command.is_some() {
+        let address = base64url_to_public_address(&keypair_json_of_given_user.public_key)?;
+        return Ok(TxSigner::External(keypair_json_of_given_user, address));
+    }
+
+    signing_key(&keypair_json_of_given_user).map(TxSigner::Local)
 }
 
 // `check_contract_exist` returns contract codeas a vector of bytes.
@@ -376,20 +527,23 @@ impl SubmitTx {
 pub fn check_contract_exist(path: &str) -> Result<String, DisplayMsg> {
     if path.ends_with(".wasm") {
         match dunce::canonicalize(path) {
-            Ok(canonicalized_path) => Ok(canonicalized_path
-                .into_os_string()
-                .into_string()
-                .unwrap_or_else(|_| {
-                    panic!(
-                        "{}",
-                        DisplayMsg::IncorrectFilePath(
-                            String::from("contract"),
-                            PathBuf::from(path),
-                            String::from("The path contains invalid unicode data"),
+            Ok(canonicalized_path) => {
+                validate_wasm_header(&canonicalized_path)?;
+                Ok(canonicalized_path
+                    .into_os_string()
+                    .into_string()
+                    .unwrap_or_else(|_| {
+                        panic!(
+                            "{}",
+                            DisplayMsg::IncorrectFilePath(
+                                String::from("contract"),
+                                PathBuf::from(path),
+                                String::from("The path contains invalid unicode data"),
+                            )
+                            .to_string()
                         )
-                        .to_string()
-                    )
-                })),
+                    }))
+            }
             Err(e) => Err(DisplayMsg::IncorrectFilePath(
                 String::from("contract"),
                 PathBuf::from(path),
@@ -405,6 +559,35 @@ pub fn check_contract_exist(path: &str) -> Result<String, DisplayMsg> {
     }
 }
 
+// `validate_wasm_header` checks that the file starts with the WebAssembly magic bytes and
+//  a supported binary format version, so that a corrupted or non-wasm file is rejected before
+//  it is deployed.
+//  # Arguments
+//  * `path` - canonicalized path to .wasm file
+fn validate_wasm_header(path: &Path) -> Result<(), DisplayMsg> {
+    let code = read_file(path.to_path_buf()).map_err(|e| {
+        DisplayMsg::FailToOpenOrReadFile(String::from("contract"), path.to_path_buf(), e)
+    })?;
+
+    if code.len() < 8 || code[0..4] != WASM_MAGIC {
+        return Err(DisplayMsg::InvalidWasmModule(
+            path.to_path_buf(),
+            String::from("The file does not start with the WebAssembly magic bytes."),
+        ));
+    }
+    if code[4..8] != WASM_VERSION {
+        return Err(DisplayMsg::InvalidWasmModule(
+            path.to_path_buf(),
+            format!(
+                "Unsupported WebAssembly binary version {}.",
+                u32::from_le_bytes([code[4], code[5], code[6], code[7]])
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
 // `read_contract_code` returns contract codeas a vector of bytes.
 //  # Arguments
 //  * `path` - absolute path to .wasm file or contract bytecode encoded as a Base64URL encoded string
@@ -429,6 +612,7 @@ pub fn read_contract_code(path: &str) -> Result<Vec<u8>, DisplayMsg> {
 pub struct TransactionWithReceipt {
     pub transaction: Transaction,
     pub receipt: Receipt,
+    pub total_gas_used: u64,
 }
 
 impl
@@ -443,15 +627,11 @@ impl
             pchain_types::blockchain::ReceiptV1,
         ),
     ) -> TransactionWithReceipt {
-        let receipt: Receipt = receipt
-            .iter()
-            .map(|command_receipt| {
-                From::<pchain_types::blockchain::CommandReceiptV1>::from(command_receipt.clone())
-            })
-            .collect();
+        let receipt: Receipt = receipt_from_v1(receipt, Some(&tx.commands));
 
         TransactionWithReceipt {
             transaction: From::<pchain_types::blockchain::TransactionV1>::from(tx),
+            total_gas_used: total_gas_used(&receipt),
             receipt,
         }
     }
@@ -479,22 +659,52 @@ impl
 
         TransactionWithReceipt {
             transaction: From::<pchain_types::blockchain::TransactionV2>::from(tx),
+            total_gas_used: total_gas_used(&receipt),
             receipt,
         }
     }
 }
 
+/// [TransactionDetail] is a consolidated report of a Transaction, combining the Transaction, its
+/// position on ParallelChain and the header of the containing Block.
+#[derive(Serialize, Debug)]
+pub struct TransactionDetail {
+    pub transaction_hash: Base64String,
+    pub known: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<Base64String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_height: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_codes: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_gas_used: Option<u64>,
+}
+
 impl From<pchain_types::blockchain::Log> for Event {
     fn from(event: pchain_types::blockchain::Log) -> Event {
         Event {
-            topic: match Base64String::from_utf8(event.topic.clone()) {
-                Ok(string_value) => format!("(UTF8) {}", string_value),
-                Err(_) => format!("(Base64 encoded) {}", base64url::encode(&event.topic)),
-            },
-            value: match Base64String::from_utf8(event.value.clone()) {
-                Ok(string_value) => format!("(UTF8) {}", string_value),
-                Err(_) => format!("(Base64 encoded) {}", base64url::encode(&event.value)),
-            },
+            topic: utf8_or_base64(event.topic),
+            value: utf8_or_base64(event.value),
         }
     }
 }
+
+/// `utf8_or_base64` displays bytes as a string labelled "(UTF8)" if they are valid UTF-8, or
+/// as a base64url string labelled "(Base64 encoded)" otherwise.
+pub fn utf8_or_base64(bytes: Vec<u8>) -> String {
+    match String::from_utf8(bytes) {
+        Ok(string_value) => format!("(UTF8) {}", string_value),
+        Err(e) => format!("(Base64 encoded) {}", base64url::encode(e.as_bytes())),
+    }
+}
+
+/// Magic bytes (`\0asm`) at the start of every WebAssembly binary module
+const WASM_MAGIC: [u8; 4] = [0x00, 0x61, 0x73, 0x6d];
+
+/// WebAssembly binary format version supported by ParallelChain
+const WASM_VERSION: [u8; 4] = [0x01, 0x00, 0x00, 0x00];
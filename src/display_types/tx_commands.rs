@@ -8,6 +8,7 @@ use crate::command::Base64String;
 use crate::display_msg::DisplayMsg;
 use crate::display_types::read_contract_code;
 use crate::parser::{base64url_to_public_address, call_arguments_from_json_array};
+use crate::utils;
 use pchain_types::{blockchain::Command, cryptography::PublicAddress, runtime::*};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -65,6 +66,118 @@ pub enum TxCommand {
     NextEpoch,
 }
 
+impl TxCommand {
+    /// Amount (in Grays) that the command takes from the balance of the signer, not counting gas.
+    pub fn spent_amount(&self) -> u64 {
+        match self {
+            TxCommand::Call { amount, .. } => amount.unwrap_or(0),
+            TxCommand::Transfer { amount, .. } | TxCommand::TopUpDeposit { amount, .. } => *amount,
+            TxCommand::CreateDeposit { balance, .. } => *balance,
+            _ => 0,
+        }
+    }
+
+    /// Plain-English description of what the command does on chain, for `transaction submit --explain`.
+    pub fn describe(&self) -> String {
+        let on_off = |enabled: &bool| if *enabled { "on" } else { "off" };
+        match self {
+            TxCommand::Call {
+                target,
+                method,
+                arguments,
+                amount,
+            } => format!(
+                "Call method `{}` of contract {} with {} argument(s), sending {} Grays to the contract",
+                method,
+                target,
+                arguments.as_ref().map_or(0, |arguments| arguments.len()),
+                amount.unwrap_or(0)
+            ),
+            TxCommand::Deploy {
+                contract,
+                cbi_version,
+            } => format!(
+                "Deploy the contract {} (CBI version {}) to a new address",
+                contract, cbi_version
+            ),
+            TxCommand::Transfer { recipient, amount } => {
+                format!("Transfer {} Grays to {}", amount, recipient)
+            }
+            TxCommand::CreatePool { commission_rate } => format!(
+                "Create a pool operated by the signer, with commission rate {}%",
+                commission_rate
+            ),
+            TxCommand::DeletePool => String::from(
+                "Delete the pool operated by the signer, together with the stakes in the pool",
+            ),
+            TxCommand::SetPoolSettings { commission_rate } => format!(
+                "Change the commission rate of the pool operated by the signer to {}%",
+                commission_rate
+            ),
+            TxCommand::CreateDeposit {
+                operator,
+                balance,
+                auto_stake_rewards,
+            } => format!(
+                "Deposit {} Grays to the pool of operator {}, with auto stake rewards {}",
+                balance,
+                operator,
+                on_off(auto_stake_rewards)
+            ),
+            TxCommand::SetDepositSettings {
+                operator,
+                auto_stake_rewards,
+            } => format!(
+                "Turn auto stake rewards {} for the deposit to the pool of operator {}",
+                on_off(auto_stake_rewards),
+                operator
+            ),
+            TxCommand::TopUpDeposit { operator, amount } => format!(
+                "Add {} Grays to the deposit to the pool of operator {}",
+                amount, operator
+            ),
+            TxCommand::WithdrawDeposit {
+                operator,
+                max_amount,
+            } => format!(
+                "Withdraw up to {} Grays from the deposit to the pool of operator {} back to the balance of the signer",
+                max_amount, operator
+            ),
+            TxCommand::StakeDeposit {
+                operator,
+                max_amount,
+            } => format!(
+                "Stake up to {} Grays of the deposit to the pool of operator {}",
+                max_amount, operator
+            ),
+            TxCommand::UnstakeDeposit {
+                operator,
+                max_amount,
+            } => format!(
+                "Unstake up to {} Grays of the deposit to the pool of operator {}",
+                max_amount, operator
+            ),
+            TxCommand::NextEpoch => String::from("Advance the blockchain to the next epoch"),
+        }
+    }
+
+    /// Name and value of the address field of the command, i.e. the target of a call, the recipient of a
+    /// transfer or the operator of a deposit.
+    pub fn address(&self) -> Option<(&'static str, &Base64String)> {
+        match self {
+            TxCommand::Call { target, .. } => Some(("target", target)),
+            TxCommand::Transfer { recipient, .. } => Some(("recipient", recipient)),
+            TxCommand::CreateDeposit { operator, .. }
+            | TxCommand::SetDepositSettings { operator, .. }
+            | TxCommand::TopUpDeposit { operator, .. }
+            | TxCommand::WithdrawDeposit { operator, .. }
+            | TxCommand::StakeDeposit { operator, .. }
+            | TxCommand::UnstakeDeposit { operator, .. } => Some(("operator", operator)),
+            _ => None,
+        }
+    }
+}
+
 impl TryFrom<TxCommand> for Command {
     type Error = String;
 
@@ -93,7 +206,7 @@ impl TryFrom<TxCommand> for Command {
                         Ok(result) => result,
                         Err(e) => {
                             println!("{}", DisplayMsg::FailToParseCallArguments(e.to_string()));
-                            std::process::exit(1);
+                            utils::exit(1);
                         }
                     });
 
@@ -10,6 +10,8 @@ use std::{fs::File, path::PathBuf};
 
 use crate::config::{get_home_dir, get_keypair_path};
 use crate::display_msg::DisplayMsg;
+use crate::mnemonic;
+use crate::parser::decode_base64url;
 use crate::utils;
 
 /// [KeypairJSON] wraps around serde serializable/deserializable
@@ -21,6 +23,10 @@ pub struct KeypairJSON {
     pub private_key: String,
     pub public_key: String,
     pub keypair: String,
+    /// Command which signs on behalf of a keypair added by `keys import-external`, e.g. through an HSM.
+    /// Such a keypair has no private key in the keypair file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer_command: Option<String>,
 }
 
 // `setup_keypair_file` sets up a keypair file on the defalt keypair path
@@ -61,7 +67,7 @@ pub fn setup_keypair_file() {
     }
     if let Err(e) = load_existing_keypairs(get_keypair_path()) {
         println!("{}", e);
-        std::process::exit(1);
+        utils::exit(1);
     }
 }
 
@@ -94,6 +100,33 @@ pub fn get_keypair_from_json(
 //
 pub fn load_existing_keypairs(
     path_to_keypair_json: PathBuf,
+) -> Result<Vec<KeypairJSON>, DisplayMsg> {
+    load_keypairs(path_to_keypair_json, utils::decrypt)
+}
+
+// `load_existing_keypairs_with_passphrase` reads the keypairs on file to a list as `load_existing_keypairs`,
+//  with a passphrase already given by login instead of logging in again.
+//  # Arguments
+//  * `path_to_keypair_json` - path to keypair JSON file
+//  * `encoded_passphrase` - Base64url encoded password returned by login
+//
+pub fn load_existing_keypairs_with_passphrase(
+    path_to_keypair_json: PathBuf,
+    encoded_passphrase: &str,
+) -> Result<Vec<KeypairJSON>, DisplayMsg> {
+    load_keypairs(path_to_keypair_json, |encrypt_bytes| {
+        utils::decrypt_with_passphrase(encrypt_bytes, encoded_passphrase.to_string())
+    })
+}
+
+// `load_keypairs` reads the keypairs on file to a list, decrypting the file with `decrypt`.
+//  # Arguments
+//  * `path_to_keypair_json` - path to keypair JSON file
+//  * `decrypt` - decrypts the content of a non-empty keypair file
+//
+fn load_keypairs(
+    path_to_keypair_json: PathBuf,
+    decrypt: impl FnOnce(&[u8]) -> Result<Vec<u8>, DisplayMsg>,
 ) -> Result<Vec<KeypairJSON>, DisplayMsg> {
     let keypair_base64_string = if path_to_keypair_json.is_file() {
         match utils::read_file(path_to_keypair_json.clone()) {
@@ -101,7 +134,7 @@ pub fn load_existing_keypairs(
                 if encrypt_bytes.is_empty() {
                     return Ok(Vec::new());
                 }
-                let json = utils::decrypt(&encrypt_bytes)?;
+                let json = decrypt(&encrypt_bytes)?;
                 match serde_json::from_slice::<Vec<KeypairJSON>>(&json) {
                     Ok(kp_json_vec) => kp_json_vec,
                     Err(e) => return Err(DisplayMsg::ParseKeypairFailure(e)),
@@ -138,15 +171,48 @@ pub fn generate_keypair(keypair_name: &str) -> KeypairJSON {
     let mut chacha20_rng = ChaCha20Rng::from_rng(&mut osrng).unwrap();
     let keypair = pchain_types::cryptography::Keypair::generate(&mut chacha20_rng);
 
-    let secret = keypair.as_bytes();
-    let verifying = keypair.verifying_key();
-    let public = verifying.as_bytes();
+    keypair_json(keypair_name, &keypair)
+}
+
+// `generate_mnemonic_keypair` generates a new keypair derived from a new 24-word mnemonic phrase, which is
+//  returned together with the keypair. The phrase is not saved in the keypair.
+//  # Arguments
+//  * `keypair_name` - name of the keypair saved on the JSON file
+//
+pub fn generate_mnemonic_keypair(keypair_name: &str) -> (KeypairJSON, String) {
+    let phrase = mnemonic::generate_mnemonic();
+    let keypair = mnemonic::signing_key_from_mnemonic(&phrase);
+
+    (keypair_json(keypair_name, &keypair), phrase)
+}
 
+// `restore_mnemonic_keypair` restores a keypair created by `keys create --mnemonic` from its mnemonic phrase.
+//  # Arguments
+//  * `phrase` - 24-word mnemonic phrase
+//  * `keypair_name` - name of the keypair saved on the JSON file
+//
+pub fn restore_mnemonic_keypair(
+    phrase: &str,
+    keypair_name: &str,
+) -> Result<KeypairJSON, DisplayMsg> {
+    let phrase = mnemonic::normalize_mnemonic(phrase).map_err(DisplayMsg::InvalidMnemonic)?;
+    let keypair = mnemonic::signing_key_from_mnemonic(&phrase);
+
+    Ok(keypair_json(keypair_name, &keypair))
+}
+
+// `keypair_json` converts an Ed25519 keypair to its serde serializable deserialzable form.
+//  # Arguments
+//  * `keypair_name` - name of the keypair saved on the JSON file
+//  * `keypair` - the Ed25519 keypair
+//
+fn keypair_json(keypair_name: &str, keypair: &ed25519_dalek::SigningKey) -> KeypairJSON {
     KeypairJSON {
         name: keypair_name.to_string(),
-        private_key: base64url::encode(secret),
-        public_key: base64url::encode(public),
+        private_key: base64url::encode(keypair.as_bytes()),
+        public_key: base64url::encode(keypair.verifying_key().as_bytes()),
         keypair: base64url::encode(keypair.to_keypair_bytes()),
+        signer_command: None,
     }
 }
 
@@ -161,7 +227,7 @@ pub fn add_keypair(
     public_key: &str,
     keypair_name: &str,
 ) -> Result<KeypairJSON, DisplayMsg> {
-    let mut sender_public_key = match base64url::decode(&public_key) {
+    let mut sender_public_key = match decode_base64url(public_key) {
         Ok(addr) => addr,
         Err(e) => {
             return Err(DisplayMsg::FailToDecodeBase64String(
@@ -171,16 +237,7 @@ pub fn add_keypair(
             ));
         }
     };
-    let mut sender_private_key = match base64url::decode(&private_key) {
-        Ok(addr) => addr,
-        Err(e) => {
-            return Err(DisplayMsg::FailToDecodeBase64String(
-                String::from("private key"),
-                String::from(public_key),
-                e.to_string(),
-            ));
-        }
-    };
+    let mut sender_private_key = decode_private_key(private_key)?.to_vec();
 
     // Concatenate two keys together
     sender_private_key.append(&mut sender_public_key);
@@ -197,9 +254,201 @@ pub fn add_keypair(
         private_key: String::from(private_key),
         keypair: base64url::encode(keypair.to_keypair_bytes()),
         name: keypair_name.to_string(),
+        signer_command: None,
     })
 }
 
+// `derive_public_key` returns the public key of a private key, without saving anything to the keypair file.
+//  # Arguments
+//  * `private_key` - Base64url encoded private key of the ParallelChain account
+//
+pub fn derive_public_key(private_key: &str) -> Result<String, DisplayMsg> {
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&decode_private_key(private_key)?);
+    Ok(base64url::encode(signing_key.verifying_key().as_bytes()))
+}
+
+// `decode_private_key` decodes a private key into the 32 bytes of an Ed25519 secret key.
+//  # Arguments
+//  * `private_key` - Base64url encoded private key
+//
+fn decode_private_key(private_key: &str) -> Result<[u8; 32], DisplayMsg> {
+    // the private key is not repeated in the error, which may end up in logs
+    let private_key_bytes = decode_base64url(private_key)
+        .map_err(|e| DisplayMsg::FailToDecodeBase64Secret(String::from("private key"), e))?;
+    let length = private_key_bytes.len();
+
+    private_key_bytes.try_into().map_err(|_| {
+        DisplayMsg::InvalidEd25519Keypair(format!(
+            "The private key is {length} bytes long but should be 32 bytes long."
+        ))
+    })
+}
+
+// `signing_key` decodes a keypair from the keypair file into the Ed25519 key which signs transactions and messages.
+//  # Arguments
+//  * `keypair` - the keypair read from the keypair file
+//
+pub fn signing_key(keypair: &KeypairJSON) -> Result<ed25519_dalek::SigningKey, DisplayMsg> {
+    // the keypair is not repeated in the error, as it contains the private key
+    let keypair_bytes: [u8; 64] = decode_base64url(&keypair.keypair)
+        .map_err(|e| DisplayMsg::FailToDecodeBase64Secret(String::from("keypair"), e))?
+        .try_into()
+        .map_err(|_| {
+            DisplayMsg::InvalidEd25519Keypair(String::from("Keypair is not 64 bytes long."))
+        })?;
+
+    ed25519_dalek::SigningKey::from_keypair_bytes(&keypair_bytes)
+        .map_err(|e| DisplayMsg::InvalidEd25519Keypair(e.to_string()))
+}
+
+// `add_external_keypair` creates a keypair whose signing is delegated to an external command, so that the
+//  private key never has to be imported, e.g. when it is kept in an HSM.
+//  # Arguments
+//  * `public_key` - public key of the ParallelChain account
+//  * `signer_command` - command which signs messages for the public key, see `sign_externally`
+//  * `keypair_name` - name of the keypair saved on the JSON file
+//
+pub fn add_external_keypair(
+    public_key: &str,
+    signer_command: &str,
+    keypair_name: &str,
+) -> Result<KeypairJSON, DisplayMsg> {
+    verifying_key(public_key)?;
+    if signer_command.trim().is_empty() {
+        return Err(DisplayMsg::FailToRunSignerCommand(
+            String::from(signer_command),
+            String::from("Signer command is empty."),
+        ));
+    }
+
+    Ok(KeypairJSON {
+        name: keypair_name.to_string(),
+        private_key: String::new(),
+        public_key: String::from(public_key),
+        keypair: String::new(),
+        signer_command: Some(String::from(signer_command)),
+    })
+}
+
+// `sign_externally` signs a message with the signer command of a keypair added by `keys import-external`.
+//  The command is run by the shell, with the public key in the environment variable `PCHAIN_SIGNER_PUBLIC_KEY`.
+//  It reads the message from stdin as a Base64url string followed by a newline, and prints the 64-byte Ed25519
+//  signature to stdout as a Base64url string. The signature is verified against the public key before it is used.
+//  # Arguments
+//  * `keypair` - the external keypair
+//  * `message` - message to sign
+//
+pub fn sign_externally(
+    keypair: &KeypairJSON,
+    message: &[u8],
+) -> Result<pchain_types::cryptography::SignatureBytes, DisplayMsg> {
+    use ed25519_dalek::Verifier;
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let signer_command = keypair.signer_command.clone().unwrap_or_default();
+    let fail = |error: String| DisplayMsg::FailToRunSignerCommand(signer_command.clone(), error);
+
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.arg("/C");
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.arg("-c");
+        command
+    };
+    let mut child = command
+        .arg(&signer_command)
+        .env("PCHAIN_SIGNER_PUBLIC_KEY", &keypair.public_key)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| fail(e.to_string()))?;
+
+    // a command which exits without reading the message closes the pipe. What it prints is still checked below.
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = writeln!(stdin, "{}", base64url::encode(message));
+    }
+    let output = child.wait_with_output().map_err(|e| fail(e.to_string()))?;
+    if !output.status.success() {
+        return Err(fail(format!("The command exits with {}.", output.status)));
+    }
+
+    let signature: pchain_types::cryptography::SignatureBytes =
+        decode_base64url(String::from_utf8_lossy(&output.stdout).trim())
+            .map_err(|e| {
+                fail(format!(
+                    "The command does not print a Base64url signature. {e}"
+                ))
+            })?
+            .try_into()
+            .map_err(|_| fail(String::from("The signature printed is not 64 bytes long.")))?;
+
+    verifying_key(&keypair.public_key)?
+        .verify(message, &ed25519_dalek::Signature::from_bytes(&signature))
+        .map_err(|_| {
+            fail(format!(
+                "The signature printed is not a valid signature of public key <{}>.",
+                keypair.public_key
+            ))
+        })?;
+    Ok(signature)
+}
+
+// `verify_signature` checks that a signature is a valid Ed25519 signature of a message by a public key.
+//  # Arguments
+//  * `public_key` - Base64url encoded public key of the signer
+//  * `message` - Base64url encoded message
+//  * `signature` - Base64url encoded signature
+//
+pub fn verify_signature(
+    public_key: &str,
+    message: &str,
+    signature: &str,
+) -> Result<bool, DisplayMsg> {
+    use ed25519_dalek::Verifier;
+
+    let message = decode_base64url(message).map_err(|e| {
+        DisplayMsg::FailToDecodeBase64String(String::from("message"), String::from(message), e)
+    })?;
+    let signature: pchain_types::cryptography::SignatureBytes = decode_base64url(signature)
+        .map_err(|e| {
+            DisplayMsg::FailToDecodeBase64String(
+                String::from("signature"),
+                String::from(signature),
+                e,
+            )
+        })?
+        .try_into()
+        .map_err(|_| DisplayMsg::IncorrectSignatureLength)?;
+
+    Ok(verifying_key(public_key)?
+        .verify(&message, &ed25519_dalek::Signature::from_bytes(&signature))
+        .is_ok())
+}
+
+// `verifying_key` decodes a public key into an Ed25519 key which verifies signatures.
+//  # Arguments
+//  * `public_key` - Base64url encoded public key
+//
+fn verifying_key(public_key: &str) -> Result<ed25519_dalek::VerifyingKey, DisplayMsg> {
+    let public_key_bytes: [u8; 32] = decode_base64url(public_key)
+        .map_err(|e| {
+            DisplayMsg::FailToDecodeBase64String(
+                String::from("public key"),
+                String::from(public_key),
+                e,
+            )
+        })?
+        .try_into()
+        .map_err(|_| DisplayMsg::IncorrectBase64urlLength)?;
+
+    ed25519_dalek::VerifyingKey::from_bytes(&public_key_bytes)
+        .map_err(|e| DisplayMsg::InvalidEd25519Keypair(e.to_string()))
+}
+
 // `append_keypair_to_json` takes a path to keypair JSON and appends a new keypair to the file.
 //  # Arguments
 //  * `path_to_keypair_json` - path to keypair JSON file
@@ -218,7 +467,50 @@ pub fn append_keypair_to_json(
     } else {
         keypairs.push(new_keypair);
     };
-    let updated_keypairs = match serde_json::to_vec(&keypairs) {
+    save_keypairs_to_json(path_to_keypair_json, &keypairs)
+}
+
+// `save_keypairs_to_json` encrypts a list of keypairs and overwrites the keypair JSON file with it.
+//  # Arguments
+//  * `path_to_keypair_json` - path to keypair JSON file
+//  * `keypairs` - all keypairs to keep in the file
+//
+pub fn save_keypairs_to_json(
+    path_to_keypair_json: PathBuf,
+    keypairs: &[KeypairJSON],
+) -> Result<String, DisplayMsg> {
+    save_keypairs(path_to_keypair_json, keypairs, utils::encrypt)
+}
+
+// `save_keypairs_to_json_with_passphrase` overwrites the keypair JSON file as `save_keypairs_to_json`,
+//  with a passphrase already given by login instead of logging in again.
+//  # Arguments
+//  * `path_to_keypair_json` - path to keypair JSON file
+//  * `keypairs` - all keypairs to keep in the file
+//  * `encoded_passphrase` - Base64url encoded password returned by login
+//
+pub fn save_keypairs_to_json_with_passphrase(
+    path_to_keypair_json: PathBuf,
+    keypairs: &[KeypairJSON],
+    encoded_passphrase: &str,
+) -> Result<String, DisplayMsg> {
+    save_keypairs(path_to_keypair_json, keypairs, |source| {
+        utils::encrypt_with_passphrase(source, encoded_passphrase.to_string())
+    })
+}
+
+// `save_keypairs` overwrites the keypair JSON file with a list of keypairs, encrypted with `encrypt`.
+//  # Arguments
+//  * `path_to_keypair_json` - path to keypair JSON file
+//  * `keypairs` - all keypairs to keep in the file
+//  * `encrypt` - encrypts the serialized keypairs
+//
+fn save_keypairs(
+    path_to_keypair_json: PathBuf,
+    keypairs: &[KeypairJSON],
+    encrypt: impl FnOnce(&[u8]) -> Result<Vec<u8>, DisplayMsg>,
+) -> Result<String, DisplayMsg> {
+    let updated_keypairs = match serde_json::to_vec(keypairs) {
         Ok(data) => data,
         Err(e) => {
             return Err(DisplayMsg::FailToEncodeJson(
@@ -228,7 +520,7 @@ pub fn append_keypair_to_json(
             ))
         }
     };
-    let updated_keypairs_bytes = utils::encrypt(&updated_keypairs)?;
+    let updated_keypairs_bytes = encrypt(&updated_keypairs)?;
 
     match utils::write_file(path_to_keypair_json.clone(), &updated_keypairs_bytes) {
         Ok(_) => Ok(String::from("Success")),
@@ -239,3 +531,160 @@ pub fn append_keypair_to_json(
         )),
     }
 }
+
+// `verify_exported_keypair` checks that an exported keypair file deserializes back to the keypair that was
+//  exported, and that the Ed25519 keypair can be reconstructed from it.
+//  # Arguments
+//  * `exported` - content read back from the exported keypair file
+//  * `expected` - keypair which was exported
+//
+pub fn verify_exported_keypair(exported: &[u8], expected: &KeypairJSON) -> Result<(), String> {
+    let exported: KeypairJSON = serde_json::from_slice(exported).map_err(|e| e.to_string())?;
+    if exported.name != expected.name
+        || exported.private_key != expected.private_key
+        || exported.public_key != expected.public_key
+        || exported.keypair != expected.keypair
+    {
+        return Err(String::from(
+            "Content of the file differs from the keypair being exported.",
+        ));
+    }
+
+    check_keypair_consistency(&exported)
+}
+
+// `check_keypair_consistency` checks that the public key of a keypair is a valid Ed25519 public key which is
+//  not weak, and that the Ed25519 keypair can be reconstructed from it with the same private and public key.
+//  A keypair added by `keys import-external` has only its public key checked.
+//  # Arguments
+//  * `keypair` - keypair to check
+//
+pub fn check_keypair_consistency(keypair: &KeypairJSON) -> Result<(), String> {
+    let public_key = verifying_key(&keypair.public_key).map_err(|e| e.to_string())?;
+    if public_key.is_weak() {
+        return Err(String::from(
+            "Public key is a point of small order, for which signatures can be forged.",
+        ));
+    }
+    if keypair.signer_command.is_some() {
+        return Ok(());
+    }
+
+    let keypair_bytes: [u8; 64] = base64url::decode(&keypair.keypair)
+        .map_err(|e| e.to_string())?
+        .try_into()
+        .map_err(|_| String::from("Keypair is not 64 bytes long."))?;
+    let signing_key =
+        ed25519_dalek::SigningKey::from_keypair_bytes(&keypair_bytes).map_err(|e| e.to_string())?;
+    if signing_key.verifying_key() != public_key {
+        return Err(String::from(
+            "Public key does not match the one derived from the keypair.",
+        ));
+    }
+    if base64url::decode(&keypair.private_key).ok().as_deref() != Some(&keypair_bytes[..32]) {
+        return Err(String::from(
+            "Private key does not match the one in the keypair.",
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_verify_exported_keypair() {
+        let keypair = generate_keypair("test");
+        let exported = serde_json::to_vec_pretty(&keypair).unwrap();
+        assert!(verify_exported_keypair(&exported, &keypair).is_ok());
+
+        // Truncated file
+        assert!(verify_exported_keypair(&exported[..exported.len() / 2], &keypair).is_err());
+
+        // Keypair which does not match the public key
+        let other = generate_keypair("test");
+        let mut tampered = keypair.clone();
+        tampered.keypair = other.keypair;
+        let exported = serde_json::to_vec_pretty(&tampered).unwrap();
+        assert!(verify_exported_keypair(&exported, &tampered).is_err());
+    }
+
+    #[test]
+    fn test_signing_key() {
+        let keypair = generate_keypair("test");
+        let key = signing_key(&keypair).unwrap();
+        assert_eq!(
+            base64url::encode(key.verifying_key().as_bytes()),
+            keypair.public_key
+        );
+
+        // Keypair which is too short, e.g. a truncated keypair file
+        let mut truncated = keypair.clone();
+        truncated.keypair = base64url::encode([1u8; 32]);
+        assert!(signing_key(&truncated).is_err());
+    }
+
+    #[test]
+    fn test_check_keypair_consistency() {
+        let keypair = generate_keypair("test");
+        assert!(check_keypair_consistency(&keypair).is_ok());
+
+        // Private key which does not match the keypair
+        let other = generate_keypair("other");
+        let mut tampered = keypair.clone();
+        tampered.private_key = other.private_key.clone();
+        assert!(check_keypair_consistency(&tampered).is_err());
+
+        // Public key which does not match the keypair
+        let mut tampered = keypair.clone();
+        tampered.public_key = other.public_key.clone();
+        assert!(check_keypair_consistency(&tampered).is_err());
+
+        // Keypair which cannot be decoded
+        let mut tampered = keypair.clone();
+        tampered.keypair = String::from("AAAA");
+        assert!(check_keypair_consistency(&tampered).is_err());
+
+        // Weak public key, i.e. the identity point, of an external keypair
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        let external =
+            add_external_keypair(&base64url::encode(identity), "exit 1", "external").unwrap();
+        assert!(check_keypair_consistency(&external).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sign_externally() {
+        use ed25519_dalek::Signer;
+
+        let local = generate_keypair("local");
+        let keypair_bytes: [u8; 64] = base64url::decode(&local.keypair)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let signing_key = ed25519_dalek::SigningKey::from_keypair_bytes(&keypair_bytes).unwrap();
+        let message = b"message";
+        let signature = base64url::encode(signing_key.sign(message).to_bytes());
+
+        // A signer command which prints a valid signature
+        let external =
+            add_external_keypair(&local.public_key, &format!("echo {signature}"), "external")
+                .unwrap();
+        assert_eq!(
+            sign_externally(&external, message).unwrap().to_vec(),
+            base64url::decode(&signature).unwrap()
+        );
+
+        // A signature of another message
+        assert!(sign_externally(&external, b"other message").is_err());
+
+        // A signer command which fails
+        let external = add_external_keypair(&local.public_key, "exit 1", "external").unwrap();
+        assert!(sign_externally(&external, message).is_err());
+
+        assert!(add_external_keypair(&local.public_key, " ", "external").is_err());
+    }
+}
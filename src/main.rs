@@ -27,6 +27,9 @@ pub mod config;
 /// keys to your accounts on ParallelChain.
 pub mod keypair;
 
+/// `mnemonic` defines the BIP39 mnemonic phrase which backs up a keypair, and derives the keypair from it.
+pub mod mnemonic;
+
 /// `utils` defines methods to read file and generate random string for keypair name.
 pub mod utils;
 
@@ -34,41 +37,181 @@ pub mod utils;
 pub mod parser;
 
 extern crate argon2;
-use clap::Parser;
-use command::PChainCLI;
-use config::{get_hash_path, Config};
+use clap::{CommandFactory, Parser};
+use command::{ConfigCommand, PChainCLI, PChainCommand};
+use config::{get_hash_path, get_keypair_path, use_ca_cert, use_proxy, Config};
+use display_msg::DisplayMsg;
+use std::time::Duration;
 
 use crate::sub_commands::{
-    match_crypto_subcommand, match_parse_subcommand, match_query_subcommand,
-    match_setup_subcommand, match_submit_subcommand,
+    display_config_paths, match_crypto_subcommand, match_parse_subcommand, match_query_subcommand,
+    match_setup_subcommand, match_submit_subcommand, set_retries,
 };
 
-#[tokio::main]
-async fn main() {
+fn main() {
+    let args = match PChainCLI::try_parse() {
+        Ok(args) => args,
+        Err(e) => exit_on_parse_error(e),
+    };
+
+    if args.global_options.status_line {
+        utils::print_status_line();
+    }
+
+    // shows where the files are without creating or reading any of them
+    if let PChainCommand::Config {
+        config_subcommand: ConfigCommand::Paths,
+    } = args.command
+    {
+        display_config_paths();
+        utils::exit(0);
+    }
+
+    // read-only commands write to the home directory only to create a missing config file
+    if !args.command.is_read_only() || !config::config_file_exists() {
+        if let Err(e) = config::check_home_dir_writable() {
+            println!("{}", e);
+            utils::exit(1);
+        }
+    }
     let config = Config::load();
 
+    if args.global_options.password_stdin {
+        utils::read_password_from_stdin();
+    }
+
+    if args.global_options.abort_on_warning {
+        utils::abort_on_warning();
+    }
+
+    // finish or undo a `keys change-password` which stopped between writing its files
+    if let Err(e) = utils::recover_password_change(get_keypair_path()) {
+        println!("{}", e);
+        utils::exit(1);
+    }
+
     let default_hash_file = get_hash_path();
-    if !default_hash_file.exists() {
+    if !default_hash_file.exists() && !args.command.is_read_only() {
         match utils::setup_password() {
             Ok(()) => keypair::setup_keypair_file(),
             Err(e) => {
                 println!("{}", e);
-                std::process::exit(1);
+                utils::exit(1);
             }
         }
     }
 
-    let args = PChainCLI::parse();
+    result::set_display_options(result::DisplayOptions {
+        group_digits: args.global_options.group_digits,
+        timing: args.global_options.timing,
+        raw_response: args.global_options.raw_response,
+        compact_address: args.global_options.compact_address,
+        precision: args.global_options.precision,
+        raw_return_values: args.global_options.raw_return_values,
+    });
 
-    match args {
-        PChainCLI::Config { config_subcommand } => match_setup_subcommand(config_subcommand).await,
-        PChainCLI::Transaction { tx_subcommand } => {
-            match_submit_subcommand(tx_subcommand, config).await
-        }
-        PChainCLI::Query { query_subcommand } => {
-            match_query_subcommand(query_subcommand, config).await
-        }
-        PChainCLI::Keys { crypto_subcommand } => match_crypto_subcommand(crypto_subcommand),
-        PChainCLI::Parse { parse_subcommand } => match_parse_subcommand(parse_subcommand),
+    // an empty `--proxy` or `--ca-cert` overrides the saved one with none
+    if let Some(proxy_url) = args
+        .global_options
+        .proxy
+        .as_deref()
+        .or(config.proxy_url.as_deref())
+        .filter(|proxy_url| !proxy_url.is_empty())
+    {
+        use_proxy(proxy_url);
+    }
+    if let Some(ca_cert_path) = args
+        .global_options
+        .ca_cert
+        .as_deref()
+        .or(config.ca_cert_path.as_deref())
+        .filter(|ca_cert_path| !ca_cert_path.is_empty())
+    {
+        use_ca_cert(ca_cert_path);
+    }
+
+    run(args, config);
+    utils::exit(0);
+}
+
+// `run` runs the command in the async runtime, within `--deadline` if it is given. The runtime starts only
+//  here, after the environment read by the HTTP client has been set by `main` on a single thread.
+//  # Arguments
+//  * `args` - parsed command line
+//  * `config` - loaded config file
+//
+#[tokio::main]
+async fn run(args: PChainCLI, config: Config) {
+    let deadline = args.global_options.deadline;
+    let command = async move {
+        match args.command {
+            PChainCommand::Config { config_subcommand } => {
+                match_setup_subcommand(
+                    config_subcommand,
+                    args.global_options.proxy,
+                    args.global_options.ca_cert,
+                )
+                .await
+            }
+            PChainCommand::Transaction { tx_subcommand } => {
+                match_submit_subcommand(tx_subcommand, config).await
+            }
+            PChainCommand::Query {
+                query_subcommand,
+                show_context,
+                cache,
+                no_cache: _,
+                retries,
+            } => {
+                set_retries(retries);
+                // print the context first, as a failed query exits the program
+                if show_context {
+                    eprintln!("{}", DisplayMsg::QueryContext(config.get_url().to_string()));
+                }
+                match_query_subcommand(query_subcommand, &config, cache).await;
+            }
+            PChainCommand::Keys { crypto_subcommand } => match_crypto_subcommand(crypto_subcommand),
+            PChainCommand::Parse { parse_subcommand } => match_parse_subcommand(parse_subcommand),
+        };
     };
+
+    match deadline {
+        Some(deadline) => {
+            if tokio::time::timeout(Duration::from_secs(deadline), command)
+                .await
+                .is_err()
+            {
+                println!("{}", DisplayMsg::DeadlineExceeded(deadline));
+                utils::exit(DEADLINE_EXCEEDED_EXIT_CODE);
+            }
+        }
+        None => command.await,
+    }
 }
+
+// `exit_on_parse_error` prints the usage error, or the help or version asked for, and exits. The command line
+//  is parsed again leniently to find out whether `--status-line` is given, as the error leaves no parsed options.
+//  # Arguments
+//  * `error` - error returned by clap
+//
+fn exit_on_parse_error(error: clap::Error) -> ! {
+    let status_line = PChainCLI::command()
+        .ignore_errors(true)
+        .try_get_matches()
+        .map(|matches| matches.is_present("status-line"))
+        .unwrap_or(false);
+    if status_line {
+        utils::print_status_line();
+    }
+    let _ = error.print();
+    if error.use_stderr() {
+        utils::exit(USAGE_EXIT_CODE);
+    }
+    utils::exit(0)
+}
+
+/// Exit code when a command does not finish within `--deadline`, same as the coreutils `timeout`.
+const DEADLINE_EXCEEDED_EXIT_CODE: i32 = 124;
+
+/// Exit code of a usage error, same as clap.
+const USAGE_EXIT_CODE: i32 = 2;
@@ -0,0 +1,205 @@
+/*
+    Copyright © 2023, ParallelChain Lab
+    Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+*/
+
+//! Methods related to BIP39 mnemonic phrases, which back up a keypair created by `keys create --mnemonic`.
+//!
+//! The keypair is the SLIP-0010 Ed25519 master key of the BIP39 seed of the phrase, without passphrase.
+
+use hmac::{Hmac, Mac};
+use rand::{rngs::OsRng, RngCore};
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use sha2::{Digest, Sha256, Sha512};
+use std::convert::TryInto;
+
+// `generate_mnemonic` generates a new 24-word mnemonic phrase from 256 bits of entropy.
+//  # Arguments
+//  *
+pub fn generate_mnemonic() -> String {
+    let mut osrng = OsRng {};
+    let mut chacha20_rng = ChaCha20Rng::from_rng(&mut osrng).unwrap();
+    let mut entropy = [0u8; ENTROPY_BYTES];
+    chacha20_rng.fill_bytes(&mut entropy);
+
+    mnemonic_from_entropy(&entropy)
+}
+
+// `signing_key_from_mnemonic` derives the Ed25519 key of a mnemonic phrase.
+//  # Arguments
+//  * `phrase` - mnemonic phrase, with words separated by single spaces
+//
+pub fn signing_key_from_mnemonic(phrase: &str) -> ed25519_dalek::SigningKey {
+    signing_key_from_seed(&seed_from_mnemonic(phrase, ""))
+}
+
+// `normalize_mnemonic` checks that a mnemonic phrase has 24 words of the wordlist and a valid checksum. It returns
+//  the phrase in lowercase with words separated by single spaces, which the keypair is derived from.
+//  # Arguments
+//  * `phrase` - mnemonic phrase entered by user
+//
+pub fn normalize_mnemonic(phrase: &str) -> Result<String, String> {
+    let words: Vec<String> = phrase
+        .split_whitespace()
+        .map(|word| word.to_lowercase())
+        .collect();
+    if words.len() != MNEMONIC_WORDS {
+        return Err(format!(
+            "The phrase has {} words instead of {MNEMONIC_WORDS}.",
+            words.len()
+        ));
+    }
+
+    let wordlist: Vec<&str> = WORDLIST.lines().collect();
+    let mut indices = Vec::with_capacity(MNEMONIC_WORDS);
+    for (position, word) in words.iter().enumerate() {
+        match wordlist.binary_search(&word.as_str()) {
+            Ok(index) => indices.push(index),
+            Err(_) => {
+                return Err(format!(
+                    "Word {} \"{word}\" is not in the BIP39 English wordlist.",
+                    position + 1
+                ))
+            }
+        }
+    }
+
+    let mut bytes = [0u8; ENTROPY_BYTES + 1];
+    for (word, index) in indices.into_iter().enumerate() {
+        for bit in 0..BITS_PER_WORD {
+            if index >> (BITS_PER_WORD - 1 - bit) & 1 == 1 {
+                let position = word * BITS_PER_WORD + bit;
+                bytes[position / 8] |= 1 << (7 - position % 8);
+            }
+        }
+    }
+    let (entropy, checksum) = bytes.split_at(ENTROPY_BYTES);
+    if Sha256::digest(entropy)[0] != checksum[0] {
+        return Err(String::from(
+            "The checksum of the phrase does not match. Please check the words and their order.",
+        ));
+    }
+
+    Ok(words.join(" "))
+}
+
+// `mnemonic_from_entropy` encodes entropy and its checksum, i.e. the first byte of its SHA256 hash, in words
+//  of 11 bits each.
+//  # Arguments
+//  * `entropy` - 256 bits of entropy
+//
+fn mnemonic_from_entropy(entropy: &[u8; ENTROPY_BYTES]) -> String {
+    let mut bytes = entropy.to_vec();
+    bytes.push(Sha256::digest(entropy)[0]);
+
+    let words: Vec<&str> = WORDLIST.lines().collect();
+    (0..MNEMONIC_WORDS)
+        .map(|word| {
+            let index = (word * BITS_PER_WORD..(word + 1) * BITS_PER_WORD).fold(0, |index, bit| {
+                (index << 1) | ((bytes[bit / 8] >> (7 - bit % 8)) & 1) as usize
+            });
+            words[index]
+        })
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+// `seed_from_mnemonic` computes the 64-byte BIP39 seed of a mnemonic phrase.
+//  # Arguments
+//  * `phrase` - mnemonic phrase
+//  * `passphrase` - BIP39 passphrase
+//
+fn seed_from_mnemonic(phrase: &str, passphrase: &str) -> [u8; 64] {
+    let mut seed = [0u8; 64];
+    pbkdf2::pbkdf2_hmac::<Sha512>(
+        phrase.as_bytes(),
+        format!("mnemonic{passphrase}").as_bytes(),
+        PBKDF2_ROUNDS,
+        &mut seed,
+    );
+    seed
+}
+
+// `signing_key_from_seed` derives the SLIP-0010 Ed25519 master key of a seed.
+//  # Arguments
+//  * `seed` - BIP39 seed
+//
+fn signing_key_from_seed(seed: &[u8]) -> ed25519_dalek::SigningKey {
+    let mut mac =
+        Hmac::<Sha512>::new_from_slice(b"ed25519 seed").expect("HMAC accepts keys of any length");
+    mac.update(seed);
+    let master = mac.finalize().into_bytes();
+
+    ed25519_dalek::SigningKey::from_bytes(&master[..32].try_into().unwrap())
+}
+
+/// English wordlist of BIP39
+const WORDLIST: &str = include_str!("bip39-english.txt");
+
+/// Number of bytes of entropy of a mnemonic phrase
+const ENTROPY_BYTES: usize = 32;
+
+/// Number of words of a mnemonic phrase of 256 bits of entropy and 8 bits of checksum
+const MNEMONIC_WORDS: usize = 24;
+
+/// Number of bits encoded by a word
+const BITS_PER_WORD: usize = 11;
+
+/// Iterations of PBKDF2 which stretch a mnemonic phrase to a seed
+const PBKDF2_ROUNDS: u32 = 2048;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::encode_hex;
+
+    #[test]
+    fn test_mnemonic_from_entropy() {
+        // test vectors of BIP39
+        assert_eq!(
+            mnemonic_from_entropy(&[0u8; 32]),
+            format!("{}art", "abandon ".repeat(23))
+        );
+        assert_eq!(
+            mnemonic_from_entropy(&[0xffu8; 32]),
+            format!("{}vote", "zoo ".repeat(23))
+        );
+        assert_eq!(generate_mnemonic().split(' ').count(), MNEMONIC_WORDS);
+    }
+
+    #[test]
+    fn test_normalize_mnemonic() {
+        let phrase = generate_mnemonic();
+        assert_eq!(normalize_mnemonic(&phrase), Ok(phrase.clone()));
+        assert_eq!(
+            normalize_mnemonic(&format!("  {}\n", phrase.to_uppercase().replace(' ', "  "))),
+            Ok(phrase)
+        );
+
+        let phrase = format!("{}art", "abandon ".repeat(23));
+        assert!(normalize_mnemonic(&phrase).is_ok());
+        // wrong checksum
+        assert!(normalize_mnemonic(&format!("{}zoo", "abandon ".repeat(23))).is_err());
+        // wrong word count
+        assert!(normalize_mnemonic(&format!("{}about", "abandon ".repeat(11))).is_err());
+        // word not in the wordlist
+        assert!(normalize_mnemonic(&format!("{}artt", "abandon ".repeat(23))).is_err());
+    }
+
+    #[test]
+    fn test_signing_key_from_mnemonic() {
+        // test vector of BIP39
+        let seed = seed_from_mnemonic(&format!("{}art", "abandon ".repeat(23)), "TREZOR");
+        assert_eq!(
+            encode_hex(&seed),
+            "bda85446c68413707090a52022edd26a1c9462295029f2e60cd7c4f2bbd3097170af7a4d73245cafa9c3cca8d561a7c3de6f5d4a10be8ed2a5e608d68f92fcc8"
+        );
+
+        // test vector 1 of SLIP-0010
+        let seed: Vec<u8> = (0u8..16).collect();
+        assert_eq!(
+            encode_hex(signing_key_from_seed(&seed).as_bytes()),
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+        );
+    }
+}
@@ -12,6 +12,7 @@ use serde_json::Value;
 use std::{collections::VecDeque, convert::TryInto, fmt::Debug, ops::Deref};
 
 use crate::display_msg::DisplayMsg;
+use crate::utils::encode_hex;
 
 /// Decodes a Base64URL string into pchain_types::cryptography::PublicAddress.
 /// Throws error if decode fails.
@@ -20,12 +21,20 @@ use crate::display_msg::DisplayMsg;
 pub fn base64url_to_public_address(
     base64url: &str,
 ) -> Result<pchain_types::cryptography::PublicAddress, DisplayMsg> {
-    base64url::decode(&base64url)
+    decode_base64url(base64url)
         .map_err(|_| DisplayMsg::IncorrectBase64urlLength)?
         .try_into()
         .map_err(|_| DisplayMsg::InvalidBase64Encoding(String::from("")))
 }
 
+/// Decodes a Base64URL string supplied by the user, with or without `=` padding.
+/// Base64URL in ParallelChain is unpadded, but padded strings are often pasted from other tools.
+/// # Arguments
+/// * `base64url` - the string argument which is to be decoded
+pub fn decode_base64url(base64url: &str) -> Result<Vec<u8>, String> {
+    base64url::decode(base64url.trim_end_matches('=')).map_err(|e| e.to_string())
+}
+
 /// Read from a string in json and deserialize it to call arguments.
 ///
 /// The expected json value is in format:
@@ -60,6 +69,26 @@ pub fn call_arguments_from_json_array(json_array: &Vec<Value>) -> Result<Vec<Vec
     Ok(arguments)
 }
 
+/// Describe each serialized call argument as a json object of its index, type, value, serialized
+/// length, and serialized bytes in hex and Base64url.
+pub fn call_arguments_layout(json_args: &[Value], call_arguments: &[Vec<u8>]) -> Vec<Value> {
+    json_args
+        .iter()
+        .zip(call_arguments.iter())
+        .enumerate()
+        .map(|(index, (json_arg, bytes))| {
+            serde_json::json!({
+                "index": index,
+                "argument_type": json_arg["argument_type"],
+                "argument_value": json_arg["argument_value"],
+                "length": bytes.len(),
+                "hex": encode_hex(bytes),
+                "base64": base64url::encode(bytes),
+            })
+        })
+        .collect()
+}
+
 /// The expected json array is in format:
 /// ```json
 /// { "arguments": [ ... ] }
@@ -137,6 +166,152 @@ fn serialize_argument_value(data_type: &str, value: &Value) -> Result<Vec<u8>, D
     Ok(args)
 }
 
+/// Invokes `$callback` with the argument types that can be serialized from `argument_value` in
+/// call argument files. [supported_argument_types] lists the same types for display.
+macro_rules! serializable_argument_types {
+    ($callback:ident) => {
+        $callback!(
+            i8, i16, i32, i64, i128,
+            u8, u16, u32, u64, u128,
+            bool, String,
+
+            Vec<i8>, Vec<i16>, Vec<i32>, Vec<i64>, Vec<i128>,
+            Vec<u8>, Vec<u16>, Vec<u32>, Vec<u64>, Vec<u128>,
+            Vec<bool>, Vec<String>,
+
+            Option<i8>, Option<i16>, Option<i32>, Option<i64>, Option<i128>,
+            Option<u8>, Option<u16>, Option<u32>, Option<u64>, Option<u128>,
+            Option<bool>, Option<String>,
+
+            Vec<Vec<i8>>, Vec<Vec<i16>>, Vec<Vec<i32>>, Vec<Vec<i64>>, Vec<Vec<i128>>,
+            Vec<Vec<u8>>, Vec<Vec<u16>>, Vec<Vec<u32>>, Vec<Vec<u64>>, Vec<Vec<u128>>,
+            Vec<Vec<bool>>, Vec<Vec<String>>,
+
+            Option<Vec<i8>>, Option<Vec<i16>>, Option<Vec<i32>>, Option<Vec<i64>>, Option<Vec<i128>>,
+            Option<Vec<u8>>, Option<Vec<u16>>, Option<Vec<u32>>, Option<Vec<u64>>, Option<Vec<u128>>,
+            Option<Vec<bool>>, Option<Vec<String>>,
+
+            Vec<Option<i8>>, Vec<Option<i16>>, Vec<Option<i32>>, Vec<Option<i64>>, Vec<Option<i128>>,
+            Vec<Option<u8>>, Vec<Option<u16>>, Vec<Option<u32>>, Vec<Option<u64>>, Vec<Option<u128>>,
+            Vec<Option<bool>>, Vec<Option<String>>,
+
+            Array<u8, 32>, Array<u8, 64>, OptionArray<u8, 32>, OptionArray<u8, 64>,
+        )
+    };
+}
+
+/// Invokes `$callback` with the primitive types that a call result can be deserialized into.
+macro_rules! deserializable_result_types {
+    ($callback:ident) => {
+        $callback!(
+            i8,
+            i16,
+            i32,
+            i64,
+            i128,
+            u8,
+            u16,
+            u32,
+            u64,
+            u128,
+            bool,
+            String,
+            Vec<i8>,
+            Vec<i16>,
+            Vec<i32>,
+            Vec<i64>,
+            Vec<i128>,
+            Vec<u8>,
+            Vec<u16>,
+            Vec<u32>,
+            Vec<u64>,
+            Vec<u128>,
+            Vec<bool>,
+            Vec<String>,
+            Option<i8>,
+            Option<i16>,
+            Option<i32>,
+            Option<i64>,
+            Option<i128>,
+            Option<u8>,
+            Option<u16>,
+            Option<u32>,
+            Option<u64>,
+            Option<u128>,
+            Option<bool>,
+            Option<String>,
+            Vec<Option<i8>>,
+            Vec<Option<i16>>,
+            Vec<Option<i32>>,
+            Vec<Option<i64>>,
+            Vec<Option<i128>>,
+            Vec<Option<u8>>,
+            Vec<Option<u16>>,
+            Vec<Option<u32>>,
+            Vec<Option<u64>>,
+            Vec<Option<u128>>,
+            Vec<Option<bool>>,
+            Vec<Option<String>>,
+        )
+    };
+}
+
+/// Invokes `$callback` with the byte array types that a call result can be deserialized into,
+/// each mapped to the type it is deserialized as.
+macro_rules! deserializable_result_array_types {
+    ($callback:ident) => {
+        $callback!(
+            Array<u8, 32> => [u8; 32],
+            Array<u8, 64> => [u8; 64],
+            OptionArray<u8, 32> => Option<[u8; 32]>,
+            OptionArray<u8, 64> => Option<[u8; 64]>,
+        )
+    };
+}
+
+/// Names of the types accepted as `argument_type` in call argument files.
+pub fn supported_argument_types() -> Vec<String> {
+    macro_rules! type_names {
+        ($($t:ty,)*) => {
+            vec![$(display_argument_type(stringify!($t))),*]
+        };
+    }
+
+    let mut types = serializable_argument_types!(type_names);
+    types.extend(["Custom".to_string(), "Vec<Custom>".to_string()]);
+    types
+}
+
+/// Names of the types accepted by `parse call-result` as data type or in a schema.
+pub fn supported_call_result_types() -> Vec<String> {
+    macro_rules! type_names {
+        ($($t:ty,)*) => {
+            vec![$(display_argument_type(stringify!($t))),*]
+        };
+    }
+    macro_rules! array_type_names {
+        ($($t:ty => $q:ty,)*) => {
+            vec![$(display_argument_type(stringify!($t))),*]
+        };
+    }
+
+    let mut types = deserializable_result_types!(type_names);
+    types.extend(deserializable_result_array_types!(array_type_names));
+    types
+}
+
+/// Convert a type name used in the parser back to the form users write, e.g. `Array<u8, 32>` to `[u8;32]`.
+/// This is the reverse of [sanitize_argument_type].
+fn display_argument_type(type_name: &str) -> String {
+    let type_name = type_name.replace(' ', "");
+    let re_array = Regex::new(r"^(Option)?Array<([ui](?:8|16|32|64|128)),(32|64)>$").unwrap();
+    match re_array.captures(&type_name) {
+        Some(caps) if caps.get(1).is_some() => format!("Option<[{};{}]>", &caps[2], &caps[3]),
+        Some(caps) => format!("[{};{}]", &caps[2], &caps[3]),
+        None => type_name,
+    }
+}
+
 /// Serialize call arguments to bytes. Throws error if decode fails.
 fn serialize_primitive_argument_value(
     value: &str,
@@ -167,42 +342,172 @@ fn serialize_primitive_argument_value(
         };
     }
 
-    serialize_call_args!(
-        i8, i16, i32, i64, i128,
-        u8, u16, u32, u64, u128,
-        bool, String,
-
-        Vec<i8>, Vec<i16>, Vec<i32>, Vec<i64>, Vec<i128>,
-        Vec<u8>, Vec<u16>, Vec<u32>, Vec<u64>, Vec<u128>,
-        Vec<bool>, Vec<String>,
-
-        Option<i8>, Option<i16>, Option<i32>, Option<i64>, Option<i128>,
-        Option<u8>, Option<u16>, Option<u32>, Option<u64>, Option<u128>,
-        Option<bool>, Option<String>,
+    serializable_argument_types!(serialize_call_args);
+}
 
-        Vec<Vec<i8>>, Vec<Vec<i16>>, Vec<Vec<i32>>, Vec<Vec<i64>>, Vec<Vec<i128>>,
-        Vec<Vec<u8>>, Vec<Vec<u16>>, Vec<Vec<u32>>, Vec<Vec<u64>>, Vec<Vec<u128>>,
-        Vec<Vec<bool>>, Vec<Vec<String>>,
+/// Check the structure of a call result schema. The root must be an object with `argument_type`,
+/// or a non-empty array of such objects. Returns the path of the first malformed node.
+pub fn validate_call_result_schema(schema: &Value) -> Result<(), DisplayMsg> {
+    fn validate_node(node: &Value, path: String) -> Result<(), DisplayMsg> {
+        let object = match node {
+            Value::Object(object) => object,
+            _ => {
+                return Err(DisplayMsg::InvalidCallResultSchema(
+                    path,
+                    "Expected an object with `argument_type`.".to_string(),
+                ))
+            }
+        };
 
-        Option<Vec<i8>>, Option<Vec<i16>>, Option<Vec<i32>>, Option<Vec<i64>>, Option<Vec<i128>>,
-        Option<Vec<u8>>, Option<Vec<u16>>, Option<Vec<u32>>, Option<Vec<u64>>, Option<Vec<u128>>,
-        Option<Vec<bool>>, Option<Vec<String>>,
+        match object.get("argument_name") {
+            None | Some(Value::String(_)) => {}
+            Some(_) => {
+                return Err(DisplayMsg::InvalidCallResultSchema(
+                    path,
+                    "`argument_name` must be a string.".to_string(),
+                ))
+            }
+        }
 
-        Vec<Option<i8>>, Vec<Option<i16>>, Vec<Option<i32>>, Vec<Option<i64>>, Vec<Option<i128>>,
-        Vec<Option<u8>>, Vec<Option<u16>>, Vec<Option<u32>>, Vec<Option<u64>>, Vec<Option<u128>>,
-        Vec<Option<bool>>, Vec<Option<String>>,
+        match object.get("argument_type") {
+            Some(Value::String(_)) => Ok(()),
+            Some(Value::Array(nodes)) if !nodes.is_empty() => {
+                for (idx, node) in nodes.iter().enumerate() {
+                    validate_node(node, format!("{path}.argument_type[{idx}]"))?;
+                }
+                Ok(())
+            }
+            Some(Value::Array(_)) => Err(DisplayMsg::InvalidCallResultSchema(
+                path,
+                "`argument_type` must not be an empty array.".to_string(),
+            )),
+            Some(_) => Err(DisplayMsg::InvalidCallResultSchema(
+                path,
+                "`argument_type` must be a string or an array of objects.".to_string(),
+            )),
+            None => Err(DisplayMsg::InvalidCallResultSchema(
+                path,
+                "Missing field `argument_type`.".to_string(),
+            )),
+        }
+    }
 
-        Array<u8, 32>, Array<u8, 64>, OptionArray<u8, 32>, OptionArray<u8, 64>,
-    );
+    match schema {
+        Value::Array(nodes) if nodes.is_empty() => Err(DisplayMsg::InvalidCallResultSchema(
+            "$".to_string(),
+            "Schema must not be an empty array.".to_string(),
+        )),
+        Value::Array(nodes) => {
+            for (idx, node) in nodes.iter().enumerate() {
+                validate_node(node, format!("$[{idx}]"))?;
+            }
+            Ok(())
+        }
+        node => validate_node(node, "$".to_string()),
+    }
 }
 
 /// Deserialize the data from a pre-defined format.
 pub fn parse_call_result_from_schema(
-    serialized_data: &Vec<u8>,
+    serialized_data: &[u8],
     schema: &Value,
 ) -> Result<Vec<(String, String)>, DisplayMsg> {
+    Ok(call_result_fields(serialized_data, schema)?
+        .into_iter()
+        .map(|(name, _, (value, _))| (name, value))
+        .collect())
+}
+
+/// Deserialize the data from a pre-defined format into JSON of the same shape as the schema. A list of schema
+/// entries becomes an object keyed by `argument_name`, or an array if none of the entries is named. Unnamed
+/// entries in an object are keyed by their index, e.g. "[0]". Each value is JSON of its `argument_type`, e.g. a
+/// `String` stays a string even if it looks like a number. Integers wider than 64 bits are decimal strings, as they
+/// do not fit in a JSON number exactly.
+pub fn parse_call_result_from_schema_nested(
+    serialized_data: &[u8],
+    schema: &Value,
+) -> Result<Value, DisplayMsg> {
+    fn skeleton(nodes: &[Value]) -> Value {
+        let node_value = |node: &Value| match &node["argument_type"] {
+            Value::Array(children) => skeleton(children),
+            _ => Value::Null,
+        };
+
+        let named = |node: &Value| !node["argument_name"].as_str().unwrap_or("").is_empty();
+        if nodes.iter().any(named) {
+            Value::Object(
+                nodes
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, node)| (SchemaKey::new(node, idx).to_string(), node_value(node)))
+                    .collect(),
+            )
+        } else {
+            Value::Array(nodes.iter().map(node_value).collect())
+        }
+    }
+
+    let fields = call_result_fields(serialized_data, schema)?;
+    let mut result = match schema {
+        Value::Array(nodes) => skeleton(nodes),
+        node => skeleton(std::slice::from_ref(node)),
+    };
+
+    for (_, path, (_, value)) in fields {
+        let mut slot = &mut result;
+        for key in &path {
+            slot = match (slot, key) {
+                (Value::Array(items), SchemaKey::Index(idx)) => &mut items[*idx],
+                (Value::Object(fields), key) => {
+                    fields.entry(key.to_string()).or_insert(Value::Null)
+                }
+                _ => unreachable!(),
+            };
+        }
+        *slot = value;
+    }
+
+    Ok(result)
+}
+
+/// Key of an entry in a call result schema, i.e. its `argument_name`, or its index in the list if it has no name.
+#[derive(Clone)]
+enum SchemaKey {
+    Name(String),
+    Index(usize),
+}
+
+impl SchemaKey {
+    fn new(node: &Value, idx: usize) -> Self {
+        match node["argument_name"].as_str().unwrap_or("") {
+            "" => SchemaKey::Index(idx),
+            name => SchemaKey::Name(name.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for SchemaKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaKey::Name(name) => write!(f, "{name}"),
+            SchemaKey::Index(idx) => write!(f, "[{idx}]"),
+        }
+    }
+}
+
+/// Dotted name, path in the schema and value of a field of a call result. The value is both in debug format and JSON.
+type CallResultField = (String, Vec<SchemaKey>, (String, Value));
+
+/// Deserialize the data from a pre-defined format into the fields of the schema.
+fn call_result_fields(
+    serialized_data: &[u8],
+    schema: &Value,
+) -> Result<Vec<CallResultField>, DisplayMsg> {
+    validate_call_result_schema(schema)?;
+
     struct NamedValue<'a> {
         name: String,
+        path: Vec<SchemaKey>,
         idx: usize,
         value: &'a Value,
     }
@@ -213,6 +518,7 @@ pub fn parse_call_result_from_schema(
         for (idx, j_value) in j_values.iter().enumerate() {
             values.push_back(NamedValue {
                 name: "".to_string(),
+                path: Vec::new(),
                 idx,
                 value: j_value,
             });
@@ -220,12 +526,19 @@ pub fn parse_call_result_from_schema(
     } else {
         values.push_back(NamedValue {
             name: "".to_string(),
+            path: Vec::new(),
             idx: 0,
             value: schema,
         });
     }
 
-    while let Some(NamedValue { name, idx, value }) = values.pop_front() {
+    while let Some(NamedValue {
+        name,
+        mut path,
+        idx,
+        value,
+    }) = values.pop_front()
+    {
         let argument_name = value["argument_name"].as_str().unwrap_or("");
         let val_name = match (name.as_str(), argument_name) {
             ("", "") => format!("[{idx}]"),
@@ -233,15 +546,17 @@ pub fn parse_call_result_from_schema(
             (name, "") => format!("{name}[{idx}]"),
             (name, val_name) => name.to_string() + "." + val_name,
         };
+        path.push(SchemaKey::new(value, idx));
 
         match &value["argument_type"] {
             Value::String(j_type) => {
-                data_types.push((val_name, j_type.to_string()));
+                data_types.push((val_name, path, j_type.to_string()));
             }
             Value::Array(j_val_array) => {
                 for (j_val_idx, j_val) in j_val_array.iter().enumerate() {
                     values.push_back(NamedValue {
                         name: val_name.clone(),
+                        path: path.clone(),
                         idx: j_val_idx,
                         value: j_val,
                     });
@@ -252,13 +567,12 @@ pub fn parse_call_result_from_schema(
     }
 
     let mut result = Vec::new();
-    let serialized_data = serialized_data.as_slice();
     let mut pos = 0;
-    for (name, data_type) in data_types {
+    for (name, path, data_type) in data_types {
         if let Some(deserialized) =
             deserialize_primitive_argument_value(&serialized_data[pos..], &mut pos, &data_type)?
         {
-            result.push((name, deserialized));
+            result.push((name, path, deserialized));
         }
     }
 
@@ -272,18 +586,18 @@ pub fn parse_call_result_from_data_type(
     let buf = vec.as_slice();
     let mut pos = 0;
     match deserialize_primitive_argument_value(buf, &mut pos, &data_type) {
-        Ok(Some(result)) => Ok(result),
+        Ok(Some((result, _))) => Ok(result),
         Ok(None) => Err(DisplayMsg::FailToSerializeCallArgument(data_type)),
         Err(e) => Err(DisplayMsg::FailToSerializeCallArgument(e.to_string())),
     }
 }
 
-/// Serialize call arguments from bytes. Throws error if decode fails.
+/// Serialize call arguments from bytes, in debug format and as JSON. Throws error if decode fails.
 fn deserialize_primitive_argument_value(
     buf: &[u8],
     pos: &mut usize,
     data_type: &str,
-) -> Result<Option<String>, DisplayMsg> {
+) -> Result<Option<(String, Value)>, DisplayMsg> {
     let dt_no_space = sanitize_argument_type(data_type);
 
     macro_rules! deserialize_call_args {
@@ -291,84 +605,82 @@ fn deserialize_primitive_argument_value(
             $(
                 if dt_no_space == stringify!($t).replace(' ', "") {
                     let data: $t = deserialize_from_buf(buf, pos)?;
-                    return Ok(Some(format!("{:?}", data)));
+                    return Ok(Some((format!("{:?}", data), data.to_json())));
                 }
             )*
         }
     }
 
-    deserialize_call_args!(
-        i8,
-        i16,
-        i32,
-        i64,
-        i128,
-        u8,
-        u16,
-        u32,
-        u64,
-        u128,
-        bool,
-        String,
-        Vec<i8>,
-        Vec<i16>,
-        Vec<i32>,
-        Vec<i64>,
-        Vec<i128>,
-        Vec<u8>,
-        Vec<u16>,
-        Vec<u32>,
-        Vec<u64>,
-        Vec<u128>,
-        Vec<bool>,
-        Vec<String>,
-        Option<i8>,
-        Option<i16>,
-        Option<i32>,
-        Option<i64>,
-        Option<i128>,
-        Option<u8>,
-        Option<u16>,
-        Option<u32>,
-        Option<u64>,
-        Option<u128>,
-        Option<bool>,
-        Option<String>,
-        Vec<Option<i8>>,
-        Vec<Option<i16>>,
-        Vec<Option<i32>>,
-        Vec<Option<i64>>,
-        Vec<Option<i128>>,
-        Vec<Option<u8>>,
-        Vec<Option<u16>>,
-        Vec<Option<u32>>,
-        Vec<Option<u64>>,
-        Vec<Option<u128>>,
-        Vec<Option<bool>>,
-        Vec<Option<String>>,
-    );
+    deserializable_result_types!(deserialize_call_args);
 
     macro_rules! deserialize_call_args_explicitly {
         ($($t:ty => $q:ty,)*) => {
             $(
                 if dt_no_space == stringify!($t).replace(' ', "") {
                     let data: $q = deserialize_from_buf(buf, pos)?;
-                    return Ok(Some(format!("{:?}", data)));
+                    return Ok(Some((format!("{:?}", data), data.to_json())));
                 }
             )*
         }
     }
 
-    deserialize_call_args_explicitly!(
-        Array<u8, 32> => [u8; 32],
-        Array<u8, 64> => [u8; 64],
-        OptionArray<u8, 32> => Option<[u8; 32]>,
-        OptionArray<u8, 64> => Option<[u8; 64]>,
-    );
+    deserializable_result_array_types!(deserialize_call_args_explicitly);
 
     Ok(None)
 }
 
+/// [ToJson] converts a deserialized call result value to JSON of the same type. Integers wider than 64 bits
+/// are converted to decimal strings, so that they are not rounded by JSON parsers.
+trait ToJson {
+    fn to_json(&self) -> Value;
+}
+
+macro_rules! to_json_as_number {
+    ($($t:ty),*) => {
+        $(
+            impl ToJson for $t {
+                fn to_json(&self) -> Value {
+                    Value::from(*self)
+                }
+            }
+        )*
+    };
+}
+
+to_json_as_number!(i8, i16, i32, i64, u8, u16, u32, u64, bool);
+
+macro_rules! to_json_as_string {
+    ($($t:ty),*) => {
+        $(
+            impl ToJson for $t {
+                fn to_json(&self) -> Value {
+                    Value::String(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+to_json_as_string!(i128, u128, String);
+
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> Value {
+        self.as_ref().map_or(Value::Null, ToJson::to_json)
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> Value {
+        Value::Array(self.iter().map(ToJson::to_json).collect())
+    }
+}
+
+impl<T: ToJson, const N: usize> ToJson for [T; N] {
+    fn to_json(&self) -> Value {
+        Value::Array(self.iter().map(ToJson::to_json).collect())
+    }
+}
+
 /// Deserialized from a buffer. Update the deserialized length to the variable `pos`.
 fn deserialize_from_buf<T: BorshDeserialize>(buf: &[u8], pos: &mut usize) -> Result<T, DisplayMsg> {
     let mut temp = buf;
@@ -452,10 +764,28 @@ impl<T, const N: usize> Deref for OptionArray<T, N> {
 
 #[cfg(test)]
 mod test {
-    use crate::parser::{parse_call_result_from_schema, serialize_primitive_argument_value};
+    use crate::display_msg::DisplayMsg;
+    use crate::parser::{
+        base64url_to_public_address, decode_base64url, parse_call_result_from_schema,
+        parse_call_result_from_schema_nested, serialize_primitive_argument_value,
+        supported_argument_types, supported_call_result_types, validate_call_result_schema,
+    };
     use borsh::{BorshDeserialize, BorshSerialize};
     use serde_json::Value;
 
+    #[test]
+    fn test_decode_base64url_padding() {
+        assert_eq!(decode_base64url("AAECAw"), Ok(vec![0, 1, 2, 3]));
+        assert_eq!(decode_base64url("AAECAw=="), Ok(vec![0, 1, 2, 3]));
+        assert!(decode_base64url("AAECAw=x").is_err());
+
+        let address = "kRPL8cXI73DNgVSSQz9WfIi-mAAvFvdXKfZ9UPBEv_A";
+        assert_eq!(
+            base64url_to_public_address(address).ok(),
+            base64url_to_public_address(&format!("{}=", address)).ok()
+        );
+    }
+
     #[test]
     fn test_serialize_primitive_argument_value() {
         match serialize_primitive_argument_value("[[[true]]]", "Vec<Vec<Vec<bool>>>") {
@@ -789,6 +1119,175 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_parse_call_result_nested() {
+        #[derive(BorshSerialize, BorshDeserialize)]
+        struct Account {
+            name: String,
+            balance: u64,
+            friends: Vec<String>,
+            flags: (bool, Option<u8>),
+        }
+        let serialized = Account {
+            name: "Tom".to_string(),
+            balance: 100,
+            friends: vec!["Jason".to_string(), "Kay".to_string()],
+            flags: (true, Some(1)),
+        }
+        .try_to_vec()
+        .unwrap();
+
+        let result = parse_call_result_from_schema_nested(
+            &serialized,
+            &serde_json::json!(
+            {
+                "argument_name": "Person",
+                "argument_type": [
+                    {"argument_name": "name", "argument_type":"String"},
+                    {"argument_name": "balance", "argument_type":"u64"},
+                    {"argument_name": "friends", "argument_type":"Vec<String>"},
+                    {"argument_name": "flags", "argument_type": [
+                        {"argument_type":"bool"},
+                        {"argument_type":"Option<u8>"}
+                    ]}
+                ]
+            }
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(
+            result,
+            serde_json::json!({
+                "Person": {
+                    "name": "Tom",
+                    "balance": 100,
+                    "friends": ["Jason", "Kay"],
+                    "flags": [true, 1]
+                }
+            })
+        );
+
+        // Values follow the schema type instead of how they look
+        let serialized = (
+            "123".to_string(),
+            "null".to_string(),
+            u128::MAX,
+            i128::MIN,
+            None::<u64>,
+        )
+            .try_to_vec()
+            .unwrap();
+        let result = parse_call_result_from_schema_nested(
+            &serialized,
+            &serde_json::json!([
+                {"argument_name": "number_like", "argument_type":"String"},
+                {"argument_name": "null_like", "argument_type":"String"},
+                {"argument_name": "large", "argument_type":"u128"},
+                {"argument_name": "negative", "argument_type":"i128"},
+                {"argument_name": "missing", "argument_type":"Option<u64>"}
+            ]),
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            serde_json::json!({
+                "number_like": "123",
+                "null_like": "null",
+                "large": u128::MAX.to_string(),
+                "negative": i128::MIN.to_string(),
+                "missing": null
+            })
+        );
+
+        // Unnamed entries at the top level
+        let serialized = (7u32, "MTK".to_string()).try_to_vec().unwrap();
+        let result = parse_call_result_from_schema_nested(
+            &serialized,
+            &serde_json::json!([{"argument_type":"u32"}, {"argument_type":"String"}]),
+        )
+        .unwrap();
+        assert_eq!(result, serde_json::json!([7, "MTK"]));
+    }
+
+    #[test]
+    fn test_validate_call_result_schema() {
+        macro_rules! assert_malformed_at {
+            ($($schema:expr, $path:expr,)*) => {
+                $(
+                    match validate_call_result_schema(&$schema) {
+                        Err(DisplayMsg::InvalidCallResultSchema(path, _)) => assert_eq!(path, $path),
+                        _ => panic!("expected malformed schema at {}", $path),
+                    }
+                )*
+            }
+        }
+
+        assert_malformed_at!(
+            serde_json::json!({"name": "balance", "type": "u64"}),
+            "$",
+            serde_json::json!([]),
+            "$",
+            serde_json::json!("u64"),
+            "$",
+            serde_json::json!([{"argument_type": "u8"}, {"argument_name": "x"}]),
+            "$[1]",
+            serde_json::json!({"argument_type": [{"argument_type": "u8"}, 1]}),
+            "$.argument_type[1]",
+            serde_json::json!({"argument_type": [{"argument_type": []}]}),
+            "$.argument_type[0]",
+            serde_json::json!({"argument_name": 1, "argument_type": "u8"}),
+            "$",
+        );
+
+        assert!(validate_call_result_schema(&serde_json::json!({"argument_type": "u8"})).is_ok());
+        assert!(validate_call_result_schema(&serde_json::json!([
+            {"argument_name": "name", "argument_type": "String"},
+            {"argument_name": "friends", "argument_type": [{"argument_type": "Vec<String>"}]},
+        ]))
+        .is_ok());
+    }
+
+    #[test]
+    fn test_supported_types() {
+        let argument_types = supported_argument_types();
+        for data_type in [
+            "u64",
+            "Vec<Vec<String>>",
+            "[u8;32]",
+            "Option<[u8;64]>",
+            "Custom",
+        ] {
+            assert!(
+                argument_types.contains(&data_type.to_string()),
+                "{}",
+                data_type
+            );
+        }
+
+        let result_types = supported_call_result_types();
+        for data_type in ["u64", "Vec<Option<bool>>", "[u8;32]", "Option<[u8;64]>"] {
+            assert!(
+                result_types.contains(&data_type.to_string()),
+                "{}",
+                data_type
+            );
+        }
+        assert!(!result_types.contains(&"Vec<Vec<String>>".to_string()));
+
+        // Every listed type should be accepted by the serializer / deserializer.
+        for data_type in result_types {
+            let mut pos = 0;
+            assert!(
+                super::deserialize_primitive_argument_value(&[0u8; 64], &mut pos, &data_type)
+                    .unwrap()
+                    .is_some(),
+                "{}",
+                data_type
+            );
+        }
+    }
+
     #[test]
     fn test_callresult() {
         macro_rules! assert_data_types {
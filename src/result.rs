@@ -5,18 +5,111 @@
 
 //! Definition of methods related to processing results and displaying them in beauified format.
 
-use crate::command::Base64String;
 use crate::display_msg::DisplayMsg;
 use crate::display_types::{
-    Block, BlockHeader, CommandReceipt, Deposit, Pool, Receipt, Stake, Transaction,
+    receipt_from_v1, total_gas_used, utf8_or_base64, Block, BlockHeader, CommandReceipt, Deposit,
+    Epoch, FlatPool, Pool, Receipt, ReceiptWithTotalGas, Stake, Transaction,
     TransactionWithReceipt, ValidatorSet,
 };
 use crate::utils::write_file;
-use pchain_types::blockchain::{CommandReceiptV1, CommandReceiptV2};
+use futures_util::FutureExt;
+use pchain_types::blockchain::CommandReceiptV2;
 use pchain_types::rpc::*;
 use serde_json::Value;
 use std::collections::BTreeMap;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
 use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+/// [DisplayOptions] holds the global flags which change how results are rendered on the terminal.
+#[derive(Debug, Default)]
+pub struct DisplayOptions {
+    /// Group digits of amount, balance and power fields with commas.
+    pub group_digits: bool,
+    /// Print the elapsed time of each request to Fullnode RPC on stderr.
+    pub timing: bool,
+    /// Print the untouched response from Fullnode RPC on stderr before beautifying it.
+    pub raw_response: bool,
+    /// Also show amounts in XPLL, rounded to this many fractional digits.
+    pub precision: Option<u32>,
+    /// Abbreviate addresses in human-facing lists and tables.
+    pub compact_address: bool,
+    /// Show return values as plain base64url, without the "(Base64 encoded)" label.
+    pub raw_return_values: bool,
+}
+
+static DISPLAY_OPTIONS: OnceLock<DisplayOptions> = OnceLock::new();
+
+/// `set_display_options` sets the global display options. It should be called once, before any result
+///  is displayed. Later calls are ignored.
+pub fn set_display_options(options: DisplayOptions) {
+    let _ = DISPLAY_OPTIONS.set(options);
+}
+
+pub(crate) fn display_options() -> &'static DisplayOptions {
+    DISPLAY_OPTIONS.get_or_init(DisplayOptions::default)
+}
+
+/// Results displayed by the query which `query batch` is running, kept instead of printed. `None` outside a batch.
+static CAPTURED_RESULTS: Mutex<Option<Vec<Value>>> = Mutex::new(None);
+
+// `capture_results` runs a query of `query batch` in-process and returns what it would display, instead of printing
+//  it. A query which fails ends by [DisplayMsg::exit], which hands its error back here rather than exiting the
+//  program.
+// # Arguments
+// * `query` - the query to run
+//
+pub(crate) async fn capture_results<F: Future<Output = ()>>(
+    query: F,
+) -> Result<Vec<Value>, DisplayMsg> {
+    *CAPTURED_RESULTS.lock().unwrap() = Some(Vec::new());
+    let outcome = AssertUnwindSafe(query).catch_unwind().await;
+    let results = CAPTURED_RESULTS.lock().unwrap().take().unwrap_or_default();
+    match outcome {
+        Ok(()) => Ok(results),
+        Err(payload) => match payload.downcast::<DisplayMsg>() {
+            Ok(error) => Err(*error),
+            Err(payload) => std::panic::resume_unwind(payload),
+        },
+    }
+}
+
+// `is_capturing_results` tells whether a query of `query batch` is running, so that results and errors are handed to
+//  [capture_results] instead of being printed.
+pub(crate) fn is_capturing_results() -> bool {
+    CAPTURED_RESULTS.lock().unwrap().is_some()
+}
+
+// `capture` keeps a result of the running query of `query batch`. It returns false, leaving the result to be
+//  printed, if no batch is running.
+// # Arguments
+// * `result` - makes the result to keep
+//
+fn capture(result: impl FnOnce() -> Value) -> bool {
+    match CAPTURED_RESULTS.lock().unwrap().as_mut() {
+        Some(results) => {
+            results.push(result());
+            true
+        }
+        None => false,
+    }
+}
+
+/// Number of fractional digits of XPLL, i.e. 1 XPLL is 10^8 Grays.
+const XPLL_DECIMALS: u32 = 8;
+
+/// Number of characters kept at each end of an address under `--compact-address`.
+const COMPACT_ADDRESS_CHARS: usize = 4;
+
+/// Fields holding amounts in Grays (or stake power) which are grouped under `--group-digits`.
+const AMOUNT_FIELDS: [&str; 5] = [
+    "amount",
+    "balance",
+    "max_amount",
+    "power",
+    "operator_stake_power",
+];
 
 /// `display_beautified_rpc_result` translates the return result from Fullnode RPC/Chain Scanner
 ///  endpoints to beautified readable content.
@@ -25,13 +118,19 @@ use std::path::PathBuf;
 ///
 pub fn display_beautified_rpc_result(response: ClientResponse) {
     match response {
-        ClientResponse::SubmitTx(result, signed_tx) => {
+        ClientResponse::SubmitTx(result, signed_tx, hash_only) => {
             match result {
                 Ok(res) => {
-                    match res.error {
+                    match res.error.clone() {
                         Some(error) => {
-                            println!("{}", DisplayMsg::FailSubmitTx(error));
-                            std::process::exit(1);
+                            DisplayMsg::FailSubmitTx(error).exit();
+                        }
+                        None if hash_only => {
+                            let tx_hash = match signed_tx {
+                                TransactionV1OrV2::V1(txn) => txn.hash,
+                                TransactionV1OrV2::V2(txn) => txn.hash,
+                            };
+                            println!("{}", base64url::encode(tx_hash));
                         }
                         None => {
                             let mut tx = Vec::new();
@@ -70,6 +169,10 @@ pub fn display_beautified_rpc_result(response: ClientResponse) {
                                         "Command(s): ",
                                         serde_json::Value::Array(tx_print.commands),
                                     ));
+                                    tx.push((
+                                        "Signer: ",
+                                        serde_json::to_value(tx_print.signer).unwrap(),
+                                    ));
                                     tx.push((
                                         "Transaction Hash: ",
                                         serde_json::to_value(tx_print.hash).unwrap(),
@@ -112,6 +215,10 @@ pub fn display_beautified_rpc_result(response: ClientResponse) {
                                         "Command(s): ",
                                         serde_json::Value::Array(tx_print.commands),
                                     ));
+                                    tx.push((
+                                        "Signer: ",
+                                        serde_json::to_value(tx_print.signer).unwrap(),
+                                    ));
                                     tx.push((
                                         "Transaction Hash: ",
                                         serde_json::to_value(tx_print.hash).unwrap(),
@@ -127,14 +234,13 @@ pub fn display_beautified_rpc_result(response: ClientResponse) {
                     }
                 }
                 Err(e) => {
-                    println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
-                    std::process::exit(1);
+                    DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
                 }
             }
         }
-        ClientResponse::Block(result) => match result {
+        ClientResponse::Block(result, with_receipts) => match result {
             Ok(BlockResponseV2 { block: Some(block) }) => {
-                let block_print: Block = match block {
+                let mut block_print: Block = match block {
                     BlockV1ToV2::V1(block) => {
                         From::<pchain_types::blockchain::BlockV1>::from(block)
                     }
@@ -142,15 +248,16 @@ pub fn display_beautified_rpc_result(response: ClientResponse) {
                         From::<pchain_types::blockchain::BlockV2>::from(block)
                     }
                 };
-                println!("{:#}", serde_json::to_value(block_print).unwrap())
+                if !with_receipts {
+                    block_print.receipts = None;
+                }
+                display_json(serde_json::to_value(block_print).unwrap())
             }
             Err(e) => {
-                println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
-                std::process::exit(1);
+                DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
             }
             _ => {
-                println!("{}", DisplayMsg::CannotFindRelevantBlock);
-                std::process::exit(1);
+                DisplayMsg::CannotFindRelevantBlock.exit();
             }
         },
         ClientResponse::BlockHeader(result) => match result {
@@ -165,99 +272,169 @@ pub fn display_beautified_rpc_result(response: ClientResponse) {
                         From::<pchain_types::blockchain::BlockHeaderV2>::from(bh)
                     }
                 };
-                println!("{:#}", serde_json::to_value(header_print).unwrap())
+                display_json(serde_json::to_value(header_print).unwrap())
             }
             Err(e) => {
-                println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
-                std::process::exit(1);
+                DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
             }
             _ => {
-                println!("{}", DisplayMsg::CannotFindRelevantBlock);
-                std::process::exit(1);
+                DisplayMsg::CannotFindRelevantBlock.exit();
             }
         },
-        ClientResponse::Transaction(result) => match result {
-            Ok(TransactionResponseV2 {
-                transaction: Some(transaction),
-                receipt,
-                block_hash: _,
-                position: _,
-            }) => match transaction {
-                TransactionV1ToV2::V1(txn) => match receipt {
-                    Some(ReceiptV1ToV2::V1(receipt)) => {
-                        let tx_print: TransactionWithReceipt =
-                            From::<(
-                                pchain_types::blockchain::TransactionV1,
-                                pchain_types::blockchain::ReceiptV1,
-                            )>::from((txn, receipt));
-                        println!("{:#}", serde_json::to_value(tx_print).unwrap())
-                    }
-                    None => {
-                        let tx_print: Transaction =
-                            From::<pchain_types::blockchain::TransactionV1>::from(txn);
-                        println!("{:#}", serde_json::to_value(tx_print).unwrap())
-                    }
-                    _ => {
-                        println!("{}", DisplayMsg::CannotFindRelevantReceipt);
-                        std::process::exit(1);
+        ClientResponse::Epoch(result, epoch) => match result {
+            Ok(BlockHeaderResponseV2 {
+                block_header: Some(bh),
+            }) => {
+                let header_print: BlockHeader = match bh {
+                    BlockHeaderV1ToV2::V1(bh) => {
+                        From::<pchain_types::blockchain::BlockHeaderV1>::from(bh)
                     }
-                },
-                TransactionV1ToV2::V2(txn) => match receipt {
-                    Some(ReceiptV1ToV2::V2(receipt)) => {
-                        let tx_print: TransactionWithReceipt =
-                            From::<(
-                                pchain_types::blockchain::TransactionV2,
-                                pchain_types::blockchain::ReceiptV2,
-                            )>::from((txn, receipt));
-                        println!("{:#}", serde_json::to_value(tx_print).unwrap())
+                    BlockHeaderV1ToV2::V2(bh) => {
+                        From::<pchain_types::blockchain::BlockHeaderV2>::from(bh)
                     }
+                };
+                let epoch_print = Epoch::new(header_print, epoch);
+                display_json(serde_json::to_value(epoch_print).unwrap())
+            }
+            Err(e) => {
+                DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
+            }
+            _ => {
+                DisplayMsg::CannotFindLatestBlock.exit();
+            }
+        },
+        ClientResponse::Transaction(result, full, summary) => match result {
+            Ok(TransactionResponseV2 {
+                transaction: Some(_),
+                receipt,
+                block_hash,
+                position,
+            }) if summary => {
+                let receipt_print = match receipt {
+                    Some(receipt) => receipt_from_rpc(receipt),
                     None => {
-                        let tx_print: Transaction =
-                            From::<pchain_types::blockchain::TransactionV2>::from(txn);
-                        println!("{:#}", serde_json::to_value(tx_print).unwrap())
+                        DisplayMsg::CannotFindRelevantReceipt.exit();
                     }
-                    _ => {
-                        println!("{}", DisplayMsg::CannotFindRelevantReceipt);
-                        std::process::exit(1);
+                };
+                display_receipt_summary(&receipt_print);
+
+                if full {
+                    display_text(format!(
+                        "Block Hash: {}",
+                        block_hash.map_or(String::new(), base64url::encode)
+                    ));
+                    display_text(format!(
+                        "Position: {}",
+                        position.map_or(String::new(), |p| p.to_string())
+                    ));
+                }
+            }
+            Ok(TransactionResponseV2 {
+                transaction: Some(transaction),
+                receipt,
+                block_hash,
+                position,
+            }) => {
+                let mut tx_print = match transaction {
+                    TransactionV1ToV2::V1(txn) => match receipt {
+                        Some(ReceiptV1ToV2::V1(receipt)) => {
+                            let tx_print: TransactionWithReceipt =
+                                From::<(
+                                    pchain_types::blockchain::TransactionV1,
+                                    pchain_types::blockchain::ReceiptV1,
+                                )>::from((txn, receipt));
+                            serde_json::to_value(tx_print).unwrap()
+                        }
+                        None => {
+                            let tx_print: Transaction =
+                                From::<pchain_types::blockchain::TransactionV1>::from(txn);
+                            serde_json::to_value(tx_print).unwrap()
+                        }
+                        _ => {
+                            DisplayMsg::CannotFindRelevantReceipt.exit();
+                        }
+                    },
+                    TransactionV1ToV2::V2(txn) => match receipt {
+                        Some(ReceiptV1ToV2::V2(receipt)) => {
+                            let tx_print: TransactionWithReceipt =
+                                From::<(
+                                    pchain_types::blockchain::TransactionV2,
+                                    pchain_types::blockchain::ReceiptV2,
+                                )>::from((txn, receipt));
+                            serde_json::to_value(tx_print).unwrap()
+                        }
+                        None => {
+                            let tx_print: Transaction =
+                                From::<pchain_types::blockchain::TransactionV2>::from(txn);
+                            serde_json::to_value(tx_print).unwrap()
+                        }
+                        _ => {
+                            DisplayMsg::CannotFindRelevantReceipt.exit();
+                        }
+                    },
+                };
+
+                // attach the on-chain location of the transaction when requested
+                if full {
+                    if let Value::Object(fields) = &mut tx_print {
+                        fields.insert(
+                            "block_hash".to_string(),
+                            serde_json::to_value(block_hash.map(base64url::encode)).unwrap(),
+                        );
+                        fields.insert(
+                            "position".to_string(),
+                            serde_json::to_value(position).unwrap(),
+                        );
                     }
-                },
-            },
+                }
+
+                display_json(tx_print)
+            }
             Err(e) => {
-                println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
-                std::process::exit(1);
+                DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
             }
             _ => {
-                println!("{}", DisplayMsg::CannotFindRelevantTransaction);
-                std::process::exit(1);
+                DisplayMsg::CannotFindRelevantTransaction.exit();
             }
         },
-        ClientResponse::Receipt(result) => match result {
+        ClientResponse::Receipt(result, summary, json) => match result {
             Ok(ReceiptResponseV2 {
                 transaction_hash: _,
                 receipt: Some(receipt),
                 block_hash: _,
                 position: _,
             }) => {
-                let receipt_print: Receipt = match receipt {
-                    ReceiptV1ToV2::V1(command_receipts) => command_receipts
-                        .into_iter()
-                        .map(From::<CommandReceiptV1>::from)
-                        .collect(),
-                    ReceiptV1ToV2::V2(receipt) => receipt
-                        .command_receipts
-                        .into_iter()
-                        .map(From::<CommandReceiptV2>::from)
-                        .collect(),
-                };
-                println!("{:#}", serde_json::to_value(receipt_print).unwrap())
+                let receipt_print = receipt_from_rpc(receipt);
+                if summary {
+                    display_receipt_summary(&receipt_print);
+                } else if json {
+                    let receipt_print = ReceiptWithTotalGas::from(receipt_print);
+                    display_json(serde_json::to_value(receipt_print).unwrap());
+                } else {
+                    let total_gas_used = total_gas_used(&receipt_print);
+                    display_json(serde_json::to_value(receipt_print).unwrap());
+                    display_text(format!("Total Gas Used: {}", total_gas_used));
+                }
             }
             Err(e) => {
-                println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
-                std::process::exit(1);
+                DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
             }
             _ => {
-                println!("{}", DisplayMsg::CannotFindRelevantReceipt);
-                std::process::exit(1);
+                DisplayMsg::CannotFindRelevantReceipt.exit();
+            }
+        },
+        ClientResponse::TxGas(result) => match result {
+            Ok(ReceiptResponseV2 {
+                receipt: Some(receipt),
+                ..
+            }) => {
+                display_json(Value::from(total_gas_used(&receipt_from_rpc(receipt))));
+            }
+            Err(e) => {
+                DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
+            }
+            _ => {
+                DisplayMsg::CannotFindRelevantReceipt.exit();
             }
         },
         ClientResponse::Contract(result, destination) => match result {
@@ -280,30 +457,26 @@ pub fn display_beautified_rpc_result(response: ClientResponse) {
                         let path =
                             PathBuf::from(&destination.unwrap_or_else(|| "code.wasm".to_string()));
                         match write_file(path.clone(), code) {
-                            Ok(full_path) => println!(
-                                "{}",
-                                DisplayMsg::SuccessCreateFile(
-                                    String::from("contract"),
-                                    PathBuf::from(full_path)
-                                )
-                            ),
-                            Err(e) => println!(
-                                "{}",
-                                DisplayMsg::FailToWriteFile(String::from("contract"), path, e)
-                            ),
+                            Ok(full_path) => display_text(DisplayMsg::SuccessCreateFile(
+                                String::from("contract"),
+                                PathBuf::from(full_path),
+                            )),
+                            Err(e) => display_text(DisplayMsg::FailToWriteFile(
+                                String::from("contract"),
+                                path,
+                                e,
+                            )),
                         }
                     } else {
-                        println!("{}", DisplayMsg::CannotFindRelevantContractCode);
+                        DisplayMsg::CannotFindRelevantContractCode.exit();
                     }
                 };
             },
             Ok(StateResponseV2::Error { error }) => {
-                println!("{}", DisplayMsg::RespnoseWithHTTPError(format!("{:?}", error)));
-                std::process::exit(1);
+                DisplayMsg::RespnoseWithHTTPError(format!("{:?}", error)).exit();
             },
             Err(e) => {
-                println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
-                std::process::exit(1);
+                DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
             }
         },
         ClientResponse::State(result) => {
@@ -320,17 +493,15 @@ pub fn display_beautified_rpc_result(response: ClientResponse) {
                     }
                 },
                 Ok(StateResponseV2::Error { error }) => {
-                    println!("{}", DisplayMsg::RespnoseWithHTTPError(format!("{:?}", error)));
-                    std::process::exit(1);
+                    DisplayMsg::RespnoseWithHTTPError(format!("{:?}", error)).exit();
                 },
                 Err(e) => {
-                    println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
-                    std::process::exit(1);
+                    DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
                 }
             };
 
-            let stringify_state: Base64String = state.map_or(String::new(), base64url::encode);
-            println!("{:#}", serde_json::to_value(stringify_state).unwrap())
+            let stringify_state: String = state.map_or(String::new(), utf8_or_base64);
+            display_json(serde_json::to_value(stringify_state).unwrap())
         }
         ClientResponse::Balance(result) => {
             let balance = match result {
@@ -353,16 +524,14 @@ pub fn display_beautified_rpc_result(response: ClientResponse) {
                     }
                 },
                 Ok(StateResponseV2::Error { error }) => {
-                    println!("{}", DisplayMsg::RespnoseWithHTTPError(format!("{:?}", error)));
-                    std::process::exit(1);
+                    DisplayMsg::RespnoseWithHTTPError(format!("{:?}", error)).exit();
                 },
                 Err(e) => {
-                    println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
-                    std::process::exit(1);
+                    DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
                 }
             };
 
-            println!("{:#}", serde_json::to_value(balance).unwrap())
+            display_amount(balance)
         }
         ClientResponse::Nonce(result) => {
             let nonce = match result {
@@ -385,59 +554,24 @@ pub fn display_beautified_rpc_result(response: ClientResponse) {
                     }
                 },
                 Ok(StateResponseV2::Error { error }) => {
-                    println!("{}", DisplayMsg::RespnoseWithHTTPError(format!("{:?}", error)));
-                    std::process::exit(1);
+                    DisplayMsg::RespnoseWithHTTPError(format!("{:?}", error)).exit();
                 },
                 Err(e) => {
-                    println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
-                    std::process::exit(1);
+                    DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
                 }
             };
 
-            println!("{:#}", serde_json::to_value(nonce).unwrap())
+            display_json(serde_json::to_value(nonce).unwrap())
+        }
+        ClientResponse::NextValidatorSet(result, true) => {
+            let validator_set = validator_set_or_exit(result);
+            display_json(serde_json::to_value(validator_set.next_validators()).unwrap())
         }
         ClientResponse::PreviousValidatorSet(result)
         | ClientResponse::CurrentValidatorSet(result)
-        | ClientResponse::NextValidatorSet(result) => {
-            let validator_set: Option<pchain_types::rpc::ValidatorSet> = match result {
-                Ok(ValidatorSetsResponse {
-                    previous_validator_set: Some(None),
-                    current_validator_set: None,
-                    next_validator_set: None,
-                    block_hash: _,
-                }) => None,
-                Ok(ValidatorSetsResponse {
-                    previous_validator_set: Some(Some(vs)),
-                    current_validator_set: None,
-                    next_validator_set: None,
-                    block_hash: _,
-                })
-                | Ok(ValidatorSetsResponse {
-                    previous_validator_set: None,
-                    current_validator_set: Some(vs),
-                    next_validator_set: None,
-                    block_hash: _,
-                })
-                | Ok(ValidatorSetsResponse {
-                    previous_validator_set: None,
-                    current_validator_set: None,
-                    next_validator_set: Some(vs),
-                    block_hash: _,
-                }) => Some(vs),
-                Err(e) => {
-                    println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
-                    std::process::exit(1);
-                }
-                _ => unreachable!(),
-            };
-
-            if let Some(vs) = validator_set {
-                let vs_print: ValidatorSet = From::<pchain_types::rpc::ValidatorSet>::from(vs);
-                println!("{:#}", serde_json::to_value(vs_print).unwrap())
-            } else {
-                println!("{}", DisplayMsg::CannotFindValidatorSet);
-                std::process::exit(1);
-            }
+        | ClientResponse::NextValidatorSet(result, false) => {
+            let validator_set = validator_set_or_exit(result);
+            display_json(serde_json::to_value(validator_set).unwrap())
         }
         ClientResponse::StakePower(result) => {
             let stake = match result {
@@ -452,20 +586,18 @@ pub fn display_beautified_rpc_result(response: ClientResponse) {
                     }
                 }
                 Err(e) => {
-                    println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
-                    std::process::exit(1);
+                    DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
                 }
             };
 
             if let Some(s) = stake {
                 let stake_print: Stake = From::<pchain_types::rpc::Stake>::from(s);
-                println!("{:#}", serde_json::to_value(stake_print).unwrap())
+                display_json(serde_json::to_value(stake_print).unwrap())
             } else {
-                println!("{}", DisplayMsg::CannotFindOperatorOwnerPair);
-                std::process::exit(1);
+                DisplayMsg::CannotFindOperatorOwnerPair.exit();
             }
         }
-        ClientResponse::Pool(result) => {
+        ClientResponse::Pool(result, flatten) => {
             let pool = match result {
                 Ok(PoolsResponse {
                     pools,
@@ -478,17 +610,20 @@ pub fn display_beautified_rpc_result(response: ClientResponse) {
                     }
                 }
                 Err(e) => {
-                    println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
-                    std::process::exit(1);
+                    DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
                 }
             };
 
             if let Some(p) = pool {
-                let pool_print: Pool = From::<pchain_types::rpc::Pool>::from(p);
-                println!("{:#}", serde_json::to_value(pool_print).unwrap())
+                if flatten {
+                    let pool_print: FlatPool = From::<pchain_types::rpc::Pool>::from(p);
+                    display_json(serde_json::to_value(pool_print).unwrap())
+                } else {
+                    let pool_print: Pool = From::<pchain_types::rpc::Pool>::from(p);
+                    display_json(serde_json::to_value(pool_print).unwrap())
+                }
             } else {
-                println!("{}", DisplayMsg::CannotFindOperatorOwnerPair);
-                std::process::exit(1);
+                DisplayMsg::CannotFindOperatorOwnerPair.exit();
             }
         }
         ClientResponse::Deposit(result) => {
@@ -504,17 +639,15 @@ pub fn display_beautified_rpc_result(response: ClientResponse) {
                     }
                 }
                 Err(e) => {
-                    println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
-                    std::process::exit(1);
+                    DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
                 }
             };
 
             if let Some(d) = deposit {
                 let deposit_print: Deposit = From::<pchain_types::rpc::Deposit>::from(d);
-                println!("{:#}", serde_json::to_value(deposit_print).unwrap())
+                display_json(serde_json::to_value(deposit_print).unwrap())
             } else {
-                println!("{}", DisplayMsg::CannotFindOperatorOwnerPair);
-                std::process::exit(1);
+                DisplayMsg::CannotFindOperatorOwnerPair.exit();
             }
         }
         ClientResponse::View(result) => {
@@ -528,13 +661,255 @@ pub fn display_beautified_rpc_result(response: ClientResponse) {
                     }
                 },
                 Err(e) => {
-                    println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
-                    std::process::exit(1);
+                    DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
                 }
             };
-            println!("{:#}", serde_json::to_value(receipt_print).unwrap())
+            display_json(serde_json::to_value(receipt_print).unwrap())
+        }
+    }
+}
+
+/// `validator_set_or_exit` takes the validator set out of a response of Fullnode RPC which requests
+///  exactly one validator set. It displays the error and exits the program if there is none.
+/// # Arguments
+///  * `result` - response to a validator sets request
+///
+fn validator_set_or_exit(result: Result<ValidatorSetsResponse, ErrorResponse>) -> ValidatorSet {
+    let validator_set: Option<pchain_types::rpc::ValidatorSet> = match result {
+        Ok(ValidatorSetsResponse {
+            previous_validator_set: Some(None),
+            current_validator_set: None,
+            next_validator_set: None,
+            block_hash: _,
+        }) => None,
+        Ok(ValidatorSetsResponse {
+            previous_validator_set: Some(Some(vs)),
+            current_validator_set: None,
+            next_validator_set: None,
+            block_hash: _,
+        })
+        | Ok(ValidatorSetsResponse {
+            previous_validator_set: None,
+            current_validator_set: Some(vs),
+            next_validator_set: None,
+            block_hash: _,
+        })
+        | Ok(ValidatorSetsResponse {
+            previous_validator_set: None,
+            current_validator_set: None,
+            next_validator_set: Some(vs),
+            block_hash: _,
+        }) => Some(vs),
+        Err(e) => {
+            DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
+        }
+        _ => unreachable!(),
+    };
+
+    match validator_set {
+        Some(vs) => From::<pchain_types::rpc::ValidatorSet>::from(vs),
+        None => DisplayMsg::CannotFindValidatorSet.exit(),
+    }
+}
+
+// `receipt_from_rpc` converts a receipt returned by Fullnode RPC to its display_types equivalent.
+// # Arguments
+// * `receipt` - V1 or V2 receipt from the RPC response
+//
+pub fn receipt_from_rpc(receipt: ReceiptV1ToV2) -> Receipt {
+    match receipt {
+        ReceiptV1ToV2::V1(command_receipts) => receipt_from_v1(command_receipts, None),
+        ReceiptV1ToV2::V2(receipt) => receipt
+            .command_receipts
+            .into_iter()
+            .map(From::<CommandReceiptV2>::from)
+            .collect(),
+    }
+}
+
+// `display_receipt_summary` prints a compact table with the exit code and gas used of
+//  each command in a receipt, followed by the total gas used.
+// # Arguments
+// * `receipt` - receipt to be summarized
+//
+fn display_receipt_summary(receipt: &Receipt) {
+    display_text(format!(
+        "{:<15} {:<15} Gas Used",
+        "Command Index", "Exit Code"
+    ));
+    display_text(format!(
+        "{:<15} {:<15} --------",
+        "-------------", "---------"
+    ));
+    for (index, command_receipt) in receipt.iter().enumerate() {
+        display_text(format!(
+            "{:<15} {:<15} {}",
+            index,
+            command_receipt.exit_code(),
+            command_receipt.gas_used()
+        ));
+    }
+    display_text(format!("Total Gas Used: {}", total_gas_used(receipt)));
+}
+
+// `display_json` prints a JSON value to the terminal, applying the global display options.
+// # Arguments
+// * `value` - JSON value to be displayed
+//
+pub fn display_json(mut value: Value) {
+    let options = display_options();
+    if options.precision.is_some() {
+        map_amount_fields(&mut value, &format_amount);
+    } else if options.group_digits {
+        group_amount_fields(&mut value);
+    }
+    if !capture(|| value.clone()) {
+        println!("{:#}", value);
+    }
+}
+
+// `display_amount` prints a standalone amount (e.g. an account balance) to the terminal.
+// # Arguments
+// * `amount` - amount in Grays
+//
+fn display_amount(amount: u64) {
+    let options = display_options();
+    if options.precision.is_some() || options.group_digits {
+        display_text(format_amount(amount));
+    } else if !capture(|| Value::from(amount)) {
+        println!("{}", amount);
+    }
+}
+
+// `display_text` prints a line of a result which is not JSON, e.g. a table row or a message.
+// # Arguments
+// * `text` - line to be displayed
+//
+pub(crate) fn display_text(text: impl ToString) {
+    let text = text.to_string();
+    if !capture(|| Value::String(text.clone())) {
+        println!("{}", text);
+    }
+}
+
+// `format_amount` formats an amount in Grays according to the global display options. Under `--precision`,
+//  the amount in XPLL is shown together with the exact amount in Grays, e.g. "1.2346 XPLL (123456789 Grays)".
+// # Arguments
+// * `amount` - amount in Grays
+//
+fn format_amount(amount: u64) -> String {
+    let options = display_options();
+    let grays = if options.group_digits {
+        group_digits(amount)
+    } else {
+        amount.to_string()
+    };
+
+    match options.precision {
+        Some(precision) => format!(
+            "{} XPLL ({} Grays)",
+            format_xpll(amount, precision, options.group_digits),
+            grays
+        ),
+        None => grays,
+    }
+}
+
+// `format_xpll` converts an amount in Grays to XPLL, rounded half up to the given number of fractional digits.
+// # Arguments
+// * `amount` - amount in Grays
+// * `precision` - number of fractional digits, at most `XPLL_DECIMALS`
+// * `group` - group digits of the integer part with commas
+//
+fn format_xpll(amount: u64, precision: u32, group: bool) -> String {
+    let precision = precision.min(XPLL_DECIMALS);
+    let scale = 10u128.pow(XPLL_DECIMALS - precision);
+    let rounded = (amount as u128 + scale / 2) / scale;
+    let unit = 10u128.pow(precision);
+
+    // the integer part is at most u64::MAX / 10^8 rounded up, which fits in u64
+    let integer = (rounded / unit) as u64;
+    let integer = if group {
+        group_digits(integer)
+    } else {
+        integer.to_string()
+    };
+    if precision == 0 {
+        integer
+    } else {
+        format!(
+            "{}.{:0width$}",
+            integer,
+            rounded % unit,
+            width = precision as usize
+        )
+    }
+}
+
+// `group_amount_fields` recursively replaces the numeric values of `AMOUNT_FIELDS` in a JSON value
+//  with digit grouped strings.
+// # Arguments
+// * `value` - JSON value to be modified in place
+//
+fn group_amount_fields(value: &mut Value) {
+    map_amount_fields(value, &group_digits)
+}
+
+// `map_amount_fields` recursively replaces the numeric values of `AMOUNT_FIELDS` in a JSON value
+//  with the strings they are formatted to.
+// # Arguments
+// * `value` - JSON value to be modified in place
+// * `format` - formats an amount in Grays
+//
+fn map_amount_fields(value: &mut Value, format: &dyn Fn(u64) -> String) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields.iter_mut() {
+                match field.as_u64() {
+                    Some(amount) if AMOUNT_FIELDS.contains(&key.as_str()) => {
+                        *field = Value::String(format(amount))
+                    }
+                    _ => map_amount_fields(field, format),
+                }
+            }
         }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| map_amount_fields(item, format)),
+        _ => {}
+    }
+}
+
+// `format_address` abbreviates an address to its first and last characters, e.g. "kRPL…Pv_A", under
+//  `--compact-address`. The address is returned unchanged otherwise.
+// # Arguments
+// * `address` - Base64url encoded address
+//
+pub(crate) fn format_address(address: &str) -> String {
+    let chars: Vec<char> = address.chars().collect();
+    if !display_options().compact_address || chars.len() <= 2 * COMPACT_ADDRESS_CHARS + 1 {
+        return address.to_string();
     }
+
+    let head: String = chars[..COMPACT_ADDRESS_CHARS].iter().collect();
+    let tail: String = chars[chars.len() - COMPACT_ADDRESS_CHARS..].iter().collect();
+    format!("{head}…{tail}")
+}
+
+// `group_digits` formats an integer with a comma between every group of three digits,
+//  e.g. 1234567 becomes "1,234,567".
+// # Arguments
+// * `number` - integer to be formatted
+//
+fn group_digits(number: u64) -> String {
+    let digits = number.to_string();
+    let groups: Vec<&str> = digits
+        .as_bytes()
+        .rchunks(3)
+        .rev()
+        .map(|group| std::str::from_utf8(group).unwrap())
+        .collect();
+    groups.join(",")
 }
 
 // `display_beautified_json` converts the response of a CLI command
@@ -550,7 +925,7 @@ pub fn display_beautified_json(response: Vec<(&str, Value)>) {
     let beautified_json: Value =
         serde_json::from_str(&serde_json::to_string_pretty(&response_map).unwrap()).unwrap();
 
-    println!("{:#}", beautified_json);
+    display_json(beautified_json);
 }
 
 // `display_beautified_json_array` converts the response of a CLI command
@@ -568,32 +943,179 @@ pub fn display_beautified_json_array(response: Vec<(&str, Value)>) {
     let beautified_json: Value =
         serde_json::from_str(&serde_json::to_string_pretty(&response_array).unwrap()).unwrap();
 
-    println!("{:#}", beautified_json);
+    display_json(beautified_json);
 }
 
 // [ClientResponse] defines types that are used by the result module to process
 // different kinds of responses sent by the pchain_client library to the CLI.
+#[derive(Debug)]
 pub enum ClientResponse {
     SubmitTx(
         Result<SubmitTransactionResponseV2, ErrorResponse>,
         pchain_types::rpc::TransactionV1OrV2,
+        bool,
     ),
     Balance(Result<StateResponseV2, ErrorResponse>),
     Nonce(Result<StateResponseV2, ErrorResponse>),
     Contract(Result<StateResponseV2, ErrorResponse>, Option<Destination>),
-    Block(Result<BlockResponseV2, ErrorResponse>),
+    Block(Result<BlockResponseV2, ErrorResponse>, bool),
     BlockHeader(Result<BlockHeaderResponseV2, ErrorResponse>),
-    Transaction(Result<TransactionResponseV2, ErrorResponse>),
-    Receipt(Result<ReceiptResponseV2, ErrorResponse>),
+    Transaction(Result<TransactionResponseV2, ErrorResponse>, bool, bool),
+    Receipt(Result<ReceiptResponseV2, ErrorResponse>, bool, bool),
+    TxGas(Result<ReceiptResponseV2, ErrorResponse>),
+    Epoch(Result<BlockHeaderResponseV2, ErrorResponse>, u64),
     State(Result<StateResponseV2, ErrorResponse>),
     PreviousValidatorSet(Result<ValidatorSetsResponse, ErrorResponse>),
     CurrentValidatorSet(Result<ValidatorSetsResponse, ErrorResponse>),
-    NextValidatorSet(Result<ValidatorSetsResponse, ErrorResponse>),
-    Pool(Result<PoolsResponse, ErrorResponse>),
+    NextValidatorSet(Result<ValidatorSetsResponse, ErrorResponse>, bool),
+    Pool(Result<PoolsResponse, ErrorResponse>, bool),
     Deposit(Result<DepositsResponse, ErrorResponse>),
     StakePower(Result<StakesResponse, ErrorResponse>),
     View(Result<ViewResponseV2, ErrorResponse>),
 }
 
-type ErrorResponse = String;
+/// [ErrorResponse] is a failed request to Fullnode RPC, with the HTTP status of the response if the Fullnode responded at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorResponse {
+    pub message: String,
+    pub status: Option<u16>,
+}
+
+impl ErrorResponse {
+    /// Whether the request may succeed if sent again. Server errors (5xx) and failures to connect or to get a
+    /// response in time, e.g. a connection reset, can be transient. Client errors (4xx) and other failures, e.g. a
+    /// response which cannot be decoded, fail the same way every time.
+    pub fn is_retryable(&self) -> bool {
+        match self.status {
+            Some(status) => status >= 500,
+            None => {
+                let message = self.message.to_ascii_lowercase();
+                TRANSIENT_FAILURES
+                    .iter()
+                    .any(|failure| message.contains(failure))
+            }
+        }
+    }
+}
+
+/// Parts of the error messages of the client for failures to connect or to get a response in time.
+const TRANSIENT_FAILURES: [&str; 5] = [
+    "error sending request",
+    "connection",
+    "timed out",
+    "timeout",
+    "dns error",
+];
+
+impl From<String> for ErrorResponse {
+    // The client reports errors as text, e.g. "HTTP status server error (503 Service Unavailable) for url (...)",
+    // so the status is the first three-digit code after "HTTP status".
+    fn from(message: String) -> Self {
+        let status = message
+            .to_ascii_lowercase()
+            .find("http status")
+            .and_then(|index| {
+                message[index..]
+                    .split(|c: char| !c.is_ascii_digit())
+                    .find(|digits| digits.len() == 3)
+                    .and_then(|digits| digits.parse::<u16>().ok())
+                    .filter(|status| (100..600).contains(status))
+            });
+        Self { message, status }
+    }
+}
+
+impl std::fmt::Display for ErrorResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
 type Destination = String;
+
+#[cfg(test)]
+mod test {
+    use super::{format_xpll, group_amount_fields, group_digits, ErrorResponse};
+    use crate::display_types::utf8_or_base64;
+    use serde_json::json;
+
+    #[test]
+    fn test_group_digits() {
+        assert_eq!(group_digits(0), "0");
+        assert_eq!(group_digits(999), "999");
+        assert_eq!(group_digits(1000), "1,000");
+        assert_eq!(group_digits(u64::MAX), "18,446,744,073,709,551,615");
+    }
+
+    #[test]
+    fn test_group_amount_fields() {
+        let mut value = json!({
+            "nonce": 12345,
+            "commands": [{"Transfer": {"recipient": "abc", "amount": 1000000}}],
+            "operator_stake": {"owner": "abc", "power": 2500},
+        });
+        group_amount_fields(&mut value);
+
+        assert_eq!(value["nonce"], json!(12345));
+        assert_eq!(
+            value["commands"][0]["Transfer"]["amount"],
+            json!("1,000,000")
+        );
+        assert_eq!(value["operator_stake"]["power"], json!("2,500"));
+    }
+
+    #[test]
+    fn test_utf8_or_base64() {
+        assert_eq!(utf8_or_base64(b"hello".to_vec()), "(UTF8) hello");
+        assert_eq!(
+            utf8_or_base64(vec![0xff, 0x00]),
+            format!("(Base64 encoded) {}", base64url::encode([0xff, 0x00]))
+        );
+    }
+
+    #[test]
+    fn test_format_xpll() {
+        assert_eq!(format_xpll(0, 4, false), "0.0000");
+        assert_eq!(format_xpll(123_456_789, 4, false), "1.2346");
+        assert_eq!(format_xpll(123_456_789, 8, false), "1.23456789");
+        assert_eq!(format_xpll(150_000_000, 0, false), "2");
+        assert_eq!(format_xpll(99_995_000, 4, false), "1.0000");
+        assert_eq!(format_xpll(123_456_700_000_000, 2, true), "1,234,567.00");
+        assert_eq!(format_xpll(u64::MAX, 0, false), "184467440737");
+    }
+
+    #[test]
+    fn test_error_response_status() {
+        let error = ErrorResponse::from(String::from(
+            "HTTP status server error (503 Service Unavailable) for url (https://rpc/state)",
+        ));
+        assert_eq!(error.status, Some(503));
+        assert!(error.is_retryable());
+
+        let error = ErrorResponse::from(String::from(
+            "HTTP status client error (400 Bad Request) for url (https://rpc/state)",
+        ));
+        assert_eq!(error.status, Some(400));
+        assert!(!error.is_retryable());
+
+        let error = ErrorResponse::from(String::from("error sending request: connection reset"));
+        assert_eq!(error.status, None);
+        assert!(error.is_retryable());
+
+        let error = ErrorResponse::from(String::from(
+            "error sending request for url (https://rpc/state): operation timed out",
+        ));
+        assert_eq!(error.status, None);
+        assert!(error.is_retryable());
+
+        let error = ErrorResponse::from(String::from(
+            "error decoding response body: invalid status 200 at line 1",
+        ));
+        assert_eq!(error.status, None);
+        assert!(!error.is_retryable());
+
+        let error = ErrorResponse::from(String::from("Fail to read the query cache"));
+        assert_eq!(error.status, None);
+        assert!(!error.is_retryable());
+    }
+}
@@ -7,37 +7,288 @@
 
 use config::Config;
 use pchain_client::{Client, NetworkProvider};
+use std::path::PathBuf;
 
-use crate::command::ConfigCommand;
+use crate::command::{ConfigCommand, ContactsCommand};
 use crate::config;
 use crate::display_msg::DisplayMsg;
+use crate::result::format_address;
+use crate::sub_commands::decode_address_or_exit;
+use crate::utils::{self, read_file_to_utf8string, warn, write_file};
+
+// `display_config_paths` displays the home directory and the files in it, marking those which do not exist.
+//  It reads nothing, so it is run before the config file is loaded, e.g. to find a config file which is broken.
+//
+pub fn display_config_paths() {
+    for (name, path) in [
+        ("Home Directory", config::get_home_dir()),
+        ("Config File", config::get_config_path()),
+        ("Keypair File", config::get_keypair_path()),
+        ("Password Hash File", config::get_hash_path()),
+        ("Address Book File", config::get_contacts_path()),
+        (
+            "Submitted Transactions Ledger",
+            config::get_submitted_ledger_path(),
+        ),
+    ] {
+        let exists = path.exists();
+        println!("{}", DisplayMsg::ListPath(String::from(name), path, exists));
+    }
+}
 
 // `match_setup_subcommand` matches a CLI argument to its corresponding `Setup` subcommand and processes
 //  the request.
 //  # Arguments
 //  * `setup_subcommand` - setup subcommand from CLI
+//  * `proxy_url` - proxy given by `--proxy`, which is saved, or removed if empty, by `config setup`
+//  * `ca_cert_path` - CA bundle given by `--ca-cert`, which is saved, or removed if empty, by `config setup`
 //
-pub async fn match_setup_subcommand(setup_subcommand: ConfigCommand) {
+pub async fn match_setup_subcommand(
+    setup_subcommand: ConfigCommand,
+    proxy_url: Option<String>,
+    ca_cert_path: Option<String>,
+) {
     match setup_subcommand {
-        ConfigCommand::Setup { url } => {
-            let url = url.trim().trim_end_matches('/').to_string();
-            if !Client::new(&url).is_provider_up().await {
-                println!("{}", DisplayMsg::InavtiveRPCProvider(url));
-                std::process::exit(1);
+        ConfigCommand::Setup { url, ws_url } => {
+            let mut config = Config::load();
+            if let Some(url) = url {
+                let url = url.trim().trim_end_matches('/').to_string();
+                if is_websocket_url(&url) {
+                    println!("{}", DisplayMsg::UnsupportedWebSocketRPCProvider(url));
+                    utils::exit(1);
+                }
+                if !Client::new(&url).is_provider_up().await {
+                    println!("{}", DisplayMsg::InavtiveRPCProvider(url));
+                    utils::exit(1);
+                }
+                config.url = url;
             }
 
-            Config::load().update(&url);
+            // an empty value removes the saved one
+            if let Some(ws_url) = ws_url {
+                let ws_url = ws_url.trim().trim_end_matches('/').to_string();
+                if ws_url.is_empty() {
+                    config.ws_url = None;
+                } else if !is_websocket_url(&ws_url) {
+                    println!("{}", DisplayMsg::NotWebSocketRPCProvider(ws_url));
+                    utils::exit(1);
+                } else if tokio_tungstenite::connect_async(ws_url.as_str())
+                    .await
+                    .is_err()
+                {
+                    println!("{}", DisplayMsg::InavtiveRPCProvider(ws_url));
+                    utils::exit(1);
+                } else {
+                    config.ws_url = Some(ws_url);
+                }
+            }
+            if let Some(proxy_url) = proxy_url {
+                config.proxy_url = Some(proxy_url).filter(|proxy_url| !proxy_url.is_empty());
+            }
+            if let Some(ca_cert_path) = ca_cert_path {
+                config.ca_cert_path =
+                    Some(ca_cert_path).filter(|ca_cert_path| !ca_cert_path.is_empty());
+            }
+            config.save();
         }
         ConfigCommand::List => {
             let config = Config::load();
             let url = config.get_url();
 
             println!("{}", DisplayMsg::ListRPCProvider(url.to_string()));
+            if let Some(proxy_url) = &config.proxy_url {
+                println!("{}", DisplayMsg::ListProxy(proxy_url.to_string()));
+            }
+            if let Some(ca_cert_path) = &config.ca_cert_path {
+                println!("{}", DisplayMsg::ListCACert(PathBuf::from(ca_cert_path)));
+            }
+            if let Some(ws_url) = &config.ws_url {
+                println!(
+                    "{}",
+                    DisplayMsg::ListWebSocketRPCProvider(ws_url.to_string())
+                );
+            }
+            for (name, value) in config.gas_defaults() {
+                if let Some(value) = value {
+                    println!("{}", DisplayMsg::ListGasDefault(String::from(name), value));
+                }
+            }
             if !Client::new(url).is_provider_up().await {
-                println!("{}", DisplayMsg::InavtiveRPCProvider(String::from(url)));
+                warn(DisplayMsg::InavtiveRPCProvider(String::from(url)));
             } else {
                 println!("{}", DisplayMsg::ActiveRPCProvider(String::from(url)))
             }
         }
+        ConfigCommand::Paths => display_config_paths(),
+        ConfigCommand::Export { destination } => {
+            let path = PathBuf::from(
+                destination.unwrap_or_else(|| "pchain_client_config.toml".to_string()),
+            );
+            let toml_string = match toml::to_string(&Config::load()) {
+                Ok(toml_string) => toml_string,
+                Err(e) => {
+                    println!(
+                        "{}",
+                        DisplayMsg::FailToTOMLEncode(String::from("config"), path, e.to_string())
+                    );
+                    utils::exit(1);
+                }
+            };
+
+            match write_file(path.clone(), toml_string.as_bytes()) {
+                Ok(full_path) => println!(
+                    "{}",
+                    DisplayMsg::SuccessCreateFile(String::from("Config"), PathBuf::from(full_path))
+                ),
+                Err(e) => {
+                    println!(
+                        "{}",
+                        DisplayMsg::FailToWriteFile(String::from("config"), path, e)
+                    );
+                    utils::exit(1);
+                }
+            }
+        }
+        ConfigCommand::Import { file } => {
+            let path = PathBuf::from(file);
+            let contents = match read_file_to_utf8string(path.clone()) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    println!(
+                        "{}",
+                        DisplayMsg::FailToOpenOrReadFile(String::from("config"), path, e)
+                    );
+                    utils::exit(1);
+                }
+            };
+
+            let imported_config: Config = match toml::from_str(&contents) {
+                Ok(config) => config,
+                Err(e) => {
+                    println!(
+                        "{}",
+                        DisplayMsg::InvalidTOMLFormat(String::from("config"), path, e.to_string())
+                    );
+                    utils::exit(1);
+                }
+            };
+
+            let url = imported_config.get_url();
+            if is_websocket_url(url) {
+                println!(
+                    "{}",
+                    DisplayMsg::UnsupportedWebSocketRPCProvider(String::from(url))
+                );
+                utils::exit(1);
+            }
+            if !Client::new(url).is_provider_up().await {
+                println!("{}", DisplayMsg::InavtiveRPCProvider(String::from(url)));
+                utils::exit(1);
+            }
+
+            imported_config.save();
+        }
+        ConfigCommand::SetDefaults {
+            gas_limit,
+            max_base_fee_per_gas,
+            priority_fee_per_gas,
+        } => {
+            let mut config = Config::load();
+            if gas_limit.is_some() {
+                config.default_gas_limit = gas_limit;
+            }
+            if max_base_fee_per_gas.is_some() {
+                config.default_max_base_fee_per_gas = max_base_fee_per_gas;
+            }
+            if priority_fee_per_gas.is_some() {
+                config.default_priority_fee_per_gas = priority_fee_per_gas;
+            }
+            config.write();
+
+            println!("{}", DisplayMsg::SuccessSetGasDefaults);
+            for (name, value) in config.gas_defaults() {
+                if let Some(value) = value {
+                    println!("{}", DisplayMsg::ListGasDefault(String::from(name), value));
+                }
+            }
+        }
+        ConfigCommand::Contacts {
+            contacts_subcommand,
+        } => match_contacts_subcommand(contacts_subcommand),
+    };
+}
+
+// `match_contacts_subcommand` adds, lists or removes entries of the address book.
+//  # Arguments
+//  * `contacts_subcommand` - contacts subcommand from CLI
+//
+fn match_contacts_subcommand(contacts_subcommand: ContactsCommand) {
+    let mut contacts = match config::load_contacts() {
+        Ok(contacts) => contacts,
+        Err(e) => {
+            println!("{}", e);
+            utils::exit(1);
+        }
     };
+
+    match contacts_subcommand {
+        ContactsCommand::Add { name, address } => {
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                println!("{}", DisplayMsg::InvalidContactName(name));
+                utils::exit(1);
+            }
+            if contacts.contains_key(&name) {
+                println!("{}", DisplayMsg::ContactAlreadyExists(name));
+                utils::exit(1);
+            }
+            decode_address_or_exit("contact", &address);
+
+            contacts.insert(name.clone(), address.clone());
+            match config::save_contacts(&contacts) {
+                Ok(()) => println!("{}", DisplayMsg::SuccessAddContact(name, address)),
+                Err(e) => {
+                    println!("{}", e);
+                    utils::exit(1);
+                }
+            }
+        }
+        ContactsCommand::List => {
+            println!("{:<30} Address", "Name");
+            println!("{:<30} -------", "----");
+            for (name, address) in contacts {
+                println!(
+                    "{:<30} {}",
+                    format!("{}{}", config::CONTACT_PREFIX, name),
+                    format_address(&address)
+                );
+            }
+        }
+        ContactsCommand::Remove { name } => {
+            if contacts.remove(&name).is_none() {
+                println!("{}", DisplayMsg::ContactNotFound(name));
+                utils::exit(1);
+            }
+            match config::save_contacts(&contacts) {
+                Ok(()) => println!("{}", DisplayMsg::SuccessRemoveContact(name)),
+                Err(e) => {
+                    println!("{}", e);
+                    utils::exit(1);
+                }
+            }
+        }
+    }
+}
+
+// `is_websocket_url` checks whether a url is a WebSocket endpoint. URL schemes are case-insensitive,
+//  so `WSS://` is one too.
+//  # Arguments
+//  * `url` - RPC url to be checked
+//
+fn is_websocket_url(url: &str) -> bool {
+    let url = url.to_ascii_lowercase();
+    url.starts_with("ws://") || url.starts_with("wss://")
 }
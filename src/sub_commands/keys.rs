@@ -6,12 +6,23 @@
 //! Methods related to subcommand `crypto` in `pchain-client`.
 use crate::command::Keys;
 use crate::display_msg::DisplayMsg;
+use crate::display_types::SubmitTx;
 use crate::keypair::{
-    add_keypair, append_keypair_to_json, generate_keypair, get_keypair_from_json,
-    load_existing_keypairs,
+    add_external_keypair, add_keypair, append_keypair_to_json, check_keypair_consistency,
+    derive_public_key, generate_keypair, generate_mnemonic_keypair, get_keypair_from_json,
+    load_existing_keypairs, load_existing_keypairs_with_passphrase, restore_mnemonic_keypair,
+    save_keypairs_to_json_with_passphrase, sign_externally, signing_key, verify_exported_keypair,
+    verify_signature, KeypairJSON,
 };
+use crate::parser::decode_base64url;
+use crate::result::{display_json, format_address};
+use crate::sub_commands::BatchErrors;
 use crate::{config, utils};
 use ed25519_dalek::Signer;
+use pchain_types::rpc::TransactionV1OrV2;
+use pchain_types::serialization::Serializable;
+use serde_json::json;
+use std::collections::BTreeMap;
 
 // `match_crypto_subcommand` matches a CLI argument to its corresponding `Crypto` subcommand and processes
 //  the request.
@@ -19,11 +30,23 @@ use ed25519_dalek::Signer;
 //  * `crypto_subcommand` - crypto subcommand from CLI
 //
 pub fn match_crypto_subcommand(crypto_subcommand: Keys) {
-    use std::convert::TryFrom;
     match crypto_subcommand {
-        Keys::List => {
+        Keys::List { filter, json } => {
             match load_existing_keypairs(config::get_keypair_path()) {
                 Ok(keypairs) => {
+                    let keypairs = keypairs.into_iter().filter(|kp| match &filter {
+                        Some(filter) => kp.name.contains(filter.as_str()),
+                        None => true,
+                    });
+                    if json {
+                        display_json(
+                            keypairs
+                                .map(|kp| json!({ "name": kp.name, "public_key": kp.public_key }))
+                                .collect(),
+                        );
+                        return;
+                    }
+
                     let title = "Keypair Name (First 50 char)";
                     let padding_filler = "";
                     println!(
@@ -37,109 +60,557 @@ pub fn match_crypto_subcommand(crypto_subcommand: Keys) {
                         println!(
                             "{} {padding_filler:>padding_len$} {}",
                             &kp.name[..std::cmp::min(50, kp.name.len())],
-                            kp.public_key
+                            format_address(&kp.public_key)
                         );
                     }
                 }
                 Err(e) => {
                     println!("{}", e);
-                    std::process::exit(1);
+                    utils::exit(1);
                 }
             }
         }
-        Keys::Create { keypair_name } => {
-            let name = keypair_name.unwrap_or_else(utils::get_random_string);
-            let keypair = generate_keypair(&name);
-            let public_key = keypair.public_key.clone();
-
-            match append_keypair_to_json(config::get_keypair_path(), keypair) {
-                Ok(_) => println!("{}", DisplayMsg::SuccessCreateKey(name, public_key)),
+        Keys::Show {
+            keypair_name,
+            qr,
+            qr_png,
+        } => {
+            let keypair = match get_keypair_from_json(config::get_keypair_path(), &keypair_name) {
+                Ok(Some(kp)) => kp,
+                Ok(None) => {
+                    println!("{}", DisplayMsg::KeypairNotFound(keypair_name));
+                    utils::exit(1);
+                }
                 Err(e) => {
                     println!("{}", e);
-                    std::process::exit(1);
+                    utils::exit(1);
                 }
             };
+
+            println!("Keypair name: {}", keypair.name);
+            println!("Public key: {}", keypair.public_key);
+
+            if qr {
+                match utils::qr_code_to_string(&keypair.public_key) {
+                    Ok(qr_code) => println!("{}", qr_code),
+                    Err(e) => {
+                        println!("{}", DisplayMsg::FailToCreateQRCode(e));
+                        utils::exit(1);
+                    }
+                }
+            }
+
+            if let Some(qr_png) = qr_png {
+                let path = std::path::PathBuf::from(qr_png);
+                match utils::qr_code_to_png(&keypair.public_key)
+                    .and_then(|png| utils::write_file(path.clone(), &png))
+                {
+                    Ok(saved_path) => {
+                        println!("QR code of public address is saved at {}", saved_path)
+                    }
+                    Err(e) => {
+                        println!(
+                            "{}",
+                            DisplayMsg::FailToWriteFile(String::from("QR code"), path, e)
+                        );
+                        utils::exit(1);
+                    }
+                }
+            }
+        }
+        Keys::Address { private_key } => match derive_public_key(&private_key) {
+            Ok(public_key) => println!("Public key: {}", public_key),
+            Err(e) => {
+                println!("{}", e);
+                utils::exit(1);
+            }
+        },
+        Keys::Create {
+            keypair_name,
+            json,
+            dry_run,
+            mnemonic,
+        } => {
+            let name = keypair_name.unwrap_or_else(utils::get_random_string);
+            let (keypair, phrase) = if mnemonic {
+                let (keypair, phrase) = generate_mnemonic_keypair(&name);
+                (keypair, Some(phrase))
+            } else {
+                (generate_keypair(&name), None)
+            };
+            let public_key = keypair.public_key.clone();
+
+            if dry_run {
+                display_dry_run_keypair(name, public_key, json);
+                return;
+            }
+
+            if let Err(e) = append_keypair_to_json(config::get_keypair_path(), keypair) {
+                println!("{}", e);
+                utils::exit(1);
+            }
+
+            match phrase {
+                Some(phrase) if json => display_json(
+                    json!({ "name": name, "public_key": public_key, "mnemonic": phrase }),
+                ),
+                Some(phrase) => {
+                    println!("{}", DisplayMsg::SuccessCreateKey(name, public_key));
+                    println!("{}", DisplayMsg::MnemonicShownOnce(phrase));
+                }
+                None if json => display_json(json!({ "name": name, "public_key": public_key })),
+                None => println!("{}", DisplayMsg::SuccessCreateKey(name, public_key)),
+            }
         }
         Keys::Import {
             private_key,
             public_key,
             keypair_name,
+            json,
+            dry_run,
         } => {
             let keypair = match add_keypair(&private_key, &public_key, &keypair_name) {
                 Ok(kp) => kp,
                 Err(e) => {
                     println!("{}", e);
-                    std::process::exit(1);
+                    utils::exit(1);
+                }
+            };
+            let public_key = keypair.public_key.clone();
+
+            if dry_run {
+                display_dry_run_keypair(keypair_name, public_key, json);
+                return;
+            }
+            if let Err(e) = append_keypair_to_json(config::get_keypair_path(), keypair) {
+                println!("{}", e);
+                utils::exit(1);
+            }
+
+            if json {
+                display_json(json!({ "name": keypair_name, "public_key": public_key }));
+            } else {
+                println!("{}", DisplayMsg::SuccessAddKey(keypair_name));
+            }
+        }
+        Keys::ImportExternal {
+            public_key,
+            signer_command,
+            keypair_name,
+        } => {
+            let keypair = match add_external_keypair(&public_key, &signer_command, &keypair_name) {
+                Ok(kp) => kp,
+                Err(e) => {
+                    println!("{}", e);
+                    utils::exit(1);
                 }
             };
             if let Err(e) = append_keypair_to_json(config::get_keypair_path(), keypair) {
                 println!("{}", e);
-                std::process::exit(1);
+                utils::exit(1);
             }
 
             println!("{}", DisplayMsg::SuccessAddKey(keypair_name));
         }
-        Keys::Sign { message, keypair_name } => {
-            let keypair = match get_keypair_from_json(config::get_keypair_path(), &keypair_name) {
-                Ok(Some(kp)) => {
-                    let keypair_bs = match base64url::decode(&kp.keypair) {
-                        Ok(kp) => kp,
-                        Err(e) => {
-                            println!(
-                                "{}",
-                                DisplayMsg::FailToDecodeBase64String(
-                                    String::from("keypair name"),
-                                    keypair_name,
-                                    e.to_string()
-                                )
-                            );
-                            std::process::exit(1);
-                        }
-                    };
-                    match ed25519_dalek::SigningKey::from_keypair_bytes(
-                        &<[u8; 64]>::try_from(&keypair_bs[..]).unwrap(),
-                    ) {
-                        Ok(kp) => kp,
-                        Err(e) => {
-                            println!("{}", DisplayMsg::InvalidEd25519Keypair(e.to_string()));
-                            std::process::exit(1);
-                        }
-                    }
+        Keys::ImportMnemonic {
+            phrase,
+            keypair_name,
+        } => {
+            let keypair = match restore_mnemonic_keypair(&phrase, &keypair_name) {
+                Ok(kp) => kp,
+                Err(e) => {
+                    println!("{}", e);
+                    utils::exit(1);
                 }
+            };
+            if let Err(e) = append_keypair_to_json(config::get_keypair_path(), keypair) {
+                println!("{}", e);
+                utils::exit(1);
+            }
+
+            println!("{}", DisplayMsg::SuccessAddKey(keypair_name));
+        }
+        Keys::Sign {
+            message,
+            file,
+            plaintext,
+            keypair_name,
+            output_file,
+            raw,
+            output_encoding,
+        } => {
+            let keypair = match get_keypair_from_json(config::get_keypair_path(), &keypair_name) {
+                Ok(Some(kp)) => kp,
                 Ok(None) => {
                     println!("{}", DisplayMsg::KeypairNotFound(keypair_name));
-                    std::process::exit(1);
+                    utils::exit(1);
                 }
                 Err(e) => {
                     println!("{}", e);
-                    std::process::exit(1);
+                    utils::exit(1);
                 }
             };
 
-            let encoded_ciphertext = match base64url::decode(&message) {
-                Ok(serialized_credentials) => {
-                    let ciphertext = keypair.sign(&serialized_credentials[..]).to_bytes();
-                    base64url::encode(ciphertext)
+            // the message is given in Base64, as the content of a file, or as plaintext
+            let (serialized_credentials, signed_input) = if let Some(file) = file {
+                let path = std::path::PathBuf::from(&file);
+                match utils::read_file(path.clone()) {
+                    Ok(data) => (data, format!("File: {}", file)),
+                    Err(e) => {
+                        println!(
+                            "{}",
+                            DisplayMsg::FailToOpenOrReadFile(String::from("message"), path, e)
+                        );
+                        utils::exit(1);
+                    }
                 }
+            } else if let Some(plaintext) = plaintext {
+                (
+                    plaintext.as_bytes().to_vec(),
+                    format!("Message: {}", plaintext),
+                )
+            } else if let Some(message) = message {
+                match decode_base64url(&message) {
+                    Ok(data) => (data, format!("Message: {}", message)),
+                    Err(e) => {
+                        println!("{}", DisplayMsg::FailToSignMessage(e.to_string()));
+                        utils::exit(1);
+                    }
+                }
+            } else {
+                return;
+            };
+
+            // a keypair added by `keys import-external` is signed by its signer command
+            let ciphertext = if keypair.signer_command.is_some() {
+                match sign_externally(&keypair, &serialized_credentials) {
+                    Ok(signature) => signature,
+                    Err(e) => {
+                        println!("{}", e);
+                        utils::exit(1);
+                    }
+                }
+            } else {
+                match signing_key(&keypair) {
+                    Ok(signing_key) => signing_key.sign(&serialized_credentials[..]).to_bytes(),
+                    Err(e) => {
+                        println!("{}", e);
+                        utils::exit(1);
+                    }
+                }
+            };
+            let encoded_ciphertext = utils::encode_bytes(&ciphertext, output_encoding);
+
+            if let Some(output_file) = output_file {
+                let path = std::path::PathBuf::from(output_file);
+                let content = if raw {
+                    ciphertext.to_vec()
+                } else {
+                    encoded_ciphertext.into_bytes()
+                };
+                match utils::write_file(path.clone(), &content) {
+                    Ok(full_path) => println!(
+                        "{}",
+                        DisplayMsg::SuccessCreateFile(
+                            String::from("signature"),
+                            std::path::PathBuf::from(full_path)
+                        )
+                    ),
+                    Err(e) => {
+                        println!(
+                            "{}",
+                            DisplayMsg::FailToWriteFile(String::from("signature"), path, e)
+                        );
+                        utils::exit(1);
+                    }
+                }
+            } else {
+                println!("{}", signed_input);
+                println!("Ciphertext: {}", encoded_ciphertext);
+            }
+        }
+        Keys::Verify {
+            message,
+            signature,
+            public_key,
+        } => match verify_signature(&public_key, &message, &signature) {
+            Ok(true) => println!("{}", DisplayMsg::ValidSignature),
+            Ok(false) => {
+                println!("{}", DisplayMsg::InvalidSignature(public_key));
+                utils::exit(1);
+            }
+            Err(e) => {
+                println!("{}", e);
+                utils::exit(1);
+            }
+        },
+        Keys::Audit => {
+            let keypairs = match load_existing_keypairs(config::get_keypair_path()) {
+                Ok(keypairs) => keypairs,
                 Err(e) => {
-                    println!("{}", DisplayMsg::FailToSignMessage(e.to_string()));
-                    std::process::exit(1);
+                    println!("{}", e);
+                    utils::exit(1);
                 }
             };
 
-            println!("Message: {}", message);
-            println!("Ciphertext: {}", encoded_ciphertext);
+            let mut issues = 0;
+            for keypair in &keypairs {
+                if let Err(e) = check_keypair_consistency(keypair) {
+                    println!("{}", DisplayMsg::KeypairAuditIssue(keypair.name.clone(), e));
+                    issues += 1;
+                }
+            }
+
+            let mut names_by_public_key: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+            for keypair in &keypairs {
+                names_by_public_key
+                    .entry(&keypair.public_key)
+                    .or_default()
+                    .push(keypair.name.clone());
+            }
+            for (public_key, names) in names_by_public_key {
+                if names.len() > 1 {
+                    println!(
+                        "{}",
+                        DisplayMsg::DuplicatePublicKey(names, public_key.to_string())
+                    );
+                    issues += 1;
+                }
+            }
+
+            if issues > 0 {
+                println!("{}", DisplayMsg::KeypairAuditFailed(issues, keypairs.len()));
+                utils::exit(1);
+            }
+            println!("{}", DisplayMsg::KeypairAuditPassed(keypairs.len()));
+        }
+        Keys::ChangePassword => {
+            if let Err(e) = utils::change_password(config::get_keypair_path()) {
+                println!("{}", e);
+                utils::exit(1);
+            }
+
+            println!("{}", DisplayMsg::SuccessChangePassword);
+        }
+        Keys::ExportAll {
+            destination,
+            encrypted,
+        } => {
+            let path = std::path::PathBuf::from(
+                destination.unwrap_or_else(|| String::from(EXPORT_ALL_FILENAME)),
+            );
+            if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                if !dir.is_dir() {
+                    println!(
+                        "{}",
+                        DisplayMsg::IncorrectFilePath(
+                            String::from("keypairs export"),
+                            path,
+                            String::from("Directory provided does not exist."),
+                        )
+                    );
+                    utils::exit(1);
+                }
+            }
+
+            // the password decrypts the keypair file and encrypts the exported file, so it is asked once
+            let passphrase = match utils::login() {
+                Ok(passphrase) => passphrase,
+                Err(e) => {
+                    println!("{}", e);
+                    utils::exit(1);
+                }
+            };
+            let keypairs = match load_existing_keypairs_with_passphrase(
+                config::get_keypair_path(),
+                &passphrase,
+            ) {
+                Ok(keypairs) => keypairs,
+                Err(e) => {
+                    println!("{}", e);
+                    utils::exit(1);
+                }
+            };
+            let json = serde_json::to_string_pretty(&keypairs)
+                .unwrap()
+                .into_bytes();
+            let content = if encrypted {
+                match utils::encrypt_with_passphrase(&json, passphrase) {
+                    Ok(content) => content,
+                    Err(e) => {
+                        println!("{}", e);
+                        utils::exit(1);
+                    }
+                }
+            } else {
+                utils::warn(DisplayMsg::PlaintextPrivateKeysExported);
+                json
+            };
+
+            match utils::write_file(path.clone(), &content) {
+                Ok(saved_path) => {
+                    println!(
+                        "{}",
+                        DisplayMsg::SuccessExportAll(keypairs.len(), saved_path)
+                    )
+                }
+                Err(e) => {
+                    println!(
+                        "{}",
+                        DisplayMsg::FailToWriteFile(String::from("Export keypairs"), path, e)
+                    );
+                    utils::exit(1);
+                }
+            }
+        }
+        Keys::ImportAll {
+            file,
+            batch_options,
+        } => {
+            let path = std::path::PathBuf::from(file);
+            let content = match utils::read_file(path.clone()) {
+                Ok(content) => content,
+                Err(e) => {
+                    println!(
+                        "{}",
+                        DisplayMsg::FailToOpenOrReadFile(String::from("keypairs export"), path, e)
+                    );
+                    utils::exit(1);
+                }
+            };
+
+            // the password decrypts an encrypted exported file and the keypair file, and encrypts the keypair
+            // file again, so it is asked once
+            let passphrase = match utils::login() {
+                Ok(passphrase) => passphrase,
+                Err(e) => {
+                    println!("{}", e);
+                    utils::exit(1);
+                }
+            };
+            let imported = if utils::is_encrypted(&content) {
+                utils::decrypt_with_passphrase(&content, passphrase.clone())
+            } else {
+                Ok(content)
+            }
+            .and_then(|json| {
+                serde_json::from_slice::<Vec<KeypairJSON>>(&json).map_err(DisplayMsg::InvalidJson)
+            });
+            let imported = match imported {
+                Ok(imported) => imported,
+                Err(e) => {
+                    println!("{}", e);
+                    utils::exit(1);
+                }
+            };
+
+            let mut keypairs = match load_existing_keypairs_with_passphrase(
+                config::get_keypair_path(),
+                &passphrase,
+            ) {
+                Ok(keypairs) => keypairs,
+                Err(e) => {
+                    println!("{}", e);
+                    utils::exit(1);
+                }
+            };
+            // an invalid keypair fails the batch, so with `--fail-fast` nothing is imported
+            let mut batch_errors = BatchErrors::new(&batch_options, true);
+            let existing = keypairs.len();
+            let mut skipped_existing = 0;
+            let mut skipped_invalid = 0;
+            for keypair in imported {
+                if keypairs.iter().any(|kp| kp.name == keypair.name) {
+                    utils::warn(DisplayMsg::SkipExistingKeypair(keypair.name));
+                    skipped_existing += 1;
+                    batch_errors.succeed();
+                } else if let Err(e) = check_keypair_consistency(&keypair) {
+                    batch_errors.fail(
+                        keypair.name.clone(),
+                        DisplayMsg::InvalidImportedKeypair(keypair.name, e),
+                    );
+                    skipped_invalid += 1;
+                } else {
+                    keypairs.push(keypair);
+                    batch_errors.succeed();
+                }
+            }
+
+            if let Err(e) = save_keypairs_to_json_with_passphrase(
+                config::get_keypair_path(),
+                &keypairs,
+                &passphrase,
+            ) {
+                println!("{}", e);
+                utils::exit(1);
+            }
+
+            println!(
+                "{}",
+                DisplayMsg::SuccessImportAll(
+                    keypairs.len() - existing,
+                    skipped_existing,
+                    skipped_invalid
+                )
+            );
+            batch_errors.finish();
+        }
+        Keys::SignTransaction {
+            file,
+            keypair_name,
+            output_file,
+        } => {
+            let signed_tx = match SubmitTx::from_json_file(&file).and_then(|submit_tx| {
+                submit_tx.prepare_signed_tx(std::slice::from_ref(&keypair_name))
+            }) {
+                Ok(signed_tx) => signed_tx,
+                Err(e) => {
+                    println!("{}", e);
+                    utils::exit(1);
+                }
+            };
+            let (hash, signature) = match &signed_tx {
+                TransactionV1OrV2::V1(tx) => (tx.hash, tx.signature),
+                TransactionV1OrV2::V2(tx) => (tx.hash, tx.signature),
+            };
+
+            println!("Transaction hash: {}", base64url::encode(hash));
+            println!("Signature: {}", base64url::encode(signature));
+
+            if let Some(output_file) = output_file {
+                let path = std::path::PathBuf::from(output_file);
+                match utils::write_file(path.clone(), &signed_tx.serialize()) {
+                    Ok(full_path) => println!(
+                        "{}",
+                        DisplayMsg::SuccessCreateFile(
+                            String::from("Signed transaction"),
+                            std::path::PathBuf::from(full_path)
+                        )
+                    ),
+                    Err(e) => {
+                        println!(
+                            "{}",
+                            DisplayMsg::FailToWriteFile(String::from("Signed transaction"), path, e)
+                        );
+                        utils::exit(1);
+                    }
+                }
+            }
         }
         Keys::Export { keypair_name, destination } => {
             let keypair = match get_keypair_from_json(config::get_keypair_path(), &keypair_name) {
+                Ok(Some(kp)) if kp.signer_command.is_some() => {
+                    println!("{}", DisplayMsg::ExternalKeypairNotExportable(keypair_name));
+                    utils::exit(1);
+                }
                 Ok(Some(kp)) => kp,
                 Ok(None) => {
                     println!("{}", DisplayMsg::KeypairNotFound(keypair_name));
-                    std::process::exit(1);
+                    utils::exit(1);
                 }
                 Err(e) => {
                     println!("{}", e);
-                    std::process::exit(1);
+                    utils::exit(1);
                 }
             };
 
@@ -148,15 +619,41 @@ pub fn match_crypto_subcommand(crypto_subcommand: Keys) {
                 path.clone(),
                 serde_json::to_string_pretty(&keypair).unwrap().as_bytes(),
             ) {
-                Ok(path) => println!("Keypair is saved at {}", path),
+                Ok(saved_path) => {
+                    // Read the file back so that a broken backup is reported now, not when it is needed.
+                    if let Err(e) = utils::read_file(path.clone())
+                        .and_then(|exported| verify_exported_keypair(&exported, &keypair))
+                    {
+                        println!("{}", DisplayMsg::FailToVerifyExportedKeypair(path, e));
+                        utils::exit(1);
+                    }
+                    println!("Keypair is saved at {}", saved_path)
+                }
                 Err(e) => {
                     println!(
                         "{}",
                         DisplayMsg::FailToWriteFile(String::from("Export keypair"), path, e)
                     );
-                    std::process::exit(1);
+                    utils::exit(1);
                 }
             }
         }
     };
 }
+
+// `display_dry_run_keypair` displays the name and public key of a keypair which would be saved without `--dry-run`.
+//  # Arguments
+//  * `name` - name of the keypair
+//  * `public_key` - Base64url encoded public key of the keypair
+//  * `json` - display in JSON format
+//
+fn display_dry_run_keypair(name: String, public_key: String, json: bool) {
+    if json {
+        display_json(json!({ "name": name, "public_key": public_key, "dry_run": true }));
+    } else {
+        println!("{}", DisplayMsg::DryRunKeypair(name, public_key));
+    }
+}
+
+/// Default filename of the file written by `keys export-all`.
+const EXPORT_ALL_FILENAME: &str = "keypairs.json";
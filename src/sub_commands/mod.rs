@@ -3,6 +3,19 @@
     Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
 */
 
+use pchain_types::cryptography::{PublicAddress, Sha256Hash};
+use pchain_types::serialization::Serializable;
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::command::{Base64Address, BatchOptions};
+use crate::config::resolve_contact;
+use crate::display_msg::DisplayMsg;
+use crate::parser::base64url_to_public_address;
+use crate::result::{display_options, ErrorResponse};
+use crate::utils;
+
 /// `keys` houses methods which process subcommands related to cryptographic operations
 /// on ParallelChain, like generating keypairs, signing keypairs etc.
 pub(crate) mod keys;
@@ -27,3 +40,232 @@ pub use transaction::*;
 /// `config_command` houses methods which process subcommands related to setting up RPC url
 pub(crate) mod config_command;
 pub use config_command::*;
+
+// `decode_address_or_exit` decodes a Base64url encoded address supplied from CLI. It displays the
+//  error and exits the program if the address is malformed.
+//  # Arguments
+//  * `name` - name of the argument, used in the error message
+//  * `address` - Base64url encoded address
+//
+pub(crate) fn decode_address_or_exit(name: &str, address: &str) -> PublicAddress {
+    match base64url_to_public_address(address) {
+        Ok(addr) => addr,
+        Err(e) => {
+            DisplayMsg::FailToDecodeBase64Address(
+                String::from(name),
+                String::from(address),
+                e.to_string(),
+            )
+            .exit();
+        }
+    }
+}
+
+// `resolve_address_or_exit` resolves an address supplied from CLI, which may be `@<NAME>` of an entry in
+//  the address book. It displays the error and exits the program if the entry does not exist.
+//  # Arguments
+//  * `address` - Base64url encoded address, or `@<NAME>`
+//
+pub(crate) fn resolve_address_or_exit(address: &str) -> Base64Address {
+    match resolve_contact(address) {
+        Ok(address) => address,
+        Err(e) => {
+            e.exit();
+        }
+    }
+}
+
+// `decode_hash_or_exit` decodes a Base64url encoded block or transaction hash supplied from CLI.
+//  It displays the error and exits the program if the hash is malformed.
+//  # Arguments
+//  * `name` - name of the argument, used in the error message
+//  * `hash` - Base64url encoded hash
+//
+pub(crate) fn decode_hash_or_exit(name: &str, hash: &str) -> Sha256Hash {
+    match base64url_to_public_address(hash) {
+        Ok(hash_bytes) => hash_bytes,
+        Err(e) => {
+            DisplayMsg::FailToDecodeBase64Hash(
+                String::from(name),
+                String::from(hash),
+                e.to_string(),
+            )
+            .exit();
+        }
+    }
+}
+
+// `timed` awaits a request to Fullnode RPC. Under `--timing`, the elapsed time of the request is printed to stderr.
+//  Under `--raw-response`, the body returned by Fullnode RPC is printed to stderr as well, before anything is
+//  made of it. Responses are Borsh encoded, which has one encoding per value, so encoding the response again
+//  gives back the body exactly as it was received.
+//  # Arguments
+//  * `request` - name of the request, used in the timing message
+//  * `future` - the pending request
+//
+pub(crate) async fn timed<T, F>(request: &str, future: F) -> F::Output
+where
+    T: Serializable,
+    F: Future<Output = Result<T, String>>,
+{
+    let start = Instant::now();
+    let output = future.await;
+    if display_options().timing {
+        eprintln!(
+            "{}",
+            DisplayMsg::RequestTiming(String::from(request), start.elapsed().as_millis())
+        );
+    }
+    if display_options().raw_response {
+        if let Ok(response) = &output {
+            eprintln!(
+                "{}",
+                DisplayMsg::RawResponse(
+                    String::from(request),
+                    base64url::encode(Serializable::serialize(response))
+                )
+            );
+        }
+    }
+    output
+}
+
+// `set_retries` sets how many times `retried` sends a request again after a retryable failure. It should
+//  be called once, before any request is sent. Later calls are ignored.
+//  # Arguments
+//  * `retries` - maximum number of retries of each request
+//
+pub(crate) fn set_retries(retries: u32) {
+    let _ = RETRIES.set(retries);
+}
+
+// `retries` returns how many times a request is sent again after a retryable failure, as set by `set_retries`.
+//  # Arguments
+//  *
+pub(crate) fn retries() -> u32 {
+    RETRIES.get().copied().unwrap_or(0)
+}
+
+// `retried` sends a request to Fullnode RPC, and sends it again after a server error (5xx) or a failure
+//  without a response, up to the number of times set by `--retries`. Client errors (4xx) are returned at
+//  once, since sending the same request again cannot succeed.
+//  # Arguments
+//  * `request` - name of the request, used in the timing and retry messages
+//  * `send` - sends the request, called once per attempt
+//
+pub(crate) async fn retried<T, F, Fut>(request: &str, send: F) -> Result<T, ErrorResponse>
+where
+    T: Serializable,
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    let retries = retries();
+    let mut wait = RETRY_INITIAL_WAIT;
+    let mut attempt = 0;
+    loop {
+        match timed(request, send()).await.map_err(ErrorResponse::from) {
+            Err(error) if error.is_retryable() && attempt < retries => {
+                attempt += 1;
+                eprintln!(
+                    "{}",
+                    DisplayMsg::RetryingRequest(
+                        String::from(request),
+                        error.to_string(),
+                        attempt,
+                        retries
+                    )
+                );
+                tokio::time::sleep(wait).await;
+                wait = (wait * 2).min(RETRY_MAX_WAIT);
+            }
+            result => return result,
+        }
+    }
+}
+
+/// [BatchErrors] applies the error policy chosen by [BatchOptions] to the items of a batch. With `--fail-fast`,
+/// the program exits at the first failed item. With `--collect-errors`, failed items are remembered and reported
+/// together by `finish`. Without either flag, the default of the subcommand applies.
+pub(crate) struct BatchErrors {
+    collect_errors: bool,
+    on_stderr: bool,
+    total: usize,
+    failed: Vec<String>,
+}
+
+impl BatchErrors {
+    // `new` creates the error policy of a batch.
+    //  # Arguments
+    //  * `options` - batch options from CLI
+    //  * `collect_by_default` - whether errors are collected when neither flag is given
+    //
+    pub fn new(options: &BatchOptions, collect_by_default: bool) -> Self {
+        Self {
+            collect_errors: options.collect_errors || (collect_by_default && !options.fail_fast),
+            on_stderr: false,
+            total: 0,
+            failed: Vec::new(),
+        }
+    }
+
+    // `on_stderr` reports errors on stderr instead of stdout, for batches whose stdout is one document, e.g. JSON.
+    pub fn on_stderr(mut self) -> Self {
+        self.on_stderr = true;
+        self
+    }
+
+    // `collects_errors` tells whether the batch goes on after a failed item.
+    pub fn collects_errors(&self) -> bool {
+        self.collect_errors
+    }
+
+    // `report` displays a message of the batch on stdout, or on stderr if so chosen.
+    fn report(&self, msg: DisplayMsg) {
+        if self.on_stderr {
+            eprintln!("{}", msg);
+        } else {
+            println!("{}", msg);
+        }
+    }
+
+    // `succeed` counts an item which is processed successfully.
+    pub fn succeed(&mut self) {
+        self.total += 1;
+    }
+
+    // `fail` displays the error of an item. It exits the program unless errors are collected.
+    //  # Arguments
+    //  * `item` - name of the item, used in the summary
+    //  * `error` - reason of failure
+    //
+    pub fn fail(&mut self, item: String, error: DisplayMsg) {
+        self.report(error);
+        if !self.collect_errors {
+            utils::exit(1);
+        }
+        self.total += 1;
+        self.failed.push(item);
+    }
+
+    // `finish` displays the summary of failed items and exits the program if any item failed.
+    pub fn finish(self) {
+        if !self.failed.is_empty() {
+            let failed = self.failed.len();
+            self.report(DisplayMsg::BatchFailed(
+                failed,
+                self.total,
+                self.failed.clone(),
+            ));
+            utils::exit(1);
+        }
+    }
+}
+
+/// Maximum number of retries of each request to Fullnode RPC, set by `--retries`.
+static RETRIES: OnceLock<u32> = OnceLock::new();
+
+/// Wait before the first retry of a request. The wait doubles on every later retry, up to [RETRY_MAX_WAIT].
+const RETRY_INITIAL_WAIT: Duration = Duration::from_secs(1);
+
+/// Longest wait between two retries of a request.
+const RETRY_MAX_WAIT: Duration = Duration::from_secs(60);
@@ -6,15 +6,28 @@
 //! Methods related to subcommand `setup` in `pchain-client`.
 
 use serde_json::Value;
+use std::convert::TryFrom;
+use std::path::PathBuf;
+
+use pchain_types::{
+    blockchain::Command, cryptography::PublicAddress, rpc::TransactionV1OrV2,
+    serialization::Deserializable,
+};
 
 use crate::{
-    command::{ContractAddressVersion, Parse},
+    command::{CallResultFormat, ContractAddressVersion, Parse},
+    config::get_keypair_path,
     display_msg::DisplayMsg,
+    display_types::{SubmitTx, Transaction, TxCommand},
+    keypair::get_keypair_from_json,
     parser::{
-        base64url_to_public_address, parse_call_result_from_data_type,
-        parse_call_result_from_schema,
+        base64url_to_public_address, call_arguments_from_json_array, call_arguments_layout,
+        decode_base64url, parse_call_result_from_data_type, parse_call_result_from_schema,
+        parse_call_result_from_schema_nested, parse_json_arguments, supported_argument_types,
+        supported_call_result_types,
     },
-    utils::read_file_to_utf8string,
+    result::display_json,
+    utils::{self, encode_bytes, read_file, read_file_to_utf8string},
 };
 
 // `match_parse_subcommand` matches a CLI argument to its corresponding `Parse` subcommand and processes
@@ -42,7 +55,7 @@ pub fn match_parse_subcommand(parse_subcommand: Parse) {
             }
 
             if decode {
-                match base64url::decode(&value) {
+                match decode_base64url(&value) {
                     Ok(d) => println!("{:?}", d),
                     Err(e) => println!(
                         "{}",
@@ -60,8 +73,10 @@ pub fn match_parse_subcommand(parse_subcommand: Parse) {
             value,
             data_type,
             schema_file,
+            schema_json,
+            output_format,
         } => {
-            let value = base64url::decode(&value).unwrap_or_else(|_| {
+            let value = decode_base64url(&value).unwrap_or_else(|_| {
                 panic!(
                     "{}",
                     DisplayMsg::FailToDecodeBase64String(
@@ -80,58 +95,67 @@ pub fn match_parse_subcommand(parse_subcommand: Parse) {
                         println!("{}", DisplayMsg::FailToParseCallResult(e.to_string()));
                     }
                 }
-            } else if let Some(schema_file) = schema_file {
-                let schema = match read_file_to_utf8string(schema_file.clone()) {
-                    Ok(result) => result,
-                    Err(e) => {
-                        println!(
-                            "{}",
-                            DisplayMsg::FailToOpenOrReadFile(
-                                String::from("schema json"),
-                                schema_file,
-                                e
-                            )
-                        );
-                        std::process::exit(1);
-                    }
-                };
-
-                let schema: Value = match serde_json::from_str(&schema) {
-                    Ok(json_val) => json_val,
-                    Err(e) => {
-                        println!("{}", DisplayMsg::InvalidJson(e));
-                        std::process::exit(1);
-                    }
-                };
-
-                let result = match parse_call_result_from_schema(&value, &schema) {
-                    Ok(result) => result,
-                    Err(e) => {
-                        println!("{}", DisplayMsg::FailToParseCallResult(e.to_string()));
-                        std::process::exit(1);
-                    }
-                };
+                return;
+            }
 
-                for (name, value) in result {
-                    println!("{name}: {value}");
+            display_decoded_with_schema(
+                &value,
+                schema_file,
+                schema_json,
+                output_format,
+                DisplayMsg::FailToParseCallResult,
+            );
+        }
+        Parse::Decode {
+            value,
+            schema_file,
+            schema_json,
+            output_format,
+        } => {
+            let value = match decode_base64url(&value) {
+                Ok(value) => value,
+                Err(e) => {
+                    println!(
+                        "{}",
+                        DisplayMsg::FailToDecodeBase64String(
+                            String::from("value"),
+                            value,
+                            e.to_string()
+                        )
+                    );
+                    utils::exit(1);
                 }
-            }
+            };
+
+            display_decoded_with_schema(
+                &value,
+                schema_file,
+                schema_json,
+                output_format,
+                DisplayMsg::FailToDecodeWithSchema,
+            );
         }
-        Parse::ContractAddress { version } => match version {
+        Parse::ContractAddress {
+            version,
+            output_encoding,
+        } => match version {
             ContractAddressVersion::V1 { address, nonce } => {
                 match base64url_to_public_address(&address) {
                     Ok(sender_address) => {
                         println!(
                             "Contract Address: {}",
-                            base64url::encode(pchain_types::cryptography::contract_address_v1(
-                                &sender_address,
-                                nonce
-                            ))
+                            encode_bytes(
+                                &pchain_types::cryptography::contract_address_v1(
+                                    &sender_address,
+                                    nonce
+                                ),
+                                output_encoding
+                            )
                         )
                     }
                     Err(e) => {
                         println!("{}", e);
-                        std::process::exit(1);
+                        utils::exit(1);
                     }
                 };
             }
@@ -144,20 +168,334 @@ pub fn match_parse_subcommand(parse_subcommand: Parse) {
                     Ok(sender_address) => {
                         println!(
                             "Contract Address: {}",
-                            base64url::encode(pchain_types::cryptography::contract_address_v2(
-                                &sender_address,
-                                nonce,
-                                index
-                            ))
+                            encode_bytes(
+                                &pchain_types::cryptography::contract_address_v2(
+                                    &sender_address,
+                                    nonce,
+                                    index
+                                ),
+                                output_encoding
+                            )
                         )
                     }
                     Err(e) => {
                         println!("{}", e);
-                        std::process::exit(1);
+                        utils::exit(1);
                     }
                 };
             }
+            ContractAddressVersion::FromTx { file, keypair_name } => {
+                let path = PathBuf::from(&file);
+                let deploys = match deploy_commands_from_tx_file(&file) {
+                    Ok(deploys) => deploys,
+                    Err(e) => {
+                        println!("{}", e);
+                        utils::exit(1);
+                    }
+                };
+
+                let signer = match keypair_name {
+                    Some(keypair_name) => {
+                        match get_keypair_from_json(get_keypair_path(), &keypair_name) {
+                            Ok(Some(keypair)) => {
+                                match base64url_to_public_address(&keypair.public_key) {
+                                    Ok(address) => address,
+                                    Err(e) => {
+                                        println!("{}", e);
+                                        utils::exit(1);
+                                    }
+                                }
+                            }
+                            Ok(None) => {
+                                println!("{}", DisplayMsg::KeypairNotFound(keypair_name));
+                                utils::exit(1);
+                            }
+                            Err(e) => {
+                                println!("{}", e);
+                                utils::exit(1);
+                            }
+                        }
+                    }
+                    None => match deploys.signer {
+                        Some(signer) => signer,
+                        None => {
+                            println!("{}", DisplayMsg::SignerNotFound(path));
+                            utils::exit(1);
+                        }
+                    },
+                };
+
+                if deploys.indices.is_empty() {
+                    println!("{}", DisplayMsg::NoDeployCommand(path));
+                    utils::exit(1);
+                }
+
+                for index in deploys.indices {
+                    let contract_address = if deploys.is_v1 {
+                        pchain_types::cryptography::contract_address_v1(&signer, deploys.nonce)
+                    } else {
+                        pchain_types::cryptography::contract_address_v2(
+                            &signer,
+                            deploys.nonce,
+                            index,
+                        )
+                    };
+                    println!(
+                        "Command {index}: Contract Address: {}",
+                        encode_bytes(&contract_address, output_encoding)
+                    );
+                }
+            }
         },
+        Parse::CallArguments { file } => {
+            let path_to_json = PathBuf::from(&file);
+            let arguments_json = match read_file_to_utf8string(path_to_json.clone()) {
+                Ok(result) => result,
+                Err(e) => {
+                    println!(
+                        "{}",
+                        DisplayMsg::FailToOpenOrReadFile(
+                            String::from("call argument json"),
+                            path_to_json,
+                            e
+                        )
+                    );
+                    utils::exit(1);
+                }
+            };
+
+            let arguments: Value = match serde_json::from_str(&arguments_json) {
+                Ok(json_val) => json_val,
+                Err(e) => {
+                    println!("{}", DisplayMsg::InvalidJson(e));
+                    utils::exit(1);
+                }
+            };
+
+            let json_args = match parse_json_arguments(&arguments) {
+                Ok(json_args) => json_args,
+                Err(e) => {
+                    println!("{}", e);
+                    utils::exit(1);
+                }
+            };
+            let call_arguments = match call_arguments_from_json_array(&json_args) {
+                Ok(call_arguments) => call_arguments,
+                Err(e) => {
+                    println!("{}", e);
+                    utils::exit(1);
+                }
+            };
+
+            display_json(Value::Array(call_arguments_layout(
+                &json_args,
+                &call_arguments,
+            )));
+        }
+        Parse::KeypairInfo {
+            keypair,
+            output_encoding,
+        } => {
+            let keypair_bs = match decode_base64url(&keypair) {
+                Ok(bs) => bs,
+                Err(e) => {
+                    println!(
+                        "{}",
+                        DisplayMsg::FailToDecodeBase64String(
+                            String::from("keypair"),
+                            keypair,
+                            e.to_string()
+                        )
+                    );
+                    utils::exit(1);
+                }
+            };
+
+            let keypair_bs = match <[u8; 64]>::try_from(&keypair_bs[..]) {
+                Ok(bs) => bs,
+                Err(_) => {
+                    println!(
+                        "{}",
+                        DisplayMsg::InvalidEd25519Keypair(format!(
+                            "Expected 64 bytes but got {} bytes.",
+                            keypair_bs.len()
+                        ))
+                    );
+                    utils::exit(1);
+                }
+            };
+
+            // `from_keypair_bytes` rejects a keypair whose public half does not match its private half
+            match ed25519_dalek::SigningKey::from_keypair_bytes(&keypair_bs) {
+                Ok(signing_key) => {
+                    println!(
+                        "Public Key: {}",
+                        encode_bytes(signing_key.verifying_key().as_bytes(), output_encoding)
+                    );
+                    println!("Valid: true");
+                }
+                Err(e) => {
+                    println!("{}", DisplayMsg::InvalidEd25519Keypair(e.to_string()));
+                    utils::exit(1);
+                }
+            }
+        }
+        Parse::Transaction { value } => {
+            let bytes = match decode_base64url(&value) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    println!(
+                        "{}",
+                        DisplayMsg::FailToDecodeBase64String(
+                            String::from("transaction"),
+                            value,
+                            e.to_string()
+                        )
+                    );
+                    utils::exit(1);
+                }
+            };
+
+            let transaction: Transaction = match TransactionV1OrV2::deserialize(&bytes) {
+                Ok(TransactionV1OrV2::V1(tx)) => From::from(tx),
+                Ok(TransactionV1OrV2::V2(tx)) => From::from(tx),
+                Err(e) => {
+                    println!(
+                        "{}",
+                        DisplayMsg::FailToDeserializeTransaction(e.to_string())
+                    );
+                    utils::exit(1);
+                }
+            };
+            display_json(serde_json::to_value(transaction).unwrap());
+        }
+        Parse::ListTypes => {
+            println!("Call argument types (argument_type in argument files):");
+            for data_type in supported_argument_types() {
+                println!("  {data_type}");
+            }
+            println!();
+            println!("Call result types (--data-type, or argument_type in schema):");
+            for data_type in supported_call_result_types() {
+                println!("  {data_type}");
+            }
+        }
+    };
+    utils::exit(1);
+}
+
+// `display_decoded_with_schema` decodes a borsh serialized value with the schema of `--schema-file` or `--schema-json`
+//  and displays it. It displays the error and exits the program if the schema is invalid or does not match the value.
+//  # Arguments
+//  * `value` - borsh serialized value
+//  * `schema_file` - path to the schema file
+//  * `schema_json` - inline schema JSON, used if no schema file is given
+//  * `output_format` - display the decoded fields one per line, or as JSON shaped like the schema
+//  * `decode_error` - message displayed when the value does not match the schema
+//
+fn display_decoded_with_schema(
+    value: &[u8],
+    schema_file: Option<PathBuf>,
+    schema_json: Option<String>,
+    output_format: CallResultFormat,
+    decode_error: fn(String) -> DisplayMsg,
+) {
+    let schema = if let Some(schema_file) = schema_file {
+        match read_file_to_utf8string(schema_file.clone()) {
+            Ok(result) => result,
+            Err(e) => {
+                println!(
+                    "{}",
+                    DisplayMsg::FailToOpenOrReadFile(String::from("schema json"), schema_file, e)
+                );
+                utils::exit(1);
+            }
+        }
+    } else if let Some(schema_json) = schema_json {
+        schema_json
+    } else {
+        return;
     };
-    std::process::exit(1);
+
+    let schema: Value = match serde_json::from_str(&schema) {
+        Ok(json_val) => json_val,
+        Err(e) => {
+            println!("{}", DisplayMsg::InvalidJson(e));
+            utils::exit(1);
+        }
+    };
+
+    let result = match output_format {
+        CallResultFormat::Flat => parse_call_result_from_schema(value, &schema).map(|result| {
+            for (name, value) in result {
+                println!("{name}: {value}");
+            }
+        }),
+        CallResultFormat::Nested => {
+            parse_call_result_from_schema_nested(value, &schema).map(display_json)
+        }
+    };
+    match result {
+        Ok(()) => {}
+        Err(e @ DisplayMsg::InvalidCallResultSchema(..)) => {
+            println!("{}", e);
+            utils::exit(1);
+        }
+        Err(e) => {
+            println!("{}", decode_error(e.to_string()));
+            utils::exit(1);
+        }
+    }
+}
+
+/// [DeployCommands] holds what is needed from a transaction file to compute the addresses of the contracts it deploys.
+struct DeployCommands {
+    signer: Option<PublicAddress>,
+    nonce: u64,
+    is_v1: bool,
+    indices: Vec<u32>,
+}
+
+// `deploy_commands_from_tx_file` reads a transaction file and finds the indices of its deploy commands.
+//  JSON files created by `transaction create` do not record the signer, while signed transaction files do.
+//  # Arguments
+//  * `file` - relative or absolute path to the transaction file
+//
+fn deploy_commands_from_tx_file(file: &str) -> Result<DeployCommands, DisplayMsg> {
+    if file.ends_with(".json") {
+        let tx = SubmitTx::from_json_file(file)?;
+        let indices = (0u32..)
+            .zip(tx.commands.iter())
+            .filter(|(_, command)| matches!(command, TxCommand::Deploy { .. }))
+            .map(|(index, _)| index)
+            .collect();
+        return Ok(DeployCommands {
+            signer: None,
+            nonce: tx.nonce,
+            is_v1: tx.is_v1,
+            indices,
+        });
+    }
+
+    let path = PathBuf::from(file);
+    let bytes = read_file(path.clone()).map_err(|e| {
+        DisplayMsg::FailToOpenOrReadFile(String::from("signed transaction"), path.clone(), e)
+    })?;
+    let (signer, nonce, is_v1, commands) = match TransactionV1OrV2::deserialize(&bytes) {
+        Ok(TransactionV1OrV2::V1(tx)) => (tx.signer, tx.nonce, true, tx.commands),
+        Ok(TransactionV1OrV2::V2(tx)) => (tx.signer, tx.nonce, false, tx.commands),
+        Err(e) => return Err(DisplayMsg::FailToDecodeSignedTx(path, e.to_string())),
+    };
+    let indices = (0u32..)
+        .zip(commands.iter())
+        .filter(|(_, command)| matches!(command, Command::Deploy(_)))
+        .map(|(index, _)| index)
+        .collect();
+
+    Ok(DeployCommands {
+        signer: Some(signer),
+        nonce,
+        is_v1,
+        indices,
+    })
 }
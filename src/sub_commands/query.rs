@@ -5,111 +5,141 @@
 
 //! Methods related to subcommand `query` in `pchain-client`.
 
+use clap::Parser;
+use futures_util::{SinkExt, StreamExt};
 use pchain_client::Client;
 use pchain_types::rpc::*;
-use serde_json::Value;
+use pchain_types::serialization::{Deserializable, Serializable};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::convert::TryFrom;
+use std::future::Future;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use tokio_tungstenite::tungstenite::Message;
 
-use crate::command::{Query, Validators};
-use crate::config::Config;
+use crate::command::{PChainCLI, PChainCommand, Query, Validators};
+use crate::config::{get_cache_dir, Config};
 use crate::display_msg::DisplayMsg;
-use crate::parser::{base64url_to_public_address, call_arguments_from_json_value};
-use crate::result::{display_beautified_rpc_result, ClientResponse};
-use crate::utils::read_file_to_utf8string;
+use crate::display_types::{total_gas_used, TransactionDetail};
+use crate::parser::{call_arguments_from_json_value, decode_base64url};
+use crate::result::{
+    capture_results, display_beautified_rpc_result, display_json, display_options, display_text,
+    receipt_from_rpc, ClientResponse, ErrorResponse,
+};
+use crate::sub_commands::{
+    decode_address_or_exit, decode_hash_or_exit, resolve_address_or_exit, retried, retries,
+    BatchErrors,
+};
+use crate::utils::{format_unix_timestamp, read_file, read_file_to_utf8string, write_file};
 
 // `match_query_subcommand` matches a CLI argument to its corresponding `Query` subcommand and processes
 //  the request.
 //  # Arguments
 //  * `query_subcommand` - query subcommand from CLI
 //  * `config` - networking config for Client
+//  * `cache` - whether to answer queries of committed data from the query cache of the configured network
 //
-pub async fn match_query_subcommand(query_subcommand: Query, config: Config) {
+pub async fn match_query_subcommand(query_subcommand: Query, config: &Config, cache: bool) {
+    // following new blocks only needs the WebSocket endpoint
+    if let Query::Block {
+        follow: true,
+        with_receipts,
+        ..
+    } = query_subcommand
+    {
+        follow_blocks(config.get_ws_url(), with_receipts).await;
+        return;
+    }
+
     let url = config.get_url();
+    let cache = cache.then(|| network_cache_dir(url));
     let pchain_client = Client::new(url);
 
     match query_subcommand {
-        Query::Balance { address } => {
+        Query::Balance { address, refresh } => {
             let sender_address: pchain_types::cryptography::PublicAddress =
-                match base64url_to_public_address(&address) {
-                    Ok(addr) => addr,
-                    Err(e) => {
-                        println!(
-                            "{}",
-                            DisplayMsg::FailToDecodeBase64Address(
-                                String::from("sender"),
-                                address,
-                                e.to_string()
-                            )
-                        );
-                        std::process::exit(1);
-                    }
-                };
+                decode_address_or_exit("sender", &resolve_address_or_exit(&address));
 
-            let response = pchain_client
-                .state_v2(&StateRequest {
-                    accounts: HashSet::from([sender_address]),
-                    include_contract: false,
-                    storage_keys: HashMap::from([]),
+            loop {
+                let response = retried("state_v2", || async {
+                    pchain_client
+                        .state_v2(&StateRequest {
+                            accounts: HashSet::from([sender_address]),
+                            include_contract: false,
+                            storage_keys: HashMap::from([]),
+                        })
+                        .await
                 })
                 .await;
 
-            display_beautified_rpc_result(ClientResponse::Balance(response));
-        }
-        Query::Nonce { address } => {
-            let sender_address: pchain_types::cryptography::PublicAddress =
-                match base64url_to_public_address(&address) {
-                    Ok(addr) => addr,
-                    Err(e) => {
-                        println!(
-                            "{}",
-                            DisplayMsg::FailToDecodeBase64Address(
-                                String::from("sender"),
-                                address,
-                                e.to_string()
-                            )
-                        );
-                        std::process::exit(1);
+                let refresh = match refresh {
+                    Some(refresh) => refresh,
+                    None => {
+                        display_beautified_rpc_result(ClientResponse::Balance(response));
+                        break;
                     }
                 };
 
-            let response = pchain_client
-                .state_v2(&StateRequest {
-                    accounts: HashSet::from([sender_address]),
-                    include_contract: false,
-                    storage_keys: HashMap::from([]),
+                // a failed query is shown in place of the result, and queried again at the next refresh
+                match state_error(&response) {
+                    Some(error) => println!("{}", error),
+                    None => display_beautified_rpc_result(ClientResponse::Balance(response)),
+                }
+                wait_to_refresh(refresh).await;
+            }
+        }
+        Query::Nonce { address, refresh } => {
+            let sender_address: pchain_types::cryptography::PublicAddress =
+                decode_address_or_exit("sender", &resolve_address_or_exit(&address));
+
+            loop {
+                let response = retried("state_v2", || async {
+                    pchain_client
+                        .state_v2(&StateRequest {
+                            accounts: HashSet::from([sender_address]),
+                            include_contract: false,
+                            storage_keys: HashMap::from([]),
+                        })
+                        .await
                 })
                 .await;
 
-            display_beautified_rpc_result(ClientResponse::Nonce(response));
+                let refresh = match refresh {
+                    Some(refresh) => refresh,
+                    None => {
+                        display_beautified_rpc_result(ClientResponse::Nonce(response));
+                        break;
+                    }
+                };
+
+                // a failed query is shown in place of the result, and queried again at the next refresh
+                match state_error(&response) {
+                    Some(error) => println!("{}", error),
+                    None => display_beautified_rpc_result(ClientResponse::Nonce(response)),
+                }
+                wait_to_refresh(refresh).await;
+            }
         }
         Query::Contract {
             address,
             destination,
         } => {
             let contract_address: pchain_types::cryptography::PublicAddress =
-                match base64url_to_public_address(&address) {
-                    Ok(addr) => addr,
-                    Err(e) => {
-                        println!(
-                            "{}",
-                            DisplayMsg::FailToDecodeBase64Address(
-                                String::from("contract"),
-                                address,
-                                e.to_string()
-                            )
-                        );
-                        std::process::exit(1);
-                    }
-                };
+                decode_address_or_exit("contract", &address);
 
-            let response = pchain_client
-                .state_v2(&StateRequest {
-                    accounts: HashSet::from([contract_address]),
-                    include_contract: true,
-                    storage_keys: HashMap::from([]),
-                })
-                .await;
+            let response = retried("state_v2", || async {
+                pchain_client
+                    .state_v2(&StateRequest {
+                        accounts: HashSet::from([contract_address]),
+                        include_contract: true,
+                        storage_keys: HashMap::from([]),
+                    })
+                    .await
+            })
+            .await;
 
             display_beautified_rpc_result(ClientResponse::Contract(response, destination));
         }
@@ -118,6 +148,7 @@ pub async fn match_query_subcommand(query_subcommand: Query, config: Config) {
             ref block_hash,
             ref tx_hash,
             latest,
+            ..
         }
         | Query::BlockHeader {
             block_height,
@@ -125,46 +156,48 @@ pub async fn match_query_subcommand(query_subcommand: Query, config: Config) {
             ref tx_hash,
             latest,
         } => {
+            let header_only = matches!(query_subcommand, Query::BlockHeader { .. });
+            let with_receipts = !matches!(
+                query_subcommand,
+                Query::Block {
+                    with_receipts: false,
+                    ..
+                }
+            );
+
             if latest {
-                let response = pchain_client.highest_committed_block().await;
+                let response = retried("highest_committed_block", || async {
+                    pchain_client.highest_committed_block().await
+                })
+                .await;
 
                 let block_hash = match response {
                     Ok(HighestCommittedBlockResponse {
                         block_hash: Some(block_hash),
                     }) => block_hash,
                     Err(e) => {
-                        println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
-                        std::process::exit(1);
+                        DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
                     }
                     _ => {
-                        println!("{}", DisplayMsg::CannotFindLatestBlock);
-                        std::process::exit(1);
+                        DisplayMsg::CannotFindLatestBlock.exit();
                     }
                 };
 
-                match query_subcommand {
-                    Query::BlockHeader {
-                        block_height: _,
-                        block_hash: _,
-                        tx_hash: _,
-                        latest: _,
-                    } => {
-                        let response = pchain_client
-                            .block_header_v2(&BlockHeaderRequest { block_hash })
-                            .await;
-
-                        display_beautified_rpc_result(ClientResponse::BlockHeader(response));
-                    }
-                    _ => {
-                        let response = pchain_client.block_v2(&BlockRequest { block_hash }).await;
-
-                        display_beautified_rpc_result(ClientResponse::Block(response));
-                    }
-                };
+                display_block(
+                    &pchain_client,
+                    block_hash,
+                    header_only,
+                    with_receipts,
+                    cache.as_deref(),
+                )
+                .await;
             } else if let Some(block_height) = block_height {
-                let response = pchain_client
-                    .block_hash_by_height(&BlockHashByHeightRequest { block_height })
-                    .await;
+                let response = retried("block_hash_by_height", || async {
+                    pchain_client
+                        .block_hash_by_height(&BlockHashByHeightRequest { block_height })
+                        .await
+                })
+                .await;
 
                 let block_hash = match response {
                     Ok(BlockHashByHeightResponse {
@@ -172,90 +205,43 @@ pub async fn match_query_subcommand(query_subcommand: Query, config: Config) {
                         block_hash: Some(block_hash),
                     }) => block_hash,
                     Err(e) => {
-                        println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
-                        std::process::exit(1);
+                        DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
                     }
                     _ => {
-                        println!("{}", DisplayMsg::CannotFindRelevantBlock);
-                        std::process::exit(1);
+                        DisplayMsg::CannotFindRelevantBlock.exit();
                     }
                 };
 
-                match query_subcommand {
-                    Query::BlockHeader {
-                        block_height: _,
-                        block_hash: _,
-                        tx_hash: _,
-                        latest: _,
-                    } => {
-                        let response = pchain_client
-                            .block_header_v2(&BlockHeaderRequest { block_hash })
-                            .await;
-
-                        display_beautified_rpc_result(ClientResponse::BlockHeader(response));
-                    }
-                    _ => {
-                        let response = pchain_client.block_v2(&BlockRequest { block_hash }).await;
-
-                        display_beautified_rpc_result(ClientResponse::Block(response));
-                    }
-                };
+                display_block(
+                    &pchain_client,
+                    block_hash,
+                    header_only,
+                    with_receipts,
+                    cache.as_deref(),
+                )
+                .await;
             } else if let Some(hash) = block_hash {
                 let block_hash: pchain_types::cryptography::Sha256Hash =
-                    match base64url_to_public_address(hash) {
-                        Ok(hash) => hash,
-                        Err(e) => {
-                            println!(
-                                "{}",
-                                DisplayMsg::FailToDecodeBase64Hash(
-                                    String::from("block"),
-                                    String::from(hash),
-                                    e.to_string()
-                                )
-                            );
-                            std::process::exit(1);
-                        }
-                    };
-
-                match query_subcommand {
-                    Query::BlockHeader {
-                        block_height: _,
-                        block_hash: _,
-                        tx_hash: _,
-                        latest: _,
-                    } => {
-                        let response = pchain_client
-                            .block_header_v2(&BlockHeaderRequest { block_hash })
-                            .await;
-
-                        display_beautified_rpc_result(ClientResponse::BlockHeader(response));
-                    }
-                    _ => {
-                        let response = pchain_client.block_v2(&BlockRequest { block_hash }).await;
+                    decode_hash_or_exit("block", hash);
 
-                        display_beautified_rpc_result(ClientResponse::Block(response));
-                    }
-                }
+                display_block(
+                    &pchain_client,
+                    block_hash,
+                    header_only,
+                    with_receipts,
+                    cache.as_deref(),
+                )
+                .await;
             } else if let Some(hash) = tx_hash {
                 let transaction_hash: pchain_types::cryptography::Sha256Hash =
-                    match base64url_to_public_address(hash) {
-                        Ok(hash) => hash,
-                        Err(e) => {
-                            println!(
-                                "{}",
-                                DisplayMsg::FailToDecodeBase64Hash(
-                                    String::from("transaction"),
-                                    String::from(hash),
-                                    e.to_string()
-                                )
-                            );
-                            std::process::exit(1);
-                        }
-                    };
+                    decode_hash_or_exit("transaction", hash);
 
-                let response = pchain_client
-                    .transaction_position(&TransactionPositionRequest { transaction_hash })
-                    .await;
+                let response = retried("transaction_position", || async {
+                    pchain_client
+                        .transaction_position(&TransactionPositionRequest { transaction_hash })
+                        .await
+                })
+                .await;
 
                 let block_hash = match response {
                     Ok(TransactionPositionResponse {
@@ -264,128 +250,307 @@ pub async fn match_query_subcommand(query_subcommand: Query, config: Config) {
                         position: _,
                     }) => block_hash,
                     Err(e) => {
-                        println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
-                        std::process::exit(1);
+                        DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
                     }
                     _ => {
-                        println!("{}", DisplayMsg::CannotFindRelevantBlock);
-                        std::process::exit(1);
+                        DisplayMsg::CannotFindRelevantBlock.exit();
                     }
                 };
 
-                match query_subcommand {
-                    Query::BlockHeader {
-                        block_height: _,
-                        block_hash: _,
-                        tx_hash: _,
-                        latest: _,
-                    } => {
-                        let response = pchain_client
-                            .block_header_v2(&BlockHeaderRequest { block_hash })
-                            .await;
-
-                        display_beautified_rpc_result(ClientResponse::BlockHeader(response));
-                    }
-                    _ => {
-                        let response = pchain_client.block_v2(&BlockRequest { block_hash }).await;
-
-                        display_beautified_rpc_result(ClientResponse::Block(response));
-                    }
-                }
+                display_block(
+                    &pchain_client,
+                    block_hash,
+                    header_only,
+                    with_receipts,
+                    cache.as_deref(),
+                )
+                .await;
             }
         }
-        Query::Tx { tx_hash } => {
+        Query::Tx {
+            tx_hash,
+            full,
+            summary,
+        } => {
             let tx_hash: pchain_types::cryptography::Sha256Hash =
-                match base64url_to_public_address(&tx_hash) {
-                    Ok(hash) => hash,
-                    Err(e) => {
-                        println!(
-                            "{}",
-                            DisplayMsg::FailToDecodeBase64Hash(
-                                String::from("transaction"),
-                                tx_hash,
-                                e.to_string()
-                            )
-                        );
-                        std::process::exit(1);
-                    }
-                };
+                decode_hash_or_exit("transaction", &tx_hash);
 
-            let response = pchain_client
-                .transaction_v2(&TransactionRequest {
-                    transaction_hash: tx_hash,
-                    include_receipt: true,
-                })
-                .await;
+            let response = cached(
+                "transaction_v2",
+                &tx_hash,
+                cache.as_deref(),
+                |response: &TransactionResponseV2| response.block_hash.is_some(),
+                || async {
+                    pchain_client
+                        .transaction_v2(&TransactionRequest {
+                            transaction_hash: tx_hash,
+                            include_receipt: true,
+                        })
+                        .await
+                },
+            )
+            .await;
+
+            display_beautified_rpc_result(ClientResponse::Transaction(response, full, summary));
+        }
+        Query::Receipt {
+            tx_hash,
+            summary,
+            json,
+        } => {
+            let tx_hash: pchain_types::cryptography::Sha256Hash =
+                decode_hash_or_exit("transaction", &tx_hash);
+
+            let response = cached(
+                "receipt_v2",
+                &tx_hash,
+                cache.as_deref(),
+                |response: &ReceiptResponseV2| response.receipt.is_some(),
+                || async {
+                    pchain_client
+                        .receipt_v2(&ReceiptRequest {
+                            transaction_hash: tx_hash,
+                        })
+                        .await
+                },
+            )
+            .await;
 
-            display_beautified_rpc_result(ClientResponse::Transaction(response));
+            display_beautified_rpc_result(ClientResponse::Receipt(response, summary, json));
         }
-        Query::Receipt { tx_hash } => {
+        Query::TxGas { tx_hash } => {
             let tx_hash: pchain_types::cryptography::Sha256Hash =
-                match base64url_to_public_address(&tx_hash) {
-                    Ok(hash) => hash,
+                decode_hash_or_exit("transaction", &tx_hash);
+
+            let response = cached(
+                "receipt_v2",
+                &tx_hash,
+                cache.as_deref(),
+                |response: &ReceiptResponseV2| response.receipt.is_some(),
+                || async {
+                    pchain_client
+                        .receipt_v2(&ReceiptRequest {
+                            transaction_hash: tx_hash,
+                        })
+                        .await
+                },
+            )
+            .await;
+
+            display_beautified_rpc_result(ClientResponse::TxGas(response));
+        }
+        Query::Epoch => {
+            let response = retried("state_v2", || async {
+                pchain_client
+                    .state_v2(&StateRequest {
+                        accounts: HashSet::from([]),
+                        include_contract: false,
+                        storage_keys: HashMap::from([(
+                            NETWORK_ADDRESS,
+                            HashSet::from([CURRENT_EPOCH_KEY.to_vec()]),
+                        )]),
+                    })
+                    .await
+            })
+            .await;
+
+            let (epoch, block_hash) = match current_epoch(response) {
+                Ok(epoch_at_block) => epoch_at_block,
+                Err(e) => e.exit(),
+            };
+
+            // the header of the block which the state was read at, so that the epoch and the block agree
+            let response = retried("block_header_v2", || async {
+                pchain_client
+                    .block_header_v2(&BlockHeaderRequest { block_hash })
+                    .await
+            })
+            .await;
+
+            display_beautified_rpc_result(ClientResponse::Epoch(response, epoch));
+        }
+        Query::TxStatus {
+            tx_hash,
+            watch,
+            interval,
+            timeout,
+        } => {
+            let tx_hash_bytes: pchain_types::cryptography::Sha256Hash =
+                decode_hash_or_exit("transaction", &tx_hash);
+
+            let started_at = std::time::Instant::now();
+            let mut wait = std::time::Duration::from_secs(interval.max(1));
+            let max_wait = wait * 8;
+            loop {
+                match retried("transaction_position", || async {
+                    pchain_client
+                        .transaction_position(&TransactionPositionRequest {
+                            transaction_hash: tx_hash_bytes,
+                        })
+                        .await
+                })
+                .await
+                {
+                    Ok(TransactionPositionResponse {
+                        transaction_hash: _,
+                        block_hash: Some(block_hash),
+                        position: Some(position),
+                    }) => {
+                        display_text(DisplayMsg::TransactionCommitted(
+                            tx_hash,
+                            base64url::encode(block_hash),
+                            position,
+                        ));
+                        return;
+                    }
+                    Ok(_) if !watch => DisplayMsg::TransactionNotCommitted(tx_hash).exit(),
+                    Ok(_) => println!("{}", DisplayMsg::TransactionNotCommitted(tx_hash.clone())),
                     Err(e) => {
-                        println!(
-                            "{}",
-                            DisplayMsg::FailToDecodeBase64Hash(
-                                String::from("transaction"),
-                                tx_hash,
-                                e.to_string()
-                            )
-                        );
-                        std::process::exit(1);
+                        DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
                     }
                 };
 
-            let response = pchain_client
-                .receipt_v2(&ReceiptRequest {
-                    transaction_hash: tx_hash,
+                let elapsed = started_at.elapsed();
+                if elapsed >= std::time::Duration::from_secs(timeout) {
+                    DisplayMsg::WatchTimeout(tx_hash, timeout).exit();
+                }
+
+                // back off between polls, without sleeping past the timeout
+                tokio::time::sleep(wait.min(std::time::Duration::from_secs(timeout) - elapsed))
+                    .await;
+                wait = (wait * 2).min(max_wait);
+            }
+        }
+        Query::TxDetail { tx_hash } => {
+            let tx_hash_bytes: pchain_types::cryptography::Sha256Hash =
+                decode_hash_or_exit("transaction", &tx_hash);
+
+            let mut detail = TransactionDetail {
+                transaction_hash: tx_hash,
+                known: false,
+                block_hash: None,
+                block_height: None,
+                position: None,
+                timestamp: None,
+                exit_codes: None,
+                total_gas_used: None,
+            };
+
+            let receipt = match retried("transaction_v2", || async {
+                pchain_client
+                    .transaction_v2(&TransactionRequest {
+                        transaction_hash: tx_hash_bytes,
+                        include_receipt: true,
+                    })
+                    .await
+            })
+            .await
+            {
+                Ok(TransactionResponseV2 {
+                    transaction: Some(_),
+                    receipt,
+                    ..
+                }) => receipt,
+                Ok(_) => {
+                    display_json(serde_json::to_value(detail).unwrap());
+                    return;
+                }
+                Err(e) => {
+                    DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
+                }
+            };
+            detail.known = true;
+
+            if let Some(receipt) = receipt {
+                let receipt = receipt_from_rpc(receipt);
+                detail.total_gas_used = Some(total_gas_used(&receipt));
+                detail.exit_codes = Some(
+                    receipt
+                        .iter()
+                        .map(|command_receipt| command_receipt.exit_code().to_string())
+                        .collect(),
+                );
+            }
+
+            let block_hash = match retried("transaction_position", || async {
+                pchain_client
+                    .transaction_position(&TransactionPositionRequest {
+                        transaction_hash: tx_hash_bytes,
+                    })
+                    .await
+            })
+            .await
+            {
+                Ok(TransactionPositionResponse {
+                    transaction_hash: _,
+                    block_hash,
+                    position,
+                }) => {
+                    detail.position = position;
+                    block_hash
+                }
+                Err(e) => {
+                    DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
+                }
+            };
+
+            if let Some(block_hash) = block_hash {
+                detail.block_hash = Some(base64url::encode(block_hash));
+
+                match retried("block_header_v2", || async {
+                    pchain_client
+                        .block_header_v2(&BlockHeaderRequest { block_hash })
+                        .await
                 })
-                .await;
+                .await
+                {
+                    Ok(BlockHeaderResponseV2 {
+                        block_header: Some(block_header),
+                    }) => {
+                        let (height, timestamp) = match block_header {
+                            BlockHeaderV1ToV2::V1(bh) => (bh.height, bh.timestamp),
+                            BlockHeaderV1ToV2::V2(bh) => (bh.height, bh.timestamp),
+                        };
+                        detail.block_height = Some(height);
+                        detail.timestamp = Some(format_unix_timestamp(timestamp as u64));
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        DisplayMsg::RespnoseWithHTTPError(e.to_string()).exit();
+                    }
+                }
+            }
 
-            display_beautified_rpc_result(ClientResponse::Receipt(response));
+            display_json(serde_json::to_value(detail).unwrap());
         }
         Query::Storage { address, key } => {
             let contract_address: pchain_types::cryptography::PublicAddress =
-                match base64url_to_public_address(&address) {
-                    Ok(addr) => addr,
-                    Err(e) => {
-                        println!(
-                            "{}",
-                            DisplayMsg::FailToDecodeBase64Address(
-                                String::from("contract"),
-                                address,
-                                e.to_string()
-                            )
-                        );
-                        std::process::exit(1);
-                    }
-                };
-            let world_state_key: Vec<u8> = match base64url::decode(&key) {
+                decode_address_or_exit("contract", &address);
+            let world_state_key: Vec<u8> = match decode_base64url(&key) {
                 Ok(k) => k,
                 Err(e) => {
-                    println!(
-                        "{}",
-                        DisplayMsg::FailToDecodeBase64String(
-                            String::from("world state key"),
-                            key,
-                            e.to_string()
-                        )
-                    );
-                    std::process::exit(1);
+                    DisplayMsg::FailToDecodeBase64String(
+                        String::from("world state key"),
+                        key,
+                        e.to_string(),
+                    )
+                    .exit();
                 }
             };
 
-            let response = pchain_client
-                .state_v2(&StateRequest {
-                    accounts: HashSet::from([]),
-                    include_contract: true,
-                    storage_keys: HashMap::from([(
-                        contract_address,
-                        HashSet::from([world_state_key]),
-                    )]),
-                })
-                .await;
+            let response = retried("state_v2", || async {
+                pchain_client
+                    .state_v2(&StateRequest {
+                        accounts: HashSet::from([]),
+                        include_contract: true,
+                        storage_keys: HashMap::from([(
+                            contract_address,
+                            HashSet::from([world_state_key.clone()]),
+                        )]),
+                    })
+                    .await
+            })
+            .await;
 
             display_beautified_rpc_result(ClientResponse::State(response));
         }
@@ -395,20 +560,7 @@ pub async fn match_query_subcommand(query_subcommand: Query, config: Config) {
             arguments,
         } => {
             let contract_address: pchain_types::cryptography::PublicAddress =
-                match base64url_to_public_address(&target) {
-                    Ok(addr) => addr,
-                    Err(e) => {
-                        println!(
-                            "{}",
-                            DisplayMsg::FailToDecodeBase64Address(
-                                String::from("target"),
-                                target,
-                                e.to_string()
-                            )
-                        );
-                        std::process::exit(1);
-                    }
-                };
+                decode_address_or_exit("target", &target);
 
             let arguments = match arguments {
                 Some(path) => {
@@ -416,38 +568,31 @@ pub async fn match_query_subcommand(query_subcommand: Query, config: Config) {
                     let arguments_json = match read_file_to_utf8string(path_to_json.clone()) {
                         Ok(result) => result,
                         Err(e) => {
-                            println!(
-                                "{}",
-                                DisplayMsg::FailToOpenOrReadFile(
-                                    String::from("view argment json"),
-                                    path_to_json,
-                                    e
-                                )
-                            );
-                            std::process::exit(1);
+                            DisplayMsg::FailToOpenOrReadFile(
+                                String::from("view argment json"),
+                                path_to_json,
+                                e,
+                            )
+                            .exit();
                         }
                     };
 
                     let arguments: Value = match serde_json::from_str(&arguments_json) {
                         Ok(json_val) => json_val,
                         Err(e) => {
-                            println!("{}", DisplayMsg::InvalidJson(e));
-                            std::process::exit(1);
+                            DisplayMsg::InvalidJson(e).exit();
                         }
                     };
 
                     let call_arguments = match call_arguments_from_json_value(&arguments) {
                         Ok(result) => result,
                         Err(e) => {
-                            println!(
-                                "{}",
-                                DisplayMsg::FailToDecodeJson(
-                                    String::from("call argument"),
-                                    path_to_json,
-                                    e.to_string()
-                                )
-                            );
-                            std::process::exit(1);
+                            DisplayMsg::FailToDecodeJson(
+                                String::from("call argument"),
+                                path_to_json,
+                                e.to_string(),
+                            )
+                            .exit();
                         }
                     };
 
@@ -456,13 +601,20 @@ pub async fn match_query_subcommand(query_subcommand: Query, config: Config) {
                 None => None,
             };
 
-            let response = pchain_client
-                .view_v2(&ViewRequest {
-                    target: contract_address,
-                    method: method.into_bytes(),
-                    arguments,
+            // `ViewRequest` carries no gas limit. The budget for view calls is decided by the Fullnode.
+            let request = ViewRequest {
+                target: contract_address,
+                method: method.into_bytes(),
+                arguments,
+            };
+            let response = if let Some(cache_dir) = cache.as_deref() {
+                cached_view(&pchain_client, cache_dir, &request).await
+            } else {
+                retried("view_v2", || async {
+                    pchain_client.view_v2(&request).await
                 })
-                .await;
+                .await
+            };
 
             display_beautified_rpc_result(ClientResponse::View(response));
         }
@@ -470,157 +622,580 @@ pub async fn match_query_subcommand(query_subcommand: Query, config: Config) {
             validator_subcommand,
         } => match validator_subcommand {
             Validators::Previous { with_delegator } => {
-                let response = pchain_client
-                    .validator_sets(&ValidatorSetsRequest {
-                        include_prev: true,
-                        include_prev_delegators: with_delegator,
-                        include_curr: false,
-                        include_curr_delegators: false,
-                        include_next: false,
-                        include_next_delegators: false,
-                    })
-                    .await;
+                let response = retried("validator_sets", || async {
+                    pchain_client
+                        .validator_sets(&ValidatorSetsRequest {
+                            include_prev: true,
+                            include_prev_delegators: with_delegator,
+                            include_curr: false,
+                            include_curr_delegators: false,
+                            include_next: false,
+                            include_next_delegators: false,
+                        })
+                        .await
+                })
+                .await;
 
                 display_beautified_rpc_result(ClientResponse::PreviousValidatorSet(response));
             }
             Validators::Current { with_delegator } => {
-                let response = pchain_client
-                    .validator_sets(&ValidatorSetsRequest {
-                        include_prev: false,
-                        include_prev_delegators: false,
-                        include_curr: true,
-                        include_curr_delegators: with_delegator,
-                        include_next: false,
-                        include_next_delegators: false,
-                    })
-                    .await;
+                let response = retried("validator_sets", || async {
+                    pchain_client
+                        .validator_sets(&ValidatorSetsRequest {
+                            include_prev: false,
+                            include_prev_delegators: false,
+                            include_curr: true,
+                            include_curr_delegators: with_delegator,
+                            include_next: false,
+                            include_next_delegators: false,
+                        })
+                        .await
+                })
+                .await;
 
                 display_beautified_rpc_result(ClientResponse::CurrentValidatorSet(response));
             }
-            Validators::Next { with_delegator } => {
-                let response = pchain_client
-                    .validator_sets(&ValidatorSetsRequest {
-                        include_prev: false,
-                        include_prev_delegators: false,
-                        include_curr: false,
-                        include_curr_delegators: false,
-                        include_next: true,
-                        include_next_delegators: with_delegator,
-                    })
-                    .await;
+            Validators::Next {
+                with_delegator,
+                json,
+            } => {
+                let response = retried("validator_sets", || async {
+                    pchain_client
+                        .validator_sets(&ValidatorSetsRequest {
+                            include_prev: false,
+                            include_prev_delegators: false,
+                            include_curr: false,
+                            include_curr_delegators: false,
+                            include_next: true,
+                            include_next_delegators: with_delegator,
+                        })
+                        .await
+                })
+                .await;
 
-                display_beautified_rpc_result(ClientResponse::NextValidatorSet(response));
+                display_beautified_rpc_result(ClientResponse::NextValidatorSet(response, json));
             }
         },
         Query::Deposit { operator, owner } => {
             let operator: pchain_types::cryptography::PublicAddress =
-                match base64url_to_public_address(&operator) {
-                    Ok(addr) => addr,
-                    Err(e) => {
-                        println!(
-                            "{}",
-                            DisplayMsg::FailToDecodeBase64Address(
-                                String::from("operator"),
-                                operator,
-                                e.to_string()
-                            )
-                        );
-                        std::process::exit(1);
-                    }
-                };
+                decode_address_or_exit("operator", &resolve_address_or_exit(&operator));
 
             let owner: pchain_types::cryptography::PublicAddress =
-                match base64url_to_public_address(&owner) {
-                    Ok(addr) => addr,
-                    Err(e) => {
-                        println!(
-                            "{}",
-                            DisplayMsg::FailToDecodeBase64Address(
-                                String::from("owner"),
-                                owner,
-                                e.to_string()
-                            )
-                        );
-                        std::process::exit(1);
-                    }
-                };
+                decode_address_or_exit("owner", &resolve_address_or_exit(&owner));
 
-            let response = pchain_client
-                .deposits(&DepositsRequest {
-                    stakes: HashSet::from([(operator, owner)]),
-                })
-                .await;
+            let response = retried("deposits", || async {
+                pchain_client
+                    .deposits(&DepositsRequest {
+                        stakes: HashSet::from([(operator, owner)]),
+                    })
+                    .await
+            })
+            .await;
 
             display_beautified_rpc_result(ClientResponse::Deposit(response))
         }
         Query::Pool {
             operator,
             with_stakes,
+            json,
         } => {
             let operator: pchain_types::cryptography::PublicAddress =
-                match base64url_to_public_address(&operator) {
-                    Ok(addr) => addr,
-                    Err(e) => {
-                        println!(
-                            "{}",
-                            DisplayMsg::FailToDecodeBase64Address(
-                                String::from("operator"),
-                                operator,
-                                e.to_string()
-                            )
-                        );
-                        std::process::exit(1);
-                    }
-                };
+                decode_address_or_exit("operator", &resolve_address_or_exit(&operator));
 
-            let response = pchain_client
-                .pools(&PoolsRequest {
-                    operators: HashSet::from([operator]),
-                    include_stakes: with_stakes,
-                })
-                .await;
+            let response = retried("pools", || async {
+                pchain_client
+                    .pools(&PoolsRequest {
+                        operators: HashSet::from([operator]),
+                        include_stakes: with_stakes,
+                    })
+                    .await
+            })
+            .await;
 
-            display_beautified_rpc_result(ClientResponse::Pool(response))
+            display_beautified_rpc_result(ClientResponse::Pool(response, json))
         }
         Query::Stake { operator, owner } => {
             let operator: pchain_types::cryptography::PublicAddress =
-                match base64url_to_public_address(&operator) {
-                    Ok(addr) => addr,
-                    Err(e) => {
-                        println!(
-                            "{}",
-                            DisplayMsg::FailToDecodeBase64Address(
-                                String::from("operator"),
-                                operator,
-                                e.to_string()
-                            )
-                        );
-                        std::process::exit(1);
-                    }
-                };
+                decode_address_or_exit("operator", &resolve_address_or_exit(&operator));
 
             let owner: pchain_types::cryptography::PublicAddress =
-                match base64url_to_public_address(&owner) {
-                    Ok(addr) => addr,
-                    Err(e) => {
-                        println!(
-                            "{}",
-                            DisplayMsg::FailToDecodeBase64Address(
-                                String::from("owner"),
-                                owner,
-                                e.to_string()
-                            )
-                        );
-                        std::process::exit(1);
-                    }
-                };
+                decode_address_or_exit("owner", &resolve_address_or_exit(&owner));
 
-            let response = pchain_client
-                .stakes(&StakesRequest {
-                    stakes: HashSet::from([(operator, owner)]),
-                })
-                .await;
+            let response = retried("stakes", || async {
+                pchain_client
+                    .stakes(&StakesRequest {
+                        stakes: HashSet::from([(operator, owner)]),
+                    })
+                    .await
+            })
+            .await;
             display_beautified_rpc_result(ClientResponse::StakePower(response))
         }
+        Query::Batch {
+            file,
+            batch_options,
+        } => {
+            run_query_batch(
+                PathBuf::from(file),
+                config,
+                cache.is_some(),
+                BatchErrors::new(&batch_options, true).on_stderr(),
+            )
+            .await
+        }
+    }
+}
+
+/// [BatchQuery] is a query listed in the file of `query batch`.
+#[derive(Deserialize)]
+struct BatchQuery {
+    /// Key of the result of the query.
+    name: String,
+    /// Arguments of `pchain_client query`, e.g. `["balance", "--address", "<ADDRESS>"]`.
+    query: Vec<String>,
+}
+
+// `run_query_batch` runs the queries listed in a JSON file and displays their results as one JSON object.
+//  All queries are checked before any is run. Each query is run in-process with its results captured instead
+//  of printed, and a failed query hands back its error, which does not end the batch under `--collect-errors`.
+//  The result of a failed query is its error message under `error`.
+//  # Arguments
+//  * `path` - path to the JSON file of queries
+//  * `config` - networking config for Client
+//  * `cache` - whether the queries are answered from the query cache
+//  * `batch_errors` - error policy of the batch
+//
+async fn run_query_batch(
+    path: PathBuf,
+    config: &Config,
+    cache: bool,
+    mut batch_errors: BatchErrors,
+) {
+    let batch: Vec<BatchQuery> = match read_file_to_utf8string(path.clone())
+        .and_then(|contents| serde_json::from_str(&contents).map_err(|e| e.to_string()))
+    {
+        Ok(batch) => batch,
+        Err(e) => DisplayMsg::InvalidQueryBatch(path, e).exit(),
+    };
+
+    let mut names = HashSet::new();
+    let mut queries = Vec::new();
+    for query in batch {
+        if !names.insert(query.name.clone()) {
+            DisplayMsg::InvalidQueryBatch(path, format!("Name `{}` is repeated.", query.name))
+                .exit();
+        }
+
+        let args = ["pchain_client", "query"]
+            .iter()
+            .copied()
+            .chain(query.query.iter().map(String::as_str));
+        let parsed = match PChainCLI::try_parse_from(args) {
+            Ok(PChainCLI {
+                command:
+                    PChainCommand::Query {
+                        query_subcommand, ..
+                    },
+                ..
+            }) => match query_subcommand {
+                Query::Batch { .. } => Err(String::from("A batch cannot contain `batch`.")),
+                Query::Balance {
+                    refresh: Some(_), ..
+                }
+                | Query::Nonce {
+                    refresh: Some(_), ..
+                }
+                | Query::TxStatus { watch: true, .. }
+                | Query::Block { follow: true, .. } => Err(String::from(
+                    "`--refresh`, `--watch` and `--follow` never finish, so they cannot be used in a batch.",
+                )),
+                query_subcommand => Ok(query_subcommand),
+            },
+            Ok(_) => unreachable!(),
+            Err(e) => Err(e.to_string().trim().to_string()),
+        };
+        match parsed {
+            Ok(query_subcommand) => queries.push((query.name, query_subcommand)),
+            Err(error) => {
+                DisplayMsg::InvalidQueryBatch(path, format!("Query `{}`: {}", query.name, error))
+                    .exit()
+            }
+        }
+    }
+
+    let mut results = Map::new();
+    for (name, query_subcommand) in queries {
+        // the batch itself is a query, so running one of its queries recurses
+        let query = Box::pin(match_query_subcommand(query_subcommand, config, cache));
+        match capture_results(query).await {
+            Ok(mut displayed) => {
+                let result = if displayed.len() == 1 {
+                    displayed.remove(0)
+                } else {
+                    Value::Array(displayed)
+                };
+                results.insert(name, result);
+                batch_errors.succeed();
+            }
+            Err(error) => {
+                let error = error.to_string();
+                results.insert(name.clone(), json!({ "error": error }));
+                if !batch_errors.collects_errors() {
+                    // the results so far are still displayed, as the batch stops here
+                    display_json(Value::Object(results.clone()));
+                }
+                batch_errors.fail(name.clone(), DisplayMsg::FailToRunQueryBatch(name, error));
+            }
+        }
+    }
+
+    display_json(Value::Object(results));
+    batch_errors.finish();
+}
+
+// `display_block` queries a block, or only its header, by hash and displays it.
+//  # Arguments
+//  * `pchain_client` - client to Fullnode RPC
+//  * `block_hash` - hash of the block
+//  * `header_only` - query the block header instead of the whole block
+//  * `with_receipts` - include the receipts when displaying the whole block
+//  * `cache` - directory of the query cache of the network, if the query cache is used
+//
+async fn display_block(
+    pchain_client: &Client,
+    block_hash: pchain_types::cryptography::Sha256Hash,
+    header_only: bool,
+    with_receipts: bool,
+    cache: Option<&Path>,
+) {
+    if header_only {
+        let response = cached(
+            "block_header_v2",
+            &block_hash,
+            cache,
+            |response: &BlockHeaderResponseV2| response.block_header.is_some(),
+            || async {
+                pchain_client
+                    .block_header_v2(&BlockHeaderRequest { block_hash })
+                    .await
+            },
+        )
+        .await;
+
+        display_beautified_rpc_result(ClientResponse::BlockHeader(response));
+    } else {
+        let response = cached(
+            "block_v2",
+            &block_hash,
+            cache,
+            |response: &BlockResponseV2| response.block.is_some(),
+            || async { pchain_client.block_v2(&BlockRequest { block_hash }).await },
+        )
+        .await;
+
+        display_beautified_rpc_result(ClientResponse::Block(response, with_receipts));
+    }
+}
+
+// `follow_blocks` subscribes to new blocks through the WebSocket endpoint of Fullnode RPC, and displays each
+//  block pushed by the subscription. It only returns by exiting, when the subscription fails or is closed.
+//  # Arguments
+//  * `ws_url` - WebSocket endpoint of Fullnode RPC
+//  * `with_receipts` - whether to include the receipts of the transactions in each block
+//
+async fn follow_blocks(ws_url: &str, with_receipts: bool) {
+    let (mut subscription, _) = match tokio_tungstenite::connect_async(ws_url).await {
+        Ok(connection) => connection,
+        Err(e) => {
+            DisplayMsg::FailToSubscribe(ws_url.to_string(), e.to_string()).exit();
+        }
+    };
+    if let Err(e) = subscription
+        .send(Message::Text(String::from(SUBSCRIBE_NEW_BLOCKS)))
+        .await
+    {
+        DisplayMsg::FailToSubscribe(ws_url.to_string(), e.to_string()).exit();
+    }
+
+    while let Some(message) = subscription.next().await {
+        match message {
+            // a block is pushed as a binary message, serialized in the same way as the response of `block_v2`
+            Ok(Message::Binary(bytes)) => match BlockResponseV2::deserialize(&bytes) {
+                Ok(response) if response.block.is_some() => display_beautified_rpc_result(
+                    ClientResponse::Block(Ok(response), with_receipts),
+                ),
+                Ok(_) => {}
+                Err(e) => {
+                    DisplayMsg::FailToSubscribe(ws_url.to_string(), e.to_string()).exit();
+                }
+            },
+            Ok(Message::Close(_)) => break,
+            // pings are answered by the connection itself, and other messages carry no block
+            Ok(_) => {}
+            Err(e) => {
+                DisplayMsg::FailToSubscribe(ws_url.to_string(), e.to_string()).exit();
+            }
+        }
+    }
+
+    DisplayMsg::SubscriptionClosed(ws_url.to_string()).exit();
+}
+
+// `wait_to_refresh` waits before a query is sent again under `--refresh`. If `refresh_redraws`, the previously
+//  printed line is then cleared, so that the new result replaces it instead of scrolling.
+//  # Arguments
+//  * `refresh` - seconds to wait
+//
+async fn wait_to_refresh(refresh: u64) {
+    tokio::time::sleep(std::time::Duration::from_secs(refresh)).await;
+
+    if refresh_redraws() {
+        // move the cursor up one line and erase that line
+        print!("\x1b[1A\x1b[2K");
+        let _ = std::io::stdout().flush();
+    }
+}
+
+// `refresh_redraws` tells whether `--refresh` replaces the previous result in place. That needs stdout to be a
+//  terminal, and nothing to be printed to the same terminal on stderr between two results, e.g. by `--timing`,
+//  `--raw-response` or retries, since erasing the line above would then erase that output instead of the result.
+//  # Arguments
+//  *
+fn refresh_redraws() -> bool {
+    let options = display_options();
+    let stderr_interleaves = std::io::stderr().is_terminal()
+        && (options.timing || options.raw_response || retries() > 0);
+    std::io::stdout().is_terminal() && !stderr_interleaves
+}
+
+// `state_error` returns the error of a failed state query, which `--refresh` shows as the result instead of exiting.
+//  # Arguments
+//  * `response` - response of the state query
+//
+fn state_error(response: &Result<StateResponseV2, ErrorResponse>) -> Option<DisplayMsg> {
+    match response {
+        Ok(StateResponseV2::Ok { .. }) => None,
+        Ok(StateResponseV2::Error { error }) => {
+            Some(DisplayMsg::RespnoseWithHTTPError(format!("{:?}", error)))
+        }
+        Err(e) => Some(DisplayMsg::RespnoseWithHTTPError(e.to_string())),
+    }
+}
+
+// `cached` answers a request for committed data from the query cache if it is there. Otherwise the request
+//  is sent to Fullnode RPC, and a response holding committed data is saved to the cache. Data is immutable once
+//  committed, so cached responses never expire.
+//  # Arguments
+//  * `request` - name of the request
+//  * `hash` - block or transaction hash the request is about
+//  * `cache` - directory of the query cache of the network, if the query cache is used
+//  * `is_committed` - whether a response holds committed data and can be saved
+//  * `send` - sends the request, called once per attempt
+//
+async fn cached<T, F, Fut>(
+    request: &str,
+    hash: &pchain_types::cryptography::Sha256Hash,
+    cache: Option<&Path>,
+    is_committed: fn(&T) -> bool,
+    send: F,
+) -> Result<T, ErrorResponse>
+where
+    T: Serializable + Deserializable,
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    let cache_dir = match cache {
+        Some(cache_dir) => cache_dir,
+        None => return retried(request, send).await,
+    };
+
+    let path = cache_dir.join(format!("{}_{}", request, base64url::encode(hash)));
+    cached_at(path, request, is_committed, send).await
+}
+
+// `cached_view` answers a view call from the query cache if the same call was made when the highest committed
+//  block was the same as now. Otherwise the call is sent to Fullnode RPC and its response is saved. Cached view
+//  calls are cleared once the highest committed block changes, as the state they read may have changed.
+//  # Arguments
+//  * `pchain_client` - client to Fullnode RPC
+//  * `cache_dir` - directory of the query cache of the network
+//  * `request` - the view call
+//
+async fn cached_view(
+    pchain_client: &Client,
+    cache_dir: &Path,
+    request: &ViewRequest,
+) -> Result<ViewResponseV2, ErrorResponse> {
+    let highest_committed_block = match retried("highest_committed_block", || async {
+        pchain_client.highest_committed_block().await
+    })
+    .await
+    {
+        Ok(HighestCommittedBlockResponse {
+            block_hash: Some(block_hash),
+        }) => block_hash,
+        // The cache cannot be checked against an unknown head, so it is not used.
+        _ => return retried("view_v2", || async { pchain_client.view_v2(request).await }).await,
+    };
+
+    let view_cache_dir = cache_dir.join(VIEW_CACHE_DIRNAME);
+    let head_dir = view_cache_dir.join(base64url::encode(highest_committed_block));
+    if let Ok(entries) = std::fs::read_dir(&view_cache_dir) {
+        for entry in entries.flatten() {
+            if entry.path() != head_dir {
+                let _ = std::fs::remove_dir_all(entry.path());
+            }
+        }
+    }
+
+    let key: pchain_types::cryptography::Sha256Hash =
+        Sha256::digest(Serializable::serialize(request)).into();
+    cached_at(
+        head_dir.join(base64url::encode(key)),
+        "view_v2",
+        |_| true,
+        || async { pchain_client.view_v2(request).await },
+    )
+    .await
+}
+
+// `cached_at` answers a request from the cache file at `path` if it is there. Otherwise the request is sent to
+//  Fullnode RPC, and the response is saved to the file if it can be cached.
+//  # Arguments
+//  * `path` - path to the cache file of the request
+//  * `request` - name of the request
+//  * `is_committed` - whether a response can be saved
+//  * `send` - sends the request, called once per attempt
+//
+async fn cached_at<T, F, Fut>(
+    path: PathBuf,
+    request: &str,
+    is_committed: fn(&T) -> bool,
+    send: F,
+) -> Result<T, ErrorResponse>
+where
+    T: Serializable + Deserializable,
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    if let Some(response) = read_file(path.clone())
+        .ok()
+        .and_then(|bytes| <T as Deserializable>::deserialize(&bytes).ok())
+    {
+        return Ok(response);
+    }
+
+    let response = retried(request, send).await;
+    if let Ok(response) = &response {
+        // The cache only saves RPC round trips, failing to write it does not fail the query.
+        let cache_dir = path
+            .parent()
+            .map(|dir| dir.to_path_buf())
+            .unwrap_or_default();
+        if is_committed(response) && std::fs::create_dir_all(cache_dir).is_ok() {
+            let _ = write_file(path, &Serializable::serialize(response));
+        }
+    }
+    response
+}
+
+// `network_cache_dir` returns the directory of the query cache for a Fullnode RPC, so that responses of one
+//  network are never answered for another. The directory is named by the hash of the URL.
+//  # Arguments
+//  * `url` - URL of Fullnode RPC
+//
+fn network_cache_dir(url: &str) -> PathBuf {
+    let key: pchain_types::cryptography::Sha256Hash = Sha256::digest(url.as_bytes()).into();
+    get_cache_dir().join(base64url::encode(key))
+}
+
+// `current_epoch` reads the current epoch from a state query of the network account, and returns it together
+//  with the hash of the block which the state was read at.
+//  # Arguments
+//  * `response` - response of the state query for `CURRENT_EPOCH_KEY` of the network account
+//
+fn current_epoch(
+    response: Result<StateResponseV2, ErrorResponse>,
+) -> Result<(u64, pchain_types::cryptography::Sha256Hash), DisplayMsg> {
+    match response {
+        Ok(StateResponseV2::Ok {
+            accounts: _,
+            storage_tuples,
+            block_hash,
+        }) => storage_tuples
+            .get(&NETWORK_ADDRESS)
+            .and_then(|storage| storage.get(CURRENT_EPOCH_KEY.as_slice()))
+            // the epoch is stored as a Borsh encoded u64
+            .and_then(|value| <[u8; 8]>::try_from(value.as_slice()).ok())
+            .map(|value| (u64::from_le_bytes(value), block_hash))
+            .ok_or(DisplayMsg::CannotFindCurrentEpoch),
+        Ok(StateResponseV2::Error { error }) => {
+            Err(DisplayMsg::RespnoseWithHTTPError(format!("{:?}", error)))
+        }
+        Err(e) => Err(DisplayMsg::RespnoseWithHTTPError(e.to_string())),
+    }
+}
+
+/// Address of the network account, whose storage holds the state of the network, e.g. the current epoch
+const NETWORK_ADDRESS: pchain_types::cryptography::PublicAddress = [0; 32];
+
+/// Key of the current epoch in the storage of the network account
+const CURRENT_EPOCH_KEY: [u8; 1] = [5];
+
+/// Directory name of cached view calls, under the query cache of a network
+const VIEW_CACHE_DIRNAME: &str = "view";
+
+/// Request sent to the WebSocket endpoint of Fullnode RPC to subscribe to new blocks
+const SUBSCRIBE_NEW_BLOCKS: &str =
+    r#"{"jsonrpc":"2.0","id":1,"method":"subscribe","params":["new_blocks"]}"#;
+
+#[cfg(test)]
+mod test {
+    use super::{current_epoch, network_cache_dir, CURRENT_EPOCH_KEY, NETWORK_ADDRESS};
+    use crate::config::get_cache_dir;
+    use pchain_types::rpc::StateResponseV2;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_current_epoch() {
+        let state = |value: Vec<u8>| {
+            Ok(StateResponseV2::Ok {
+                accounts: HashMap::new(),
+                storage_tuples: HashMap::from([(
+                    NETWORK_ADDRESS,
+                    HashMap::from([(CURRENT_EPOCH_KEY.to_vec(), value)]),
+                )]),
+                block_hash: [1; 32],
+            })
+        };
+
+        assert_eq!(
+            current_epoch(state(42u64.to_le_bytes().to_vec())).unwrap(),
+            (42, [1; 32])
+        );
+        assert!(current_epoch(state(vec![42])).is_err());
+        assert!(current_epoch(Ok(StateResponseV2::Ok {
+            accounts: HashMap::new(),
+            storage_tuples: HashMap::new(),
+            block_hash: [1; 32],
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn test_network_cache_dir() {
+        let home = temp_dir::TempDir::new().unwrap();
+        std::env::set_var("PCHAIN_CLI_HOME", home.path());
+
+        let mainnet = network_cache_dir("https://pchain-main-rpc02.parallelchain.io");
+        let testnet = network_cache_dir("https://pchain-test-rpc02.parallelchain.io");
+
+        assert_eq!(mainnet.parent(), Some(get_cache_dir().as_path()));
+        assert_eq!(testnet.parent(), Some(get_cache_dir().as_path()));
+        assert_ne!(mainnet, testnet);
+        assert_eq!(
+            mainnet,
+            network_cache_dir("https://pchain-main-rpc02.parallelchain.io")
+        );
     }
 }
@@ -6,18 +6,31 @@
 //! Methods related to subcommand `submit` in `pchain-client`.
 
 use pchain_client::Client;
-use serde_json::Value;
-use std::path::PathBuf;
+use pchain_types::blockchain::{Command, CommandReceiptV2};
+use pchain_types::cryptography::{PublicAddress, Sha256Hash};
+use pchain_types::rpc::{
+    Account, BlockHeaderRequest, BlockHeaderResponse, BlockHeaderResponseV2, BlockHeaderV1ToV2,
+    BlockRequest, BlockResponseV2, BlockV1ToV2, HighestCommittedBlockResponse, ReceiptRequest,
+    ReceiptResponseV2, ReceiptV1ToV2, StateRequest, StateResponseV2, TransactionV1OrV2,
+};
+use pchain_types::serialization::Serializable;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
 
 use crate::command::{CreateTx, DepositTx, PoolTx, StakeTx, Transaction};
-use crate::config::Config;
+use crate::config::{get_keypair_path, get_submitted_ledger_path, Config};
 use crate::display_msg::DisplayMsg;
 use crate::display_types::{check_contract_exist, SubmitTx, TxCommand};
+use crate::keypair::{get_keypair_from_json, KeypairJSON};
 use crate::parser::{
-    base64url_to_public_address, call_arguments_from_json_array, parse_json_arguments,
+    call_arguments_from_json_array, call_arguments_layout, parse_call_result_from_schema,
+    parse_json_arguments, validate_call_result_schema,
 };
-use crate::result::{display_beautified_rpc_result, ClientResponse};
-use crate::utils::read_file_to_utf8string;
+use crate::result::{display_beautified_rpc_result, display_json, ClientResponse, ErrorResponse};
+use crate::sub_commands::{decode_address_or_exit, resolve_address_or_exit, timed, BatchErrors};
+use crate::utils::{self, read_file_to_utf8string, warn, write_file};
 
 // `match_submit_subcommand` matches a CLI argument to its corresponding `Submit` subcommand and processes
 //  the request.
@@ -30,38 +43,453 @@ pub async fn match_submit_subcommand(tx_subcommand: Transaction, config: Config)
     let pchain_client = Client::new(url);
 
     match tx_subcommand {
-        Transaction::Submit { file, keypair_name } => {
+        Transaction::Submit {
+            file,
+            keypair_name,
+            hash_only,
+            min_base_fee_per_gas,
+            allow_low_base_fee,
+            expected_nonce,
+            assert_nonce,
+            wait_receipt,
+            decode_with,
+            timeout,
+            expected_signer,
+            explain,
+            idempotent,
+        } => {
+            // read the schema before submitting, so a bad schema does not leave a submitted transaction behind
+            let schema = decode_with.map(read_call_result_schema);
+
             let submit_tx = match SubmitTx::from_json_file(&file) {
                 Ok(tx_json) => tx_json,
                 Err(e) => {
                     println!("{}", e);
-                    std::process::exit(1);
+                    utils::exit(1);
                 }
             };
 
-            let signed_tx = match submit_tx.prepare_signed_tx(&keypair_name) {
+            check_base_fee(
+                submit_tx.max_base_fee_per_gas,
+                min_base_fee_per_gas,
+                allow_low_base_fee,
+            );
+
+            if let Err(e) = submit_tx
+                .validate_commands()
+                .and_then(|_| submit_tx.validate_addresses())
+            {
+                println!("{}", e);
+                utils::exit(1);
+            }
+
+            let nonce = submit_tx.nonce;
+            if let Some(expected_nonce) = expected_nonce {
+                if nonce != expected_nonce {
+                    println!("{}", DisplayMsg::UnexpectedTxNonce(nonce, expected_nonce));
+                    utils::exit(1);
+                }
+            }
+
+            // the co-signers are checked together with the signer, then refused, as a transaction carries one signature
+            if !explain && !submit_tx.co_signers.is_empty() {
+                if let Err(e) = submit_tx
+                    .clone()
+                    .prepare_signed_tx(std::slice::from_ref(&keypair_name))
+                {
+                    println!("{}", e);
+                    utils::exit(1);
+                }
+            }
+
+            let keypair = load_keypair(&keypair_name);
+            if let Some(expected_signer) = expected_signer {
+                let expected_signer = resolve_address_or_exit(&expected_signer);
+                check_signer(
+                    &keypair,
+                    decode_address_or_exit("expected signer", &expected_signer),
+                );
+            }
+
+            if explain {
+                explain_submit_tx(&file, &keypair, &submit_tx);
+                return;
+            }
+
+            let signed_tx = match submit_tx.sign_with_keypair(keypair) {
                 Ok(tx) => tx,
                 Err(e) => {
                     println!("{}", e);
-                    std::process::exit(1);
+                    utils::exit(1);
+                }
+            };
+
+            if idempotent {
+                let tx_hash = match &signed_tx {
+                    TransactionV1OrV2::V1(tx) => base64url::encode(tx.hash),
+                    TransactionV1OrV2::V2(tx) => base64url::encode(tx.hash),
+                };
+                if submitted_tx_hashes().contains(&tx_hash) {
+                    println!(
+                        "{}",
+                        DisplayMsg::TxAlreadySubmitted(tx_hash, get_submitted_ledger_path())
+                    );
+                    return;
                 }
+            }
+
+            if assert_nonce {
+                let signer = match &signed_tx {
+                    TransactionV1OrV2::V1(tx) => tx.signer,
+                    TransactionV1OrV2::V2(tx) => tx.signer,
+                };
+                let committed_nonce = committed_nonce(&pchain_client, signer).await;
+                if nonce != committed_nonce {
+                    println!("{}", DisplayMsg::StaleTxNonce(nonce, committed_nonce));
+                    utils::exit(1);
+                }
+            }
+
+            let (tx_hash, call_indices): (_, Vec<usize>) = match &signed_tx {
+                TransactionV1OrV2::V1(tx) => (tx.hash, call_command_indices(&tx.commands)),
+                TransactionV1OrV2::V2(tx) => (tx.hash, call_command_indices(&tx.commands)),
             };
 
-            let response = pchain_client.submit_transaction_v2(&signed_tx).await;
+            let response = timed(
+                "submit_transaction_v2",
+                pchain_client.submit_transaction_v2(&signed_tx),
+            )
+            .await
+            .map_err(ErrorResponse::from);
+
+            display_beautified_rpc_result(ClientResponse::SubmitTx(response, signed_tx, hash_only));
 
-            display_beautified_rpc_result(ClientResponse::SubmitTx(response, signed_tx))
+            // the transaction is accepted, as a rejected transaction exits the program
+            if idempotent {
+                record_submitted_tx_hash(&base64url::encode(tx_hash));
+            }
+
+            // nothing but the hash is printed with `--hash-only`
+            if hash_only || !wait_receipt {
+                return;
+            }
+
+            let response = wait_for_receipt(&pchain_client, tx_hash, timeout).await;
+            let receipt = response.receipt.clone();
+            display_beautified_rpc_result(ClientResponse::Receipt(Ok(response), false, false));
+
+            if let (Some(schema), Some(receipt)) = (schema, receipt) {
+                match decode_call_return_values(receipt, &call_indices, &schema) {
+                    Ok(results) => {
+                        for (index, result) in results {
+                            println!("Command {index}:");
+                            for (name, value) in result {
+                                println!("{name}: {value}");
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        println!("{}", e);
+                        utils::exit(1);
+                    }
+                }
+            }
         }
         Transaction::Create {
             destination,
             v1,
             v2: _,
+            auto_version,
             priority_fee_per_gas,
             gas_limit,
             max_base_fee_per_gas,
             nonce,
+            min_base_fee_per_gas,
+            allow_low_base_fee,
+            sign_with,
+            note,
+            co_signers,
+            batch_options,
             create_tx_subcommand,
         } => {
+            let gas_limit = gas_or_default(gas_limit, config.default_gas_limit, "gas-limit");
+            let max_base_fee_per_gas = gas_or_default(
+                max_base_fee_per_gas,
+                config.default_max_base_fee_per_gas,
+                "max-base-fee-per-gas",
+            );
+            let priority_fee_per_gas = gas_or_default(
+                priority_fee_per_gas,
+                config.default_priority_fee_per_gas,
+                "priority-fee-per-gas",
+            );
+            check_base_fee(
+                max_base_fee_per_gas,
+                min_base_fee_per_gas,
+                allow_low_base_fee,
+            );
+            let v1 = if auto_version {
+                let v1 = network_uses_v1(&pchain_client).await;
+                println!(
+                    "{}",
+                    DisplayMsg::DetectedTxVersion(String::from(if v1 { "V1" } else { "V2" }))
+                );
+                v1
+            } else {
+                v1
+            };
+
+            let verbose = matches!(create_tx_subcommand, CreateTx::Call { verbose: true, .. });
+            // Each item is named for the summary of `--collect-errors`, together with its command and destination
+            let commands: Vec<(String, Result<TxCommand, DisplayMsg>, Option<String>)> =
+                match create_tx_subcommand {
+                    CreateTx::Call {
+                        target,
+                        method,
+                        arguments: Some(arguments),
+                        amount,
+                        verbose: _,
+                    } if Path::new(&arguments).is_dir() => {
+                        let target = resolve_address_or_exit(&target);
+                        decode_address_or_exit("target", &target);
+
+                        let output_dir =
+                            PathBuf::from(destination.unwrap_or_else(|| ".".to_string()));
+                        let extension = if sign_with.is_some() {
+                            "signed"
+                        } else {
+                            "json"
+                        };
+                        call_argument_files_in_dir(&arguments)
+                            .into_iter()
+                            .map(|file| {
+                                let stem = file.file_stem().unwrap_or_default().to_string_lossy();
+                                let destination = output_dir.join(format!("{stem}_tx.{extension}"));
+                                let command =
+                                    read_call_arguments(&file).map(|arguments| TxCommand::Call {
+                                        target: target.clone(),
+                                        method: method.clone(),
+                                        arguments,
+                                        amount,
+                                    });
+                                (
+                                    file.to_string_lossy().to_string(),
+                                    command,
+                                    Some(destination.to_string_lossy().to_string()),
+                                )
+                            })
+                            .collect()
+                    }
+                    create_tx_subcommand => {
+                        vec![(
+                            String::from("transaction"),
+                            Ok(subcommand_parser(create_tx_subcommand)),
+                            destination,
+                        )]
+                    }
+                };
+
+            // read the keypair once for the whole batch, so that the password is asked only once
+            let signer = sign_with.as_deref().map(load_keypair);
+
+            // The nonce of each item is fixed by its position, whether or not the items before it fail
+            let mut batch_errors = BatchErrors::new(&batch_options, false);
+            for (offset, (item, command, destination)) in (0u64..).zip(commands) {
+                let command = match command {
+                    Ok(command) => command,
+                    Err(e) => {
+                        batch_errors.fail(item, e);
+                        continue;
+                    }
+                };
+
+                if let Err(e) = check_tx_cost(
+                    std::slice::from_ref(&command),
+                    gas_limit,
+                    max_base_fee_per_gas,
+                    priority_fee_per_gas,
+                ) {
+                    batch_errors.fail(item, e);
+                    continue;
+                }
+
+                let arguments_layout = match &command {
+                    TxCommand::Call {
+                        arguments: Some(arguments),
+                        ..
+                    } if verbose => call_arguments_from_json_array(arguments)
+                        .ok()
+                        .map(|call_arguments| call_arguments_layout(arguments, &call_arguments)),
+                    _ => None,
+                };
+
+                let item_nonce = match nonce.checked_add(offset) {
+                    Some(item_nonce) => item_nonce,
+                    None => {
+                        batch_errors.fail(item, DisplayMsg::NonceOverflow(nonce, offset));
+                        continue;
+                    }
+                };
+
+                let tx = SubmitTx {
+                    is_v1: v1,
+                    commands: vec![command],
+                    nonce: item_nonce,
+                    gas_limit,
+                    max_base_fee_per_gas,
+                    priority_fee_per_gas,
+                    note: note.clone(),
+                    co_signers: co_signers.clone(),
+                };
+
+                if let Err(e) = save_created_tx(tx, destination, signer.clone()) {
+                    batch_errors.fail(item, e);
+                    continue;
+                }
+                batch_errors.succeed();
+                if let Some(arguments_layout) = arguments_layout {
+                    display_json(Value::Array(arguments_layout));
+                }
+            }
+            batch_errors.finish();
+        }
+        Transaction::Replace {
+            keypair_name,
+            v1,
+            nonce,
+            file,
+            gas_limit,
+            max_base_fee_per_gas,
+            priority_fee_per_gas,
+            min_base_fee_per_gas,
+            allow_low_base_fee,
+        } => {
+            check_base_fee(
+                max_base_fee_per_gas,
+                min_base_fee_per_gas,
+                allow_low_base_fee,
+            );
+
+            // check the replacement before asking for the password, as it does not depend on the signer
+            let replacement_tx = file.map(|file| match SubmitTx::from_json_file(&file) {
+                Ok(tx_json) => tx_json,
+                Err(e) => {
+                    println!("{}", e);
+                    utils::exit(1);
+                }
+            });
+            if let Some(replacement_tx) = &replacement_tx {
+                if let Err(e) = replacement_tx
+                    .validate_commands()
+                    .and_then(|_| replacement_tx.validate_addresses())
+                {
+                    println!("{}", e);
+                    utils::exit(1);
+                }
+            }
+            if let Err(e) = check_tx_cost(
+                replacement_tx
+                    .as_ref()
+                    .map_or(&[], |replacement_tx| &replacement_tx.commands),
+                gas_limit,
+                max_base_fee_per_gas,
+                priority_fee_per_gas,
+            ) {
+                e.exit();
+            }
+
+            if let Some(replacement_tx) = replacement_tx
+                .as_ref()
+                .filter(|replacement_tx| !replacement_tx.co_signers.is_empty())
+            {
+                if let Err(e) = replacement_tx
+                    .clone()
+                    .prepare_signed_tx(std::slice::from_ref(&keypair_name))
+                {
+                    println!("{}", e);
+                    utils::exit(1);
+                }
+            }
+
+            let keypair = load_keypair(&keypair_name);
+            let signer = decode_address_or_exit("signer", &keypair.public_key);
+
+            let nonce = match nonce {
+                Some(nonce) => nonce,
+                None => committed_nonce(&pchain_client, signer).await,
+            };
+
+            let mut replacement_tx = replacement_tx.unwrap_or_else(|| SubmitTx {
+                is_v1: v1,
+                commands: vec![TxCommand::Transfer {
+                    recipient: base64url::encode(signer),
+                    amount: 0,
+                }],
+                nonce,
+                gas_limit,
+                max_base_fee_per_gas,
+                priority_fee_per_gas,
+                note: None,
+                co_signers: vec![],
+            });
+            replacement_tx.nonce = nonce;
+            replacement_tx.gas_limit = gas_limit;
+            replacement_tx.max_base_fee_per_gas = max_base_fee_per_gas;
+            replacement_tx.priority_fee_per_gas = priority_fee_per_gas;
+
+            sign_and_submit(&pchain_client, replacement_tx, keypair, false).await;
+        }
+        Transaction::Send {
+            keypair_name,
+            v1,
+            nonce,
+            gas_limit,
+            max_base_fee_per_gas,
+            priority_fee_per_gas,
+            min_base_fee_per_gas,
+            allow_low_base_fee,
+            hash_only,
+            save,
+            create_tx_subcommand,
+        } => {
+            let gas_limit = gas_or_default(gas_limit, config.default_gas_limit, "gas-limit");
+            let max_base_fee_per_gas = gas_or_default(
+                max_base_fee_per_gas,
+                config.default_max_base_fee_per_gas,
+                "max-base-fee-per-gas",
+            );
+            let priority_fee_per_gas = gas_or_default(
+                priority_fee_per_gas,
+                config.default_priority_fee_per_gas,
+                "priority-fee-per-gas",
+            );
+            check_base_fee(
+                max_base_fee_per_gas,
+                min_base_fee_per_gas,
+                allow_low_base_fee,
+            );
+
             let command = subcommand_parser(create_tx_subcommand);
+            if let Err(e) = check_tx_cost(
+                std::slice::from_ref(&command),
+                gas_limit,
+                max_base_fee_per_gas,
+                priority_fee_per_gas,
+            ) {
+                e.exit();
+            }
+
+            let keypair = load_keypair(&keypair_name);
+            let nonce = match nonce {
+                Some(nonce) => nonce,
+                None => {
+                    committed_nonce(
+                        &pchain_client,
+                        decode_address_or_exit("signer", &keypair.public_key),
+                    )
+                    .await
+                }
+            };
 
             let tx = SubmitTx {
                 is_v1: v1,
@@ -70,15 +498,100 @@ pub async fn match_submit_subcommand(tx_subcommand: Transaction, config: Config)
                 gas_limit,
                 max_base_fee_per_gas,
                 priority_fee_per_gas,
+                note: None,
+                co_signers: vec![],
             };
+            if let Err(e) = tx.validate_addresses() {
+                println!("{}", e);
+                utils::exit(1);
+            }
 
-            match tx.to_json_file(&destination.unwrap_or_else(|| "tx.json".to_string())) {
-                Ok(path) => println!(
-                    "{}",
-                    DisplayMsg::SuccessCreateFile(String::from("Transaction"), PathBuf::from(path))
-                ),
-                Err(e) => println!("{}", e),
+            if let Some(save) = save {
+                match tx.to_json_file(&save) {
+                    Ok(path) => println!(
+                        "{}",
+                        DisplayMsg::SuccessCreateFile(
+                            String::from("Transaction"),
+                            PathBuf::from(path)
+                        )
+                    ),
+                    Err(e) => {
+                        println!("{}", e);
+                        utils::exit(1);
+                    }
+                }
             }
+
+            sign_and_submit(&pchain_client, tx, keypair, hash_only).await;
+        }
+        Transaction::SuggestFees { blocks } => {
+            let response = timed(
+                "highest_committed_block",
+                pchain_client.highest_committed_block(),
+            )
+            .await;
+
+            let mut block_hash = match response {
+                Ok(HighestCommittedBlockResponse {
+                    block_hash: Some(block_hash),
+                }) => block_hash,
+                Err(e) => {
+                    println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
+                    utils::exit(1);
+                }
+                _ => {
+                    DisplayMsg::CannotFindLatestBlock.exit();
+                }
+            };
+
+            // walk back from the latest block through the parent hash recorded in each block's justify
+            let mut base_fees = Vec::new();
+            let mut priority_fees = Vec::new();
+            for _ in 0..blocks {
+                let block = match timed(
+                    "block_v2",
+                    pchain_client.block_v2(&BlockRequest { block_hash }),
+                )
+                .await
+                {
+                    Ok(BlockResponseV2 { block: Some(block) }) => block,
+                    Ok(_) => break,
+                    Err(e) => {
+                        println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
+                        utils::exit(1);
+                    }
+                };
+
+                let (base_fee, parent_hash) = match block {
+                    BlockV1ToV2::V1(block) => {
+                        priority_fees
+                            .extend(block.transactions.iter().map(|tx| tx.priority_fee_per_gas));
+                        (block.header.base_fee_per_gas, block.header.justify.block)
+                    }
+                    BlockV1ToV2::V2(block) => {
+                        priority_fees
+                            .extend(block.transactions.iter().map(|tx| tx.priority_fee_per_gas));
+                        (block.header.base_fee_per_gas, block.header.justify.block)
+                    }
+                };
+                base_fees.push(base_fee);
+                block_hash = parent_hash;
+            }
+
+            let (max_base_fee_per_gas, priority_fee_per_gas) =
+                match suggest_fees(&base_fees, priority_fees) {
+                    Some(fees) => fees,
+                    None => {
+                        DisplayMsg::CannotFindRelevantBlock.exit();
+                    }
+                };
+
+            display_json(json!({
+                "blocks_sampled": base_fees.len(),
+                "latest_base_fee_per_gas": base_fees[0],
+                "max_base_fee_per_gas": max_base_fee_per_gas,
+                "priority_fee_per_gas": priority_fee_per_gas,
+            }));
         }
         Transaction::Append {
             file,
@@ -88,7 +601,7 @@ pub async fn match_submit_subcommand(tx_subcommand: Transaction, config: Config)
                 Ok(tx_json) => tx_json,
                 Err(e) => {
                     println!("{}", e);
-                    std::process::exit(1);
+                    utils::exit(1);
                 }
             };
 
@@ -103,26 +616,676 @@ pub async fn match_submit_subcommand(tx_subcommand: Transaction, config: Config)
                 Err(e) => println!("{}", e),
             }
         }
+        Transaction::Merge { into, from } => {
+            let (mut target_tx, source_tx) = match (
+                SubmitTx::from_json_file(&into),
+                SubmitTx::from_json_file(&from),
+            ) {
+                (Ok(target_tx), Ok(source_tx)) => (target_tx, source_tx),
+                (Err(e), _) | (_, Err(e)) => {
+                    println!("{}", e);
+                    utils::exit(1);
+                }
+            };
+
+            if target_tx.is_v1 != source_tx.is_v1 {
+                println!(
+                    "{}",
+                    DisplayMsg::MismatchedTransactionVersion(
+                        PathBuf::from(into),
+                        PathBuf::from(from)
+                    )
+                );
+                utils::exit(1);
+            }
+
+            target_tx.commands.extend(source_tx.commands);
+
+            match target_tx.to_json_file(&into) {
+                Ok(path) => println!(
+                    "{}",
+                    DisplayMsg::SuccessUpdateFile(String::from("Transaction"), PathBuf::from(path))
+                ),
+                Err(e) => println!("{}", e),
+            }
+        }
+        Transaction::ExportBinary {
+            file,
+            keypair_name,
+            destination,
+        } => {
+            let submit_tx = match SubmitTx::from_json_file(&file) {
+                Ok(tx_json) => tx_json,
+                Err(e) => {
+                    println!("{}", e);
+                    utils::exit(1);
+                }
+            };
+            if let Err(e) = submit_tx.validate_addresses() {
+                println!("{}", e);
+                utils::exit(1);
+            }
+
+            let saved = submit_tx
+                .prepare_signed_tx(std::slice::from_ref(&keypair_name))
+                .and_then(|signed_tx| save_signed_tx(&signed_tx, destination));
+            if let Err(e) = saved {
+                println!("{}", e);
+                utils::exit(1);
+            }
+        }
+        Transaction::Show { file } => match SubmitTx::from_json_file(&file) {
+            Ok(tx) => display_json(serde_json::to_value(tx).unwrap()),
+            Err(e) => {
+                println!("{}", e);
+                utils::exit(1);
+            }
+        },
     };
 }
 
+// `read_call_result_schema` reads and validates a call result schema from a JSON file. Exits on error.
+//  # Arguments
+//  * `schema_file` - path to the schema JSON file
+//
+fn read_call_result_schema(schema_file: PathBuf) -> Value {
+    let schema = match read_file_to_utf8string(schema_file.clone()) {
+        Ok(result) => result,
+        Err(e) => {
+            println!(
+                "{}",
+                DisplayMsg::FailToOpenOrReadFile(String::from("schema json"), schema_file, e)
+            );
+            utils::exit(1);
+        }
+    };
+
+    let schema: Value = match serde_json::from_str(&schema) {
+        Ok(json_val) => json_val,
+        Err(e) => {
+            println!("{}", DisplayMsg::InvalidJson(e));
+            utils::exit(1);
+        }
+    };
+
+    if let Err(e) = validate_call_result_schema(&schema) {
+        println!("{}", e);
+        utils::exit(1);
+    }
+    schema
+}
+
+// `call_command_indices` returns the indices of the `Call` commands in a transaction.
+//  # Arguments
+//  * `commands` - commands of the transaction
+//
+fn call_command_indices(commands: &[Command]) -> Vec<usize> {
+    commands
+        .iter()
+        .enumerate()
+        .filter(|(_, command)| matches!(command, Command::Call(_)))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+// `call_return_values` returns the index and return value of each `Call` command receipt.
+//  # Arguments
+//  * `receipt` - receipt of the transaction
+//  * `call_indices` - indices of the `Call` commands, used for V1 receipts which do not carry the command type
+//
+fn call_return_values(receipt: ReceiptV1ToV2, call_indices: &[usize]) -> Vec<(usize, Vec<u8>)> {
+    match receipt {
+        ReceiptV1ToV2::V1(command_receipts) => command_receipts
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| call_indices.contains(index))
+            .map(|(index, command_receipt)| (index, command_receipt.return_values))
+            .collect(),
+        ReceiptV1ToV2::V2(receipt) => receipt
+            .command_receipts
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, command_receipt)| match command_receipt {
+                CommandReceiptV2::Call(call_receipt) => Some((index, call_receipt.return_value)),
+                _ => None,
+            })
+            .collect(),
+    }
+}
+
+/// Name and value of each field of a decoded call result, as returned by `parse_call_result_from_schema`.
+type CallResultFields = Vec<(String, String)>;
+
+// `decode_call_return_values` decodes the return value of each `Call` command receipt with a call result schema.
+//  # Arguments
+//  * `receipt` - receipt of the transaction
+//  * `call_indices` - indices of the `Call` commands in the transaction
+//  * `schema` - call result schema, in the format of `parse call-result --schema-file`
+//
+fn decode_call_return_values(
+    receipt: ReceiptV1ToV2,
+    call_indices: &[usize],
+    schema: &Value,
+) -> Result<Vec<(usize, CallResultFields)>, DisplayMsg> {
+    call_return_values(receipt, call_indices)
+        .into_iter()
+        .map(|(index, return_value)| {
+            parse_call_result_from_schema(&return_value, schema)
+                .map(|result| (index, result))
+                .map_err(|e| DisplayMsg::FailToParseCallResult(e.to_string()))
+        })
+        .collect()
+}
+
+// `wait_for_receipt` polls the receipt of a transaction until it is committed. Exits if it is not
+// committed within `timeout` seconds.
+//  # Arguments
+//  * `pchain_client` - client to Fullnode RPC
+//  * `tx_hash` - hash of the transaction
+//  * `timeout` - seconds to wait before giving up
+//
+async fn wait_for_receipt(
+    pchain_client: &Client,
+    tx_hash: Sha256Hash,
+    timeout: u64,
+) -> ReceiptResponseV2 {
+    let started_at = std::time::Instant::now();
+    loop {
+        match timed(
+            "receipt_v2",
+            pchain_client.receipt_v2(&ReceiptRequest {
+                transaction_hash: tx_hash,
+            }),
+        )
+        .await
+        {
+            Ok(response) if response.receipt.is_some() => return response,
+            Ok(_) => {}
+            Err(e) => {
+                println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
+                utils::exit(1);
+            }
+        };
+
+        let elapsed = started_at.elapsed();
+        if elapsed >= std::time::Duration::from_secs(timeout) {
+            println!(
+                "{}",
+                DisplayMsg::ReceiptWaitTimeout(base64url::encode(tx_hash), timeout)
+            );
+            utils::exit(1);
+        }
+
+        tokio::time::sleep(
+            RECEIPT_POLL_INTERVAL.min(std::time::Duration::from_secs(timeout) - elapsed),
+        )
+        .await;
+    }
+}
+
+// `sign_and_submit` signs a transaction with a keypair, submits it to ParallelChain and displays the response.
+//  It displays the error and exits the program if the transaction cannot be signed.
+//  # Arguments
+//  * `pchain_client` - client to Fullnode RPC
+//  * `tx` - the transaction to submit
+//  * `keypair` - the keypair which signs the transaction
+//  * `hash_only` - only display the transaction hash on success
+//
+async fn sign_and_submit(
+    pchain_client: &Client,
+    tx: SubmitTx,
+    keypair: KeypairJSON,
+    hash_only: bool,
+) {
+    let signed_tx = match tx.sign_with_keypair(keypair) {
+        Ok(tx) => tx,
+        Err(e) => {
+            println!("{}", e);
+            utils::exit(1);
+        }
+    };
+
+    let response = timed(
+        "submit_transaction_v2",
+        pchain_client.submit_transaction_v2(&signed_tx),
+    )
+    .await
+    .map_err(ErrorResponse::from);
+
+    display_beautified_rpc_result(ClientResponse::SubmitTx(response, signed_tx, hash_only))
+}
+
+// `check_signer` checks that a keypair has the expected address before it signs a transaction. Exits otherwise.
+//  # Arguments
+//  * `keypair` - the keypair which signs the transaction
+//  * `expected_signer` - expected address of the keypair
+//
+fn check_signer(keypair: &KeypairJSON, expected_signer: PublicAddress) {
+    if decode_address_or_exit("public key", &keypair.public_key) != expected_signer {
+        println!(
+            "{}",
+            DisplayMsg::UnexpectedSigner(
+                keypair.name.clone(),
+                keypair.public_key.clone(),
+                base64url::encode(expected_signer)
+            )
+        );
+        utils::exit(1);
+    }
+}
+
+// `load_keypair` reads a keypair from the keypair file, which asks for the password once. It displays the
+//  error and exits the program if the keypair does not exist.
+//  # Arguments
+//  * `keypair_name` - name of the keypair
+//
+fn load_keypair(keypair_name: &str) -> KeypairJSON {
+    match get_keypair_from_json(get_keypair_path(), keypair_name) {
+        Ok(Some(keypair)) => keypair,
+        Ok(None) => {
+            println!("{}", DisplayMsg::KeypairNotFound(keypair_name.to_string()));
+            utils::exit(1);
+        }
+        Err(e) => {
+            println!("{}", e);
+            utils::exit(1);
+        }
+    }
+}
+
+// `committed_nonce` returns the nonce of an account in the latest committed world state.
+//  # Arguments
+//  * `pchain_client` - client to Fullnode RPC
+//  * `address` - address of the account
+//
+async fn committed_nonce(pchain_client: &Client, address: PublicAddress) -> u64 {
+    let response = timed(
+        "state_v2",
+        pchain_client.state_v2(&StateRequest {
+            accounts: HashSet::from([address]),
+            include_contract: false,
+            storage_keys: HashMap::from([]),
+        }),
+    )
+    .await;
+
+    match response {
+        Ok(StateResponseV2::Ok { accounts, .. }) => match accounts.into_values().next() {
+            Some(Account::WithoutContract(account)) => account.nonce,
+            Some(Account::WithContract(account)) => account.nonce,
+            None => {
+                DisplayMsg::CannotFindRelevantState.exit();
+            }
+        },
+        Ok(StateResponseV2::Error { error }) => {
+            println!(
+                "{}",
+                DisplayMsg::RespnoseWithHTTPError(format!("{:?}", error))
+            );
+            utils::exit(1);
+        }
+        Err(e) => {
+            println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
+            utils::exit(1);
+        }
+    }
+}
+
+// `network_uses_v1` tells whether the network takes TransactionV1, by the version of the header of the
+//  latest committed block. A Fullnode which serves only V1 may not serve the V2 header endpoint, so the V1
+//  endpoint is asked when it fails.
+//  # Arguments
+//  * `pchain_client` - client to Fullnode RPC
+//
+async fn network_uses_v1(pchain_client: &Client) -> bool {
+    let response = timed(
+        "highest_committed_block",
+        pchain_client.highest_committed_block(),
+    )
+    .await;
+
+    let block_hash = match response {
+        Ok(HighestCommittedBlockResponse {
+            block_hash: Some(block_hash),
+        }) => block_hash,
+        Err(e) => {
+            println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
+            utils::exit(1);
+        }
+        _ => {
+            DisplayMsg::CannotFindLatestBlock.exit();
+        }
+    };
+
+    let response = timed(
+        "block_header_v2",
+        pchain_client.block_header_v2(&BlockHeaderRequest { block_hash }),
+    )
+    .await;
+
+    match response {
+        Ok(BlockHeaderResponseV2 {
+            block_header: Some(block_header),
+        }) => header_uses_v1(&block_header),
+        Err(_) => {
+            let response = timed(
+                "block_header",
+                pchain_client.block_header(&BlockHeaderRequest { block_hash }),
+            )
+            .await;
+
+            match response {
+                Ok(BlockHeaderResponse {
+                    block_header: Some(block_header),
+                }) => header_uses_v1(&BlockHeaderV1ToV2::V1(block_header)),
+                Err(e) => {
+                    println!("{}", DisplayMsg::RespnoseWithHTTPError(e));
+                    utils::exit(1);
+                }
+                _ => {
+                    DisplayMsg::CannotFindLatestBlock.exit();
+                }
+            }
+        }
+        _ => {
+            DisplayMsg::CannotFindLatestBlock.exit();
+        }
+    }
+}
+
+// `header_uses_v1` tells whether a block with this header takes TransactionV1.
+//  # Arguments
+//  * `block_header` - header of a block
+//
+fn header_uses_v1(block_header: &BlockHeaderV1ToV2) -> bool {
+    matches!(block_header, BlockHeaderV1ToV2::V1(_))
+}
+
+// `suggest_fees` suggests the max base fee per gas and priority fee per gas of a transaction, from
+//  the fees in recent blocks. The max base fee is the highest recent base fee plus a margin of 12.5%,
+//  and the priority fee is the median priority fee of the transactions in those blocks.
+//  # Arguments
+//  * `base_fees` - base fee per gas of the recent blocks
+//  * `priority_fees` - priority fee per gas of the transactions in the recent blocks
+//  # Return
+//  None if no block is sampled
+fn suggest_fees(base_fees: &[u64], mut priority_fees: Vec<u64>) -> Option<(u64, u64)> {
+    let highest_base_fee = *base_fees.iter().max()?;
+    let max_base_fee_per_gas = highest_base_fee.saturating_add(highest_base_fee.div_ceil(8));
+
+    priority_fees.sort_unstable();
+    let priority_fee_per_gas = priority_fees
+        .get(priority_fees.len() / 2)
+        .copied()
+        .unwrap_or(0);
+
+    Some((max_base_fee_per_gas, priority_fee_per_gas))
+}
+
+// `gas_or_default` returns a gas parameter given on the command line, or else its default saved by
+//  `config set-defaults`. It exits the program if neither is set.
+//  # Arguments
+//  * `value` - value given on the command line
+//  * `default` - default saved in the config file
+//  * `arg_name` - name of the command line option
+//
+fn gas_or_default(value: Option<u64>, default: Option<u64>, arg_name: &str) -> u64 {
+    match value.or(default) {
+        Some(value) => value,
+        None => {
+            println!("{}", DisplayMsg::MissingGasParameter(arg_name.to_string()));
+            utils::exit(1);
+        }
+    }
+}
+
+// `check_base_fee` refuses to proceed if `max_base_fee_per_gas` is below the minimum, since such
+//  transaction can never be included in a block. Only a warning is displayed if `allow_low_base_fee` is set.
+//  # Arguments
+//  * `max_base_fee_per_gas` - max base fee per gas of the transaction
+//  * `min_base_fee_per_gas` - minimum accepted max base fee per gas
+//  * `allow_low_base_fee` - proceed even if the check fails
+//
+fn check_base_fee(max_base_fee_per_gas: u64, min_base_fee_per_gas: u64, allow_low_base_fee: bool) {
+    if max_base_fee_per_gas >= min_base_fee_per_gas {
+        return;
+    }
+
+    if !allow_low_base_fee {
+        println!(
+            "{}",
+            DisplayMsg::BaseFeeBelowMinimum(max_base_fee_per_gas, min_base_fee_per_gas)
+        );
+        utils::exit(1);
+    }
+    warn(DisplayMsg::ForcedBaseFeeBelowMinimum(
+        max_base_fee_per_gas,
+        min_base_fee_per_gas,
+    ));
+}
+
+// `check_tx_cost` returns an error if the maximum cost of a transaction, i.e. the amount it spends plus
+//  gas_limit * (max_base_fee_per_gas + priority_fee_per_gas), does not fit in 64 bits. Balances are 64-bit,
+//  so such a transaction could never be paid for.
+//  # Arguments
+//  * `commands` - the commands of the transaction
+//  * `gas_limit` - gas limit of the transaction
+//  * `max_base_fee_per_gas` - maximum base fee per gas of the transaction
+//  * `priority_fee_per_gas` - priority fee per gas of the transaction
+//
+fn check_tx_cost(
+    commands: &[TxCommand],
+    gas_limit: u64,
+    max_base_fee_per_gas: u64,
+    priority_fee_per_gas: u64,
+) -> Result<(), DisplayMsg> {
+    let max_fee = max_base_fee_per_gas
+        .checked_add(priority_fee_per_gas)
+        .and_then(|fee_per_gas| gas_limit.checked_mul(fee_per_gas))
+        .ok_or(DisplayMsg::MaxFeeOverflow(
+            gas_limit,
+            max_base_fee_per_gas,
+            priority_fee_per_gas,
+        ))?;
+
+    let amount = commands.iter().try_fold(0u64, |total, command| {
+        total.checked_add(command.spent_amount())
+    });
+    match amount {
+        Some(amount) if amount.checked_add(max_fee).is_some() => Ok(()),
+        amount => Err(DisplayMsg::TxCostOverflow(
+            amount.unwrap_or(u64::MAX),
+            max_fee,
+        )),
+    }
+}
+
+// `submitted_tx_hashes` reads the hashes in the ledger of transactions submitted with `--idempotent`. The ledger
+//  is empty if its file does not exist. It displays the error and exits the program if the file cannot be read.
+//
+fn submitted_tx_hashes() -> HashSet<String> {
+    let path = get_submitted_ledger_path();
+    if !path.exists() {
+        return HashSet::new();
+    }
+
+    match read_file_to_utf8string(path.clone()) {
+        Ok(ledger) => ledger.lines().map(|hash| hash.trim().to_string()).collect(),
+        Err(e) => {
+            println!(
+                "{}",
+                DisplayMsg::FailToOpenOrReadFile(
+                    String::from("submitted transactions ledger"),
+                    path,
+                    e
+                )
+            );
+            utils::exit(1);
+        }
+    }
+}
+
+// `record_submitted_tx_hash` appends the hash of an accepted transaction to the ledger of submitted transactions.
+//  The transaction is already submitted, so a failure only displays a warning.
+//  # Arguments
+//  * `tx_hash` - Base64url encoded transaction hash
+//
+fn record_submitted_tx_hash(tx_hash: &str) {
+    use std::io::Write;
+
+    let path = get_submitted_ledger_path();
+    let result = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut ledger| writeln!(ledger, "{}", tx_hash));
+    if let Err(e) = result {
+        warn(DisplayMsg::FailToWriteFile(
+            String::from("submitted transactions ledger"),
+            path,
+            e.to_string(),
+        ));
+    }
+}
+
+// `explain_submit_tx` prints a plain-English description of a transaction about to be submitted: its signer,
+//  fees and commands. Nothing is signed or submitted.
+//  # Arguments
+//  * `file` - path to the transaction file
+//  * `keypair` - the keypair to sign the transaction, as loaded for signing it
+//  * `submit_tx` - the transaction read from the file
+//
+fn explain_submit_tx(file: &str, keypair: &KeypairJSON, submit_tx: &SubmitTx) {
+    println!(
+        "Transaction file {} ({}) would be signed by keypair <{}> with address {}, using nonce {}.",
+        file,
+        if submit_tx.is_v1 { "V1" } else { "V2" },
+        keypair.name,
+        keypair.public_key,
+        submit_tx.nonce
+    );
+    if let Some(note) = &submit_tx.note {
+        println!("Note: {}", note);
+    }
+
+    println!("It runs {} command(s) in order:", submit_tx.commands.len());
+    for (index, command) in submit_tx.commands.iter().enumerate() {
+        println!("  {}. {}", index, command.describe());
+    }
+
+    let fee_per_gas = submit_tx
+        .max_base_fee_per_gas
+        .saturating_add(submit_tx.priority_fee_per_gas);
+    let spent_amount = submit_tx.commands.iter().fold(0u64, |total, command| {
+        total.saturating_add(command.spent_amount())
+    });
+    println!(
+        "The commands spend {} Grays from the balance of the signer.",
+        spent_amount
+    );
+    println!(
+        "Gas fees are at most {} Grays: gas limit {} x (max base fee {} + priority fee {}). Unused gas is not charged.",
+        submit_tx.gas_limit.saturating_mul(fee_per_gas),
+        submit_tx.gas_limit,
+        submit_tx.max_base_fee_per_gas,
+        submit_tx.priority_fee_per_gas
+    );
+    println!("Nothing is submitted. Run the command again without `--explain` to submit the transaction.");
+}
+
+// `save_created_tx` saves a newly created transaction to file. The transaction is saved as JSON, or
+//  signed and saved in borsh-serialized form if a keypair is provided.
+//  # Arguments
+//  * `tx` - the created transaction
+//  * `destination` - path of the output file. Defaults to `tx.json`, or `tx.signed` for signed transaction
+//  * `signer` - keypair to sign the transaction with
+//
+fn save_created_tx(
+    tx: SubmitTx,
+    destination: Option<String>,
+    signer: Option<KeypairJSON>,
+) -> Result<(), DisplayMsg> {
+    if let Some(keypair) = signer {
+        let signed_tx = tx.sign_with_keypair(keypair)?;
+        return save_signed_tx(&signed_tx, destination);
+    }
+
+    let path = tx.to_json_file(&destination.unwrap_or_else(|| "tx.json".to_string()))?;
+    println!(
+        "{}",
+        DisplayMsg::SuccessCreateFile(String::from("Transaction"), PathBuf::from(path))
+    );
+    Ok(())
+}
+
+// `save_signed_tx` saves a signed transaction to file in borsh-serialized form.
+//  # Arguments
+//  * `signed_tx` - the signed transaction
+//  * `destination` - path of the output file. Defaults to `tx.signed`
+//
+fn save_signed_tx(
+    signed_tx: &TransactionV1OrV2,
+    destination: Option<String>,
+) -> Result<(), DisplayMsg> {
+    let path = PathBuf::from(destination.unwrap_or_else(|| "tx.signed".to_string()));
+    let path = write_file(path.clone(), &signed_tx.serialize())
+        .map_err(|e| DisplayMsg::FailToWriteFile(String::from("Signed transaction"), path, e))?;
+    println!(
+        "{}",
+        DisplayMsg::SuccessCreateFile(String::from("Signed transaction"), PathBuf::from(path))
+    );
+    Ok(())
+}
+
+// `call_argument_files_in_dir` lists the JSON files in a directory of call arguments, sorted by file name.
+//  It displays the error and exits the program if the directory cannot be read.
+//  # Arguments
+//  * `dir` - relative or absolute path to the directory
+//
+fn call_argument_files_in_dir(dir: &str) -> Vec<PathBuf> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            println!(
+                "{}",
+                DisplayMsg::FailToOpenOrReadFile(
+                    String::from("call argument directory"),
+                    PathBuf::from(dir),
+                    e.to_string()
+                )
+            );
+            utils::exit(1);
+        }
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension() == Some(OsStr::new("json")))
+        .collect();
+    files.sort();
+
+    if files.is_empty() {
+        println!(
+            "{}",
+            DisplayMsg::IncorrectFilePath(
+                String::from("call argument directory"),
+                PathBuf::from(dir),
+                String::from("No JSON file is found in the directory.")
+            )
+        );
+        utils::exit(1);
+    }
+    files
+}
+
 fn subcommand_parser(tx_subcommand: CreateTx) -> TxCommand {
     match tx_subcommand {
         CreateTx::Transfer {
             recipient: target_address,
             amount,
         } => {
-            if let Err(e) = base64url_to_public_address(&target_address) {
-                println!(
-                    "{}",
-                    DisplayMsg::FailToDecodeBase64Address(
-                        String::from("target"),
-                        target_address,
-                        e.to_string()
-                    )
-                );
-                std::process::exit(1);
-            };
+            let target_address = resolve_address_or_exit(&target_address);
+            decode_address_or_exit("target", &target_address);
             TxCommand::Transfer {
                 recipient: target_address,
                 amount,
@@ -131,12 +1294,27 @@ fn subcommand_parser(tx_subcommand: CreateTx) -> TxCommand {
         CreateTx::Deploy {
             contract_code,
             cbi_version,
+            cbi_check,
+            force,
         } => {
+            if cbi_check && !SUPPORTED_CBI_VERSIONS.contains(&cbi_version) {
+                let supported: Vec<String> = SUPPORTED_CBI_VERSIONS
+                    .iter()
+                    .map(|version| version.to_string())
+                    .collect();
+                let warning = DisplayMsg::UnsupportedCBIVersion(cbi_version, supported.join(", "));
+                if !force {
+                    println!("{}", warning);
+                    utils::exit(1);
+                }
+                warn(warning);
+            }
+
             let contract_path = match check_contract_exist(&contract_code) {
                 Ok(path) => path,
                 Err(e) => {
                     println!("{}", e);
-                    std::process::exit(1);
+                    utils::exit(1);
                 }
             };
             TxCommand::Deploy {
@@ -149,72 +1327,19 @@ fn subcommand_parser(tx_subcommand: CreateTx) -> TxCommand {
             method,
             arguments,
             amount,
+            verbose: _,
         } => {
-            if let Err(e) = base64url_to_public_address(&target_address) {
-                println!(
-                    "{}",
-                    DisplayMsg::FailToDecodeBase64Address(
-                        String::from("target"),
-                        target_address,
-                        e.to_string()
-                    )
-                );
-                std::process::exit(1);
-            };
+            let target_address = resolve_address_or_exit(&target_address);
+            decode_address_or_exit("target", &target_address);
 
-            let arguments = match arguments {
-                Some(path) => {
-                    let path_to_json = PathBuf::from(&path);
-                    let arguments_json = match read_file_to_utf8string(path_to_json.clone()) {
-                        Ok(result) => result,
-                        Err(e) => {
-                            println!(
-                                "{}",
-                                DisplayMsg::FailToOpenOrReadFile(
-                                    String::from("call argment json"),
-                                    path_to_json,
-                                    e
-                                )
-                            );
-                            std::process::exit(1);
-                        }
-                    };
-
-                    let arguments: Value = match serde_json::from_str(&arguments_json) {
-                        Ok(json_val) => json_val,
-                        Err(e) => {
-                            println!("{}", DisplayMsg::InvalidJson(e));
-                            std::process::exit(1);
-                        }
-                    };
-
-                    let arguments = match parse_json_arguments(&arguments).and_then(|json_arr| {
-                        // Check if it can be parsed into call arguments
-                        let call_arguments = call_arguments_from_json_array(&json_arr)?;
-                        if call_arguments.is_empty() {
-                            Ok(Vec::new())
-                        } else {
-                            Ok(json_arr)
-                        }
-                    }) {
-                        Ok(values) => values,
-                        Err(e) => {
-                            println!(
-                                "{}",
-                                DisplayMsg::FailToDecodeJson(
-                                    String::from("call argument"),
-                                    path_to_json,
-                                    e.to_string()
-                                )
-                            );
-                            std::process::exit(1);
-                        }
-                    };
-
-                    (!arguments.is_empty()).then_some(arguments)
-                }
-                None => None,
-            };
+            let arguments =
+                arguments.and_then(|path| match read_call_arguments(Path::new(&path)) {
+                    Ok(arguments) => arguments,
+                    Err(e) => {
+                        println!("{}", e);
+                        utils::exit(1);
+                    }
+                });
 
             TxCommand::Call {
                 target: target_address,
@@ -231,17 +1356,8 @@ fn subcommand_parser(tx_subcommand: CreateTx) -> TxCommand {
                 balance,
                 auto_stake_rewards,
             } => {
-                if let Err(e) = base64url_to_public_address(&operator) {
-                    println!(
-                        "{}",
-                        DisplayMsg::FailToDecodeBase64Address(
-                            String::from("operator"),
-                            operator,
-                            e.to_string()
-                        )
-                    );
-                    std::process::exit(1);
-                };
+                let operator = resolve_address_or_exit(&operator);
+                decode_address_or_exit("operator", &operator);
                 TxCommand::CreateDeposit {
                     operator,
                     balance,
@@ -252,51 +1368,24 @@ fn subcommand_parser(tx_subcommand: CreateTx) -> TxCommand {
                 operator,
                 auto_stake_rewards,
             } => {
-                if let Err(e) = base64url_to_public_address(&operator) {
-                    println!(
-                        "{}",
-                        DisplayMsg::FailToDecodeBase64Address(
-                            String::from("operator"),
-                            operator,
-                            e.to_string()
-                        )
-                    );
-                    std::process::exit(1);
-                };
+                let operator = resolve_address_or_exit(&operator);
+                decode_address_or_exit("operator", &operator);
                 TxCommand::SetDepositSettings {
                     operator,
                     auto_stake_rewards,
                 }
             }
             DepositTx::TopUp { operator, amount } => {
-                if let Err(e) = base64url_to_public_address(&operator) {
-                    println!(
-                        "{}",
-                        DisplayMsg::FailToDecodeBase64Address(
-                            String::from("operator"),
-                            operator,
-                            e.to_string()
-                        )
-                    );
-                    std::process::exit(1);
-                };
+                let operator = resolve_address_or_exit(&operator);
+                decode_address_or_exit("operator", &operator);
                 TxCommand::TopUpDeposit { operator, amount }
             }
             DepositTx::Withdraw {
                 operator,
                 max_amount,
             } => {
-                if let Err(e) = base64url_to_public_address(&operator) {
-                    println!(
-                        "{}",
-                        DisplayMsg::FailToDecodeBase64Address(
-                            String::from("operator"),
-                            operator,
-                            e.to_string()
-                        )
-                    );
-                    std::process::exit(1);
-                };
+                let operator = resolve_address_or_exit(&operator);
+                decode_address_or_exit("operator", &operator);
                 TxCommand::WithdrawDeposit {
                     operator,
                     max_amount,
@@ -310,17 +1399,8 @@ fn subcommand_parser(tx_subcommand: CreateTx) -> TxCommand {
                 operator,
                 max_amount,
             } => {
-                if let Err(e) = base64url_to_public_address(&operator) {
-                    println!(
-                        "{}",
-                        DisplayMsg::FailToDecodeBase64Address(
-                            String::from("operator"),
-                            operator,
-                            e.to_string()
-                        )
-                    );
-                    std::process::exit(1);
-                };
+                let operator = resolve_address_or_exit(&operator);
+                decode_address_or_exit("operator", &operator);
                 TxCommand::StakeDeposit {
                     operator,
                     max_amount,
@@ -330,17 +1410,8 @@ fn subcommand_parser(tx_subcommand: CreateTx) -> TxCommand {
                 operator,
                 max_amount,
             } => {
-                if let Err(e) = base64url_to_public_address(&operator) {
-                    println!(
-                        "{}",
-                        DisplayMsg::FailToDecodeBase64Address(
-                            String::from("operator"),
-                            operator,
-                            e.to_string()
-                        )
-                    );
-                    std::process::exit(1);
-                };
+                let operator = resolve_address_or_exit(&operator);
+                decode_address_or_exit("operator", &operator);
                 TxCommand::UnstakeDeposit {
                     operator,
                     max_amount,
@@ -356,3 +1427,138 @@ fn subcommand_parser(tx_subcommand: CreateTx) -> TxCommand {
         },
     }
 }
+
+// `read_call_arguments` reads the call arguments of a contract call from a JSON file.
+//  # Arguments
+//  * `path` - relative or absolute path to the JSON file of call arguments
+//
+fn read_call_arguments(path: &Path) -> Result<Option<Vec<Value>>, DisplayMsg> {
+    let path_to_json = path.to_path_buf();
+    let arguments_json = read_file_to_utf8string(path_to_json.clone()).map_err(|e| {
+        DisplayMsg::FailToOpenOrReadFile(String::from("call argment json"), path_to_json.clone(), e)
+    })?;
+
+    let arguments: Value =
+        serde_json::from_str(&arguments_json).map_err(DisplayMsg::InvalidJson)?;
+
+    let arguments = parse_json_arguments(&arguments)
+        .and_then(|json_arr| {
+            // Check if it can be parsed into call arguments
+            let call_arguments = call_arguments_from_json_array(&json_arr)?;
+            if call_arguments.is_empty() {
+                Ok(Vec::new())
+            } else {
+                Ok(json_arr)
+            }
+        })
+        .map_err(|e| {
+            DisplayMsg::FailToDecodeJson(String::from("call argument"), path_to_json, e.to_string())
+        })?;
+
+    Ok((!arguments.is_empty()).then_some(arguments))
+}
+
+/// CBI versions supported by ParallelChain runtime at the time of this release
+const SUPPORTED_CBI_VERSIONS: [u32; 1] = [0];
+
+/// Interval between polls of the receipt of a submitted transaction
+const RECEIPT_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+#[cfg(test)]
+mod test {
+    use super::{decode_call_return_values, header_uses_v1, suggest_fees};
+    use hotstuff_rs::types::QuorumCertificate;
+    use pchain_types::blockchain::{
+        BlockHeaderV1, BlockHeaderV2, CallReceipt, CommandReceiptV2, ExitCodeV2, ReceiptV2,
+        TransferReceipt,
+    };
+    use pchain_types::rpc::{BlockHeaderV1ToV2, ReceiptV1ToV2};
+
+    #[test]
+    fn test_header_uses_v1() {
+        let header_v1 = BlockHeaderV1 {
+            chain_id: 0,
+            hash: [1; 32],
+            height: 1,
+            justify: QuorumCertificate::genesis_qc(),
+            data_hash: [0; 32],
+            version: 0,
+            timestamp: 0,
+            base_fee_per_gas: 8,
+            gas_used: 0,
+            txs_hash: [0; 32],
+            state_hash: [0; 32],
+            receipts_hash: [0; 32],
+            proposer: [0; 32],
+            logs_bloom: [0; 256].into(),
+        };
+        let header_v2 = BlockHeaderV2 {
+            chain_id: header_v1.chain_id,
+            hash: header_v1.hash,
+            height: header_v1.height,
+            justify: header_v1.justify.clone(),
+            data_hash: header_v1.data_hash,
+            version: header_v1.version,
+            timestamp: header_v1.timestamp,
+            base_fee_per_gas: header_v1.base_fee_per_gas,
+            gas_used: header_v1.gas_used,
+            txns_hash: header_v1.txs_hash,
+            state_hash: header_v1.state_hash,
+            receipts_hash: header_v1.receipts_hash,
+            proposer: header_v1.proposer,
+            logs_bloom: header_v1.logs_bloom.clone(),
+        };
+
+        assert!(header_uses_v1(&BlockHeaderV1ToV2::V1(header_v1)));
+        assert!(!header_uses_v1(&BlockHeaderV1ToV2::V2(header_v2)));
+    }
+
+    #[test]
+    fn test_suggest_fees() {
+        assert_eq!(suggest_fees(&[], vec![]), None);
+        assert_eq!(suggest_fees(&[8, 16, 12], vec![]), Some((18, 0)));
+        assert_eq!(suggest_fees(&[8], vec![5, 1, 3]), Some((9, 3)));
+    }
+
+    #[test]
+    fn test_decode_call_return_values() {
+        let receipt = |return_value: Vec<u8>| {
+            ReceiptV1ToV2::V2(ReceiptV2 {
+                gas_used: 0,
+                exit_code: ExitCodeV2::Ok,
+                command_receipts: vec![
+                    CommandReceiptV2::Transfer(TransferReceipt {
+                        exit_code: ExitCodeV2::Ok,
+                        gas_used: 0,
+                    }),
+                    CommandReceiptV2::Call(CallReceipt {
+                        exit_code: ExitCodeV2::Ok,
+                        gas_used: 0,
+                        logs: Vec::new(),
+                        return_value,
+                    }),
+                ],
+            })
+        };
+        let schema = serde_json::json!([
+            {"argument_name": "count", "argument_type": "u32"},
+            {"argument_name": "flag", "argument_type": "bool"},
+        ]);
+
+        let mut return_value = 7u32.to_le_bytes().to_vec();
+        return_value.push(1);
+        let decoded = decode_call_return_values(receipt(return_value), &[1], &schema).unwrap();
+        assert_eq!(
+            decoded,
+            vec![(
+                1,
+                vec![
+                    (String::from("count"), String::from("7")),
+                    (String::from("flag"), String::from("true"))
+                ]
+            )]
+        );
+
+        assert!(decode_call_return_values(receipt(vec![7]), &[1], &schema).is_err());
+    }
+}
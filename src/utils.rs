@@ -3,6 +3,7 @@
     Licensed under the Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
 */
 
+use crate::command::OutputEncoding;
 use crate::config::get_hash_path;
 use crate::display_msg::DisplayMsg;
 use age::secrecy::Secret;
@@ -11,8 +12,21 @@ use rand::{distributions::Alphanumeric, rngs::OsRng, thread_rng, Rng, RngCore};
 use std::{
     io::{Read, Write},
     path::PathBuf,
+    sync::OnceLock,
 };
 
+/// Whether `login` reads the password from stdin, set by `--password-stdin`.
+static PASSWORD_FROM_STDIN: OnceLock<bool> = OnceLock::new();
+
+/// Password read from stdin, kept for later logins in the same command.
+static STDIN_PASSWORD: OnceLock<String> = OnceLock::new();
+
+/// Whether warnings are fatal, set by `--abort-on-warning`.
+static ABORT_ON_WARNING: OnceLock<bool> = OnceLock::new();
+
+/// Whether the program prints a final status line on exit, set by `--status-line`.
+static STATUS_LINE: OnceLock<bool> = OnceLock::new();
+
 // `login` read password from console to compute encoded string for keypair file decryption.
 // This function computes the argon2 hash of the encoded password and verify with the record saved before.
 //  # Arguments
@@ -35,10 +49,14 @@ pub(crate) fn login() -> Result<String, DisplayMsg> {
         return Ok(encoded_empty_pasword);
     }
 
-    let password = rpassword::prompt_password("password: ")
-        .unwrap()
-        .trim()
-        .to_string();
+    let password = if PASSWORD_FROM_STDIN.get() == Some(&true) {
+        password_from_stdin()?
+    } else {
+        rpassword::prompt_password("password: ")
+            .unwrap()
+            .trim()
+            .to_string()
+    };
     let encoded_password = base64url::encode(password);
 
     match argon2::verify_raw(encoded_password.as_bytes(), &salt, &hash, &argon2_config) {
@@ -48,6 +66,84 @@ pub(crate) fn login() -> Result<String, DisplayMsg> {
     }
 }
 
+// `read_password_from_stdin` makes `login` read the password as one line from stdin instead of prompting
+//  for it, e.g. when the password is piped from a secrets manager.
+//  # Arguments
+//  *
+pub(crate) fn read_password_from_stdin() {
+    let _ = PASSWORD_FROM_STDIN.set(true);
+}
+
+// `abort_on_warning` makes every later `warn` exit the program instead of proceeding.
+//  # Arguments
+//  *
+pub(crate) fn abort_on_warning() {
+    let _ = ABORT_ON_WARNING.set(true);
+}
+
+// `print_status_line` makes `exit` print a final status line on stderr.
+//  # Arguments
+//  *
+pub(crate) fn print_status_line() {
+    let _ = STATUS_LINE.set(true);
+}
+
+// `exit` exits the program with the given code. Every command exits through here, so that `--status-line`
+//  ends the output with a status line derived from the code.
+//  # Arguments
+//  * `code` - exit code of the program
+//
+pub(crate) fn exit(code: i32) -> ! {
+    if STATUS_LINE.get().copied().unwrap_or(false) {
+        if code == 0 {
+            eprintln!("{}", DisplayMsg::StatusOk);
+        } else {
+            eprintln!("{}", DisplayMsg::StatusError(code));
+        }
+    }
+    std::process::exit(code)
+}
+
+// `warn` prints a warning which does not stop the command by itself. Under `--abort-on-warning`, the
+//  program exits instead.
+//  # Arguments
+//  * `warning` - the warning to display
+//
+pub(crate) fn warn(warning: DisplayMsg) {
+    println!("{}", warning);
+    if ABORT_ON_WARNING.get().copied().unwrap_or(false) {
+        println!("{}", DisplayMsg::AbortedOnWarning);
+        exit(1);
+    }
+}
+
+// `password_from_stdin` reads the password from the first line of stdin. The line is read once and kept,
+//  since a command may need to login more than once.
+//  # Arguments
+//  *
+fn password_from_stdin() -> Result<String, DisplayMsg> {
+    if let Some(password) = STDIN_PASSWORD.get() {
+        return Ok(password.clone());
+    }
+
+    let line = read_line_from_stdin()?;
+    Ok(STDIN_PASSWORD.get_or_init(|| line).clone())
+}
+
+// `read_line_from_stdin` reads the next line of stdin as a password, with surrounding whitespace trimmed.
+//  # Arguments
+//  *
+fn read_line_from_stdin() -> Result<String, DisplayMsg> {
+    let mut line = String::new();
+    match std::io::stdin().read_line(&mut line) {
+        Ok(0) => Err(DisplayMsg::FailToReadPasswordFromStdin(String::from(
+            "Stdin is empty.",
+        ))),
+        Ok(_) => Ok(line.trim().to_string()),
+        Err(e) => Err(DisplayMsg::FailToReadPasswordFromStdin(e.to_string())),
+    }
+}
+
 // `setup_password` get user input password and hashed with argon2. The salt used in here is 32 random bytes.
 // The salt and output hash would be concatenated and save to a file. The password hash is used to verfy user
 // password later.
@@ -55,22 +151,204 @@ pub(crate) fn login() -> Result<String, DisplayMsg> {
 //  *
 pub(crate) fn setup_password() -> Result<(), DisplayMsg> {
     println!("First time to use ParallelChain Client CLI. Please setup password to protect you keypairs.");
-    let password1 =
-        rpassword::prompt_password("Your password: (press enter to skip password protection.)")
-            .unwrap()
-            .trim()
-            .to_string();
+    let password = prompt_new_password(
+        "Your password: (press enter to skip password protection.)",
+        "Re-enter your password: ",
+    )?;
+
+    let data = password_hash(&base64url::encode(password))?;
+    match write_file(get_hash_path(), &data) {
+        Ok(_) => {
+            println!("{}", DisplayMsg::SuccessSetupPassword);
+            Ok(())
+        }
+        Err(e) => Err(DisplayMsg::FailToWriteFile(
+            String::from("hash file"),
+            get_hash_path(),
+            e,
+        )),
+    }
+}
+
+// `change_password` verifies the current password, then re-encrypts the keypair file with a new password and
+//  saves the hash of the new password. Under `--password-stdin`, the current and the new password are read as
+//  consecutive lines of stdin. The new keypair file and hash file are written aside first and then moved in
+//  place, the hash file last, so the old ones are preserved if any step fails. If the program stops in between,
+//  `recover_password_change` completes or undoes the change on the next run.
+//  # Arguments
+//  * `path_to_keypair_file` - path to the encrypted keypair file
+//
+pub(crate) fn change_password(path_to_keypair_file: PathBuf) -> Result<(), DisplayMsg> {
+    let old_passphrase = login()?;
+    let old_keypairs = read_file(path_to_keypair_file.clone()).map_err(|e| {
+        DisplayMsg::FailToOpenOrReadFile(
+            String::from("keypair json"),
+            path_to_keypair_file.clone(),
+            e,
+        )
+    })?;
+    // an empty keypair file holds no keypair and is not encrypted
+    let keypairs = if old_keypairs.is_empty() {
+        None
+    } else {
+        Some(decrypt_with_passphrase(&old_keypairs, old_passphrase)?)
+    };
+
+    let new_password = if PASSWORD_FROM_STDIN.get() == Some(&true) {
+        let password = read_line_from_stdin()?;
+        confirm_weak_password(&password)?;
+        password
+    } else {
+        prompt_new_password(
+            "New password: (press enter to skip password protection.)",
+            "Re-enter new password: ",
+        )?
+    };
+    let new_passphrase = base64url::encode(new_password);
+    let new_hash = password_hash(&new_passphrase)?;
+    let new_keypairs = match keypairs {
+        Some(keypairs) => encrypt_with_passphrase(&keypairs, new_passphrase)?,
+        None => Vec::new(),
+    };
+
+    let hash_path = get_hash_path();
+    let new_keypair_path = path_to_keypair_file.with_extension(PENDING_FILE_EXTENSION);
+    let new_hash_path = hash_path.with_extension(PENDING_FILE_EXTENSION);
+    let remove_pending_files = || {
+        let _ = std::fs::remove_file(&new_keypair_path);
+        let _ = std::fs::remove_file(&new_hash_path);
+    };
+
+    if let Err(e) = write_file(new_keypair_path.clone(), &new_keypairs) {
+        remove_pending_files();
+        return Err(DisplayMsg::FailToWriteFile(
+            String::from("keypair json"),
+            new_keypair_path,
+            e,
+        ));
+    }
+    if let Err(e) = write_file(new_hash_path.clone(), &new_hash) {
+        remove_pending_files();
+        return Err(DisplayMsg::FailToWriteFile(
+            String::from("hash file"),
+            new_hash_path,
+            e,
+        ));
+    }
+
+    if let Err(e) = std::fs::rename(&new_keypair_path, &path_to_keypair_file) {
+        remove_pending_files();
+        return Err(DisplayMsg::FailToWriteFile(
+            String::from("keypair json"),
+            path_to_keypair_file,
+            e.to_string(),
+        ));
+    }
+    if let Err(e) = std::fs::rename(&new_hash_path, &hash_path) {
+        // the keypair file is already encrypted with the new password, so put the old one back
+        let _ = write_file(path_to_keypair_file, &old_keypairs);
+        remove_pending_files();
+        return Err(DisplayMsg::FailToWriteFile(
+            String::from("hash file"),
+            hash_path,
+            e.to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+// `recover_password_change` completes or undoes a `change_password` which was interrupted, e.g. by a crash, before
+//  the new keypair file and hash file were both moved in place. The keypair file is moved before the hash file, so a
+//  pending hash file without a pending keypair file means that only the hash file is left to move. Otherwise the
+//  old files are intact and the pending files are removed.
+//  # Arguments
+//  * `path_to_keypair_file` - path to the encrypted keypair file
+//
+pub(crate) fn recover_password_change(path_to_keypair_file: PathBuf) -> Result<(), DisplayMsg> {
+    let hash_path = get_hash_path();
+    let new_keypair_path = path_to_keypair_file.with_extension(PENDING_FILE_EXTENSION);
+    let new_hash_path = hash_path.with_extension(PENDING_FILE_EXTENSION);
+
+    if new_hash_path.exists() && !new_keypair_path.exists() {
+        std::fs::rename(&new_hash_path, &hash_path).map_err(|e| {
+            DisplayMsg::FailToWriteFile(String::from("hash file"), hash_path, e.to_string())
+        })?;
+        println!("{}", DisplayMsg::CompletedPasswordChange);
+        return Ok(());
+    }
+
+    let pending_paths: Vec<PathBuf> = vec![new_keypair_path, new_hash_path]
+        .into_iter()
+        .filter(|path| path.exists())
+        .collect();
+    if pending_paths.is_empty() {
+        return Ok(());
+    }
+    for path in pending_paths {
+        std::fs::remove_file(&path).map_err(|e| {
+            DisplayMsg::FailToWriteFile(String::from("pending file"), path, e.to_string())
+        })?;
+    }
+    println!("{}", DisplayMsg::UndonePasswordChange);
+    Ok(())
+}
+
+// `prompt_new_password` prompts for a new password twice and asks to confirm it if it is weak. An empty
+//  password skips password protection.
+//  # Arguments
+//  * `prompt` - prompt of the password
+//  * `reenter_prompt` - prompt to re-enter the password
+//
+fn prompt_new_password(prompt: &str, reenter_prompt: &str) -> Result<String, DisplayMsg> {
+    let password1 = rpassword::prompt_password(prompt)
+        .unwrap()
+        .trim()
+        .to_string();
     if !password1.is_empty() {
-        let password2 = rpassword::prompt_password("Re-enter your password: ")
+        let password2 = rpassword::prompt_password(reenter_prompt)
             .unwrap()
             .trim()
             .to_string();
         if password1 != password2 {
             return Err(DisplayMsg::PasswordNotMatch);
         }
+
+        confirm_weak_password(&password1)?;
+    }
+
+    Ok(password1)
+}
+
+// `confirm_weak_password` warns if a non-empty password is weak and asks the user whether to continue with it.
+//  # Arguments
+//  * `password` - password entered by user
+//
+fn confirm_weak_password(password: &str) -> Result<(), DisplayMsg> {
+    let weaknesses = password_weaknesses(password);
+    if password.is_empty() || weaknesses.is_empty() {
+        return Ok(());
     }
 
-    let encoded_password = base64url::encode(password1);
+    warn(DisplayMsg::WeakPassword(weaknesses.join(", ")));
+    print!("Continue with this password? [y/N] ");
+    let _ = std::io::stdout().flush();
+
+    let mut answer = String::new();
+    let _ = std::io::stdin().read_line(&mut answer);
+    if !answer.trim().eq_ignore_ascii_case("y") {
+        return Err(DisplayMsg::WeakPasswordNotConfirmed);
+    }
+
+    Ok(())
+}
+
+// `password_hash` hashes an encoded password with argon2 and a salt of 32 random bytes. It returns the salt
+//  followed by the hash, as saved in the hash file.
+//  # Arguments
+//  * `encoded_password` - Base64url encoded password
+//
+fn password_hash(encoded_password: &str) -> Result<Vec<u8>, DisplayMsg> {
     let mut salt = [0u8; 32];
     OsRng.fill_bytes(&mut salt);
 
@@ -80,25 +358,50 @@ pub(crate) fn setup_password() -> Result<(), DisplayMsg> {
 
     let mut data = salt.to_vec();
     data.extend_from_slice(&key);
-    match write_file(get_hash_path(), &data) {
-        Ok(_) => {
-            println!("{}", DisplayMsg::SuccessSetupPassword);
-            Ok(())
-        }
-        Err(e) => Err(DisplayMsg::FailToWriteFile(
-            String::from("hash file"),
-            get_hash_path(),
-            e,
-        )),
+    Ok(data)
+}
+
+// `password_weaknesses` checks the password against a minimal strength policy and returns
+//  the reasons why it is considered weak. An empty list means the password passes the check.
+//  # Arguments
+//  * `password` - password entered by user
+fn password_weaknesses(password: &str) -> Vec<String> {
+    let mut weaknesses = Vec::new();
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        weaknesses.push(format!("shorter than {} characters", MIN_PASSWORD_LENGTH));
+    }
+
+    let character_classes = [
+        password.chars().any(|c| c.is_lowercase()),
+        password.chars().any(|c| c.is_uppercase()),
+        password.chars().any(|c| c.is_numeric()),
+        password.chars().any(|c| !c.is_alphanumeric()),
+    ];
+    if character_classes.iter().filter(|&&found| found).count() < 3 {
+        weaknesses.push(String::from(
+            "uses fewer than 3 of lowercase letters, uppercase letters, digits and symbols",
+        ));
     }
+
+    weaknesses
 }
 
 // `encrypt` implement data encryption to create an age file.
 //  # Arguments
 //  * `source` - raw data in bytes
 pub(crate) fn encrypt(source: &[u8]) -> Result<Vec<u8>, DisplayMsg> {
-    let encoded_passphrase = login()?;
+    encrypt_with_passphrase(source, login()?)
+}
 
+// `encrypt_with_passphrase` encrypts data to an age file with the given passphrase instead of the one of login.
+//  # Arguments
+//  * `source` - raw data in bytes
+//  * `encoded_passphrase` - Base64url encoded password
+//
+pub(crate) fn encrypt_with_passphrase(
+    source: &[u8],
+    encoded_passphrase: String,
+) -> Result<Vec<u8>, DisplayMsg> {
     let encrypted = {
         let encryptor = age::Encryptor::with_user_passphrase(Secret::new(encoded_passphrase));
 
@@ -119,12 +422,33 @@ pub(crate) fn encrypt(source: &[u8]) -> Result<Vec<u8>, DisplayMsg> {
     Ok(encrypted)
 }
 
+// `is_encrypted` checks whether data is an age file, e.g. a keypair file exported with `--encrypted`,
+//  by its header.
+//  # Arguments
+//  * `source` - data in bytes
+pub(crate) fn is_encrypted(source: &[u8]) -> bool {
+    source.starts_with(AGE_HEADER)
+}
+
+/// First line of every age file in binary format.
+const AGE_HEADER: &[u8] = b"age-encryption.org/v1";
+
 // `decrypt` implement data decryption from age file to original bytes.
 //  # Arguments
 //  * `source` - encrypted data in bytes
 pub(crate) fn decrypt(source: &[u8]) -> Result<Vec<u8>, DisplayMsg> {
-    let encoded_passphrase = login()?;
+    decrypt_with_passphrase(source, login()?)
+}
 
+// `decrypt_with_passphrase` decrypts data from an age file with the given passphrase instead of the one of login.
+//  # Arguments
+//  * `source` - encrypted data in bytes
+//  * `encoded_passphrase` - Base64url encoded password
+//
+pub(crate) fn decrypt_with_passphrase(
+    source: &[u8],
+    encoded_passphrase: String,
+) -> Result<Vec<u8>, DisplayMsg> {
     let decrypted = {
         let decryptor = match age::Decryptor::new(source)
             .map_err(|e| DisplayMsg::FailtoDecrypt(e.to_string()))?
@@ -187,6 +511,27 @@ pub fn write_file(path_to_file: PathBuf, content: &[u8]) -> Result<String, Strin
         .unwrap())
 }
 
+// `encode_bytes` encodes binary output, e.g. a signature or an address, in the encoding chosen with `--output-encoding`.
+//  # Arguments
+//  * `bytes` - bytes to encode
+//  * `encoding` - output encoding
+//
+pub(crate) fn encode_bytes(bytes: &[u8], encoding: OutputEncoding) -> String {
+    match encoding {
+        OutputEncoding::Base64url => base64url::encode(bytes),
+        OutputEncoding::Base64 => base64::encode(bytes),
+        OutputEncoding::Hex => encode_hex(bytes),
+    }
+}
+
+// `encode_hex` encodes bytes as lowercase hexadecimal, two digits per byte.
+//  # Arguments
+//  * `bytes` - bytes to encode
+//
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 // get_random_string generates a rndom string.
 // for naming the docker container.
 //  # Arguments
@@ -198,3 +543,139 @@ pub fn get_random_string() -> String {
         .map(char::from)
         .collect()
 }
+
+// `format_unix_timestamp` formats seconds since the Unix epoch as a UTC date time string,
+//  e.g. "2023-08-01 09:30:00 UTC".
+//  # Arguments
+//  * `timestamp` - seconds since 1970-01-01 00:00:00 UTC
+pub(crate) fn format_unix_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86400;
+    let seconds_of_day = timestamp % 86400;
+
+    // Convert days since epoch to a civil date (proleptic Gregorian calendar)
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60
+    )
+}
+
+// `qr_code_to_string` renders the data as a QR code made of Unicode block characters, to be printed to the terminal.
+//  Light modules are drawn as blocks, which suits terminals with a dark background.
+//  # Arguments
+//  * `data` - the data to encode, e.g. a public address
+pub(crate) fn qr_code_to_string(data: &str) -> Result<String, String> {
+    let code = qrcode::QrCode::new(data.as_bytes()).map_err(|e| e.to_string())?;
+    Ok(code
+        .render::<qrcode::render::unicode::Dense1x2>()
+        .dark_color(qrcode::render::unicode::Dense1x2::Light)
+        .light_color(qrcode::render::unicode::Dense1x2::Dark)
+        .build())
+}
+
+// `qr_code_to_png` renders the data as a QR code in a grayscale PNG image.
+//  # Arguments
+//  * `data` - the data to encode, e.g. a public address
+pub(crate) fn qr_code_to_png(data: &str) -> Result<Vec<u8>, String> {
+    let code = qrcode::QrCode::new(data.as_bytes()).map_err(|e| e.to_string())?;
+    let width = code.width();
+    let colors = code.to_colors();
+
+    // A light border of QR_QUIET_ZONE modules is required around the code for scanners to find it.
+    let size = (width + 2 * QR_QUIET_ZONE) * QR_MODULE_PIXELS;
+    let mut pixels = vec![u8::MAX; size * size];
+    for (i, color) in colors.iter().enumerate() {
+        if *color == qrcode::Color::Light {
+            continue;
+        }
+        let (x, y) = (i % width + QR_QUIET_ZONE, i / width + QR_QUIET_ZONE);
+        for row in y * QR_MODULE_PIXELS..(y + 1) * QR_MODULE_PIXELS {
+            pixels[row * size + x * QR_MODULE_PIXELS..row * size + (x + 1) * QR_MODULE_PIXELS]
+                .fill(0);
+        }
+    }
+
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, size as u32, size as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .map_err(|e| e.to_string())?;
+    Ok(png)
+}
+
+/// Minimum length of a password which is not considered weak
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Extension of the keypair and hash files written aside by `change_password` before they replace the old ones
+const PENDING_FILE_EXTENSION: &str = "new";
+
+/// Width of the light border around a QR code, in modules
+const QR_QUIET_ZONE: usize = 4;
+
+/// Width and height of a QR code module in a PNG image, in pixels
+const QR_MODULE_PIXELS: usize = 8;
+
+#[cfg(test)]
+mod test {
+    use super::{
+        encode_bytes, format_unix_timestamp, password_weaknesses, qr_code_to_png,
+        MIN_PASSWORD_LENGTH,
+    };
+    use crate::command::OutputEncoding;
+
+    #[test]
+    fn test_password_weaknesses() {
+        assert_eq!(password_weaknesses("abc").len(), 2);
+        assert_eq!(password_weaknesses("abcdefghij").len(), 1);
+        assert_eq!(password_weaknesses("Abc1").len(), 1);
+        assert!(password_weaknesses("Abcdefg1").is_empty());
+        assert!(password_weaknesses("correct-horse-42").is_empty());
+        assert_eq!(
+            password_weaknesses("Ab1"),
+            vec![format!("shorter than {} characters", MIN_PASSWORD_LENGTH)]
+        );
+    }
+
+    #[test]
+    fn test_encode_bytes() {
+        let bytes = [0xfb, 0xff, 0x01, 0x02];
+        assert_eq!(encode_bytes(&bytes, OutputEncoding::Base64url), "-_8BAg");
+        assert_eq!(encode_bytes(&bytes, OutputEncoding::Base64), "+/8BAg==");
+        assert_eq!(encode_bytes(&bytes, OutputEncoding::Hex), "fbff0102");
+    }
+
+    #[test]
+    fn test_format_unix_timestamp() {
+        assert_eq!(format_unix_timestamp(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(format_unix_timestamp(951782400), "2000-02-29 00:00:00 UTC");
+        assert_eq!(format_unix_timestamp(1690882200), "2023-08-01 09:30:00 UTC");
+    }
+
+    #[test]
+    fn test_qr_code_to_png() {
+        let png = qr_code_to_png("kRPL8cXI73DNgVSSQz9WfIi-mAAvFvdXKfZ9UPBEv_A").unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    }
+}
@@ -1,6 +1,10 @@
 #![allow(dead_code)]
 
-use std::{env, path::PathBuf};
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 use regex::Regex;
 use temp_dir::TempDir;
@@ -43,6 +47,53 @@ impl TestEnv {
         std::fs::write(&file_path, content).unwrap();
         file_path
     }
+
+    /// Configures the Fullnode RPC url of the test network, for commands which need one.
+    pub fn with_rpc_config(self) -> Self {
+        self.add_file(
+            "config.toml",
+            b"url = \"https://pchain-test-rpc02.parallelchain.io\"\n",
+        );
+        self
+    }
+}
+
+/// Creates a Transaction file at `destination` with one transfer, and checks that it is created. `extra_args` are
+/// given to `transaction create` before the `transfer` subcommand. The transaction is a TransactionV2 with nonce 0
+/// unless `extra_args` sets the version or the nonce.
+pub fn create_transfer_tx(env: &TestEnv, destination: &Path, extra_args: &[&str]) {
+    let mut command = Command::new(&env.bin);
+    command
+        .arg("transaction")
+        .arg("create")
+        .arg("--destination")
+        .arg(destination)
+        .args(extra_args);
+    if !extra_args
+        .iter()
+        .any(|arg| ["--v1", "--v2", "--auto-version"].contains(arg))
+    {
+        command.arg("--v2");
+    }
+    if !extra_args.contains(&"--nonce") {
+        command.arg("--nonce").arg("0");
+    }
+    let output = command
+        .arg("--gas-limit")
+        .arg("100000000")
+        .arg("--max-base-fee-per-gas")
+        .arg("8")
+        .arg("--priority-fee-per-gas")
+        .arg("0")
+        .arg("transfer")
+        .arg("--recipient")
+        .arg("kRPL8cXI73DNgVSSQz9WfIi-mAAvFvdXKfZ9UPBEv_A")
+        .arg("--amount")
+        .arg("100")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&["Successfully create"], &output).unwrap();
 }
 
 pub fn expect_output(patterns: &[&str], output: &str) -> Result<(), String> {
@@ -1,8 +1,12 @@
-use std::{convert::TryInto, process::Command};
+use std::{
+    convert::TryInto,
+    io::Write,
+    process::{Command, Stdio},
+};
 
 use common::{expect_output, TestEnv};
 use ed25519_dalek::Signature;
-use pchain_types::cryptography::Keypair;
+use pchain_types::{cryptography::Keypair, rpc::TransactionV1OrV2, serialization::Deserializable};
 use rand_chacha::rand_core::OsRng;
 use serde_json::Value;
 use serial_test::serial;
@@ -140,6 +144,105 @@ fn test_keys_import_export() {
     assert_eq!(exported_keypair["private_key"].as_str().unwrap(), &private);
 }
 
+/// - Case:     User imports a keypair with `--dry-run`
+/// - Expect:   Valid keys are reported but not saved, so the same name can be imported afterwards. Invalid keys are refused
+/// - Command:  ./pchain_client keys import --public <PUBLIC> --private <PRIVATE> --keypair-name <KEYPAIR_NAME> --dry-run
+#[test]
+#[serial]
+fn test_keys_import_dry_run() {
+    let env = TestEnv::new();
+    let (public, private) = {
+        let mut osrng = OsRng {};
+        let keypair = Keypair::generate(&mut osrng);
+        (
+            base64url::encode(keypair.verifying_key().as_bytes()),
+            base64url::encode(keypair.as_bytes()),
+        )
+    };
+
+    let import_keypair = |public: &str, dry_run: bool| {
+        let mut command = Command::new(&env.bin);
+        command
+            .arg("keys")
+            .arg("import")
+            .arg("--public")
+            .arg(public)
+            .arg("--private")
+            .arg(&private)
+            .arg("--keypair-name")
+            .arg("testkey");
+        if dry_run {
+            command.arg("--dry-run");
+        }
+        let output = command.output().unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let output = import_keypair(&public, true);
+    expect_output(
+        &[&format!(
+            "Dry run: testkey with public key <{}> is valid",
+            public
+        )],
+        &output,
+    )
+    .unwrap();
+
+    let output = import_keypair("kRPL8cXI73DNgVSSQz9WfIi-mAAvFvdXKfZ9UPBEv_A", true);
+    assert!(!output.contains("Dry run"));
+
+    let output = import_keypair(&public, false);
+    expect_output(&["Successfully add keypair with name testkey."], &output).unwrap();
+}
+
+/// - Case:     User shows a keypair together with a QR code of its public address
+/// - Expect:   QR code is printed to the terminal and saved as a PNG file. The private key is not written anywhere
+/// - Command:  ./pchain_client keys show --keypair-name <KEYPAIR_NAME> --qr --qr-png <PNG_FILE>
+#[test]
+#[serial]
+fn test_keys_show_qr() {
+    let env = TestEnv::new();
+    let env_qr_path = env.cli_home.path().join("testkey.png");
+
+    let mut osrng = OsRng {};
+    let keypair = Keypair::generate(&mut osrng);
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("import")
+        .arg("--public")
+        .arg(base64url::encode(keypair.verifying_key().as_bytes()))
+        .arg("--private")
+        .arg(base64url::encode(keypair.as_bytes()))
+        .arg("--keypair-name")
+        .arg("testkey")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&["Successfully add keypair with name testkey."], &output).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("show")
+        .arg("--keypair-name")
+        .arg("testkey")
+        .arg("--qr")
+        .arg("--qr-png")
+        .arg(env_qr_path.to_str().unwrap())
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &["Public key: ", "█", "QR code of public address is saved at"],
+        &output,
+    )
+    .unwrap();
+    assert!(!output.contains(&base64url::encode(keypair.as_bytes())));
+    let png = std::fs::read(env_qr_path).unwrap();
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    assert!(!env.cli_home.path().join("testkey.json").exists());
+}
+
 /// - Case:     User import a keypair, and then export the keypair
 /// - Expect:   
 /// - Command:  ./pchain_client keys sign
@@ -187,3 +290,1170 @@ fn test_keys_sign() {
         Signature::from_bytes(&base64url::decode(ciphertext).unwrap().try_into().unwrap());
     assert!(keypair.verify(&[1u8, 2, 3, 4], &signature).is_ok());
 }
+
+/// - Case:     User signs the content of a file, and then a plaintext message
+/// - Expect:   Signatures of the raw file bytes and of the UTF-8 bytes of the plaintext
+/// - Command:
+///   - ./pchain_client keys sign --file <FILE> --keypair-name <KEYPAIR_NAME>
+///   - ./pchain_client keys sign --plaintext <PLAINTEXT> --keypair-name <KEYPAIR_NAME>
+#[test]
+#[serial]
+fn test_keys_sign_file_and_plaintext() {
+    let env = TestEnv::new();
+    let message_path = env.add_file("message.bin", &[0u8, 159, 146, 150, 255]);
+
+    let mut osrng = OsRng {};
+    let keypair = Keypair::generate(&mut osrng);
+    let private = base64url::encode(keypair.as_bytes());
+    let verifying = keypair.verifying_key();
+    let public = base64url::encode(verifying.as_bytes());
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("import")
+        .arg("--public")
+        .arg(&public)
+        .arg("--private")
+        .arg(&private)
+        .arg("--keypair-name")
+        .arg("testkey")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["Successfully add keypair with name testkey."], &output).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("sign")
+        .arg("--file")
+        .arg(message_path.to_str().unwrap())
+        .arg("--keypair-name")
+        .arg("testkey")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["File: ", "Ciphertext: "], &output).unwrap();
+
+    let ciphertext = output.split(' ').next_back().unwrap().trim();
+    let signature =
+        Signature::from_bytes(&base64url::decode(ciphertext).unwrap().try_into().unwrap());
+    assert!(keypair
+        .verify(&[0u8, 159, 146, 150, 255], &signature)
+        .is_ok());
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("sign")
+        .arg("--plaintext")
+        .arg("hello world")
+        .arg("--keypair-name")
+        .arg("testkey")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["Message: hello world", "Ciphertext: "], &output).unwrap();
+
+    let ciphertext = output.split(' ').next_back().unwrap().trim();
+    let signature =
+        Signature::from_bytes(&base64url::decode(ciphertext).unwrap().try_into().unwrap());
+    assert!(keypair.verify(b"hello world", &signature).is_ok());
+}
+
+/// - Case:     User signs a message and writes the signature to a file
+/// - Expect:   Base64 encoded signature by default, raw signature bytes with `--raw`
+/// - Command:  ./pchain_client keys sign --output-file <OUTPUT_FILE> [--raw]
+#[test]
+#[serial]
+fn test_keys_sign_output_file() {
+    let env = TestEnv::new();
+    let encoded_path = env.cli_home.path().join("signature.txt");
+    let raw_path = env.cli_home.path().join("signature.bin");
+
+    let mut osrng = OsRng {};
+    let keypair = Keypair::generate(&mut osrng);
+    let private = base64url::encode(keypair.as_bytes());
+    let verifying = keypair.verifying_key();
+    let public = base64url::encode(verifying.as_bytes());
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("import")
+        .arg("--public")
+        .arg(&public)
+        .arg("--private")
+        .arg(&private)
+        .arg("--keypair-name")
+        .arg("testkey")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["Successfully add keypair with name testkey."], &output).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("sign")
+        .arg("--message")
+        .arg(base64url::encode([1u8, 2, 3, 4]))
+        .arg("--keypair-name")
+        .arg("testkey")
+        .arg("--output-file")
+        .arg(encoded_path.to_str().unwrap())
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["Successfully create signature file"], &output).unwrap();
+
+    let encoded = std::fs::read_to_string(&encoded_path).unwrap();
+    let signature =
+        Signature::from_bytes(&base64url::decode(&encoded).unwrap().try_into().unwrap());
+    assert!(keypair.verify(&[1u8, 2, 3, 4], &signature).is_ok());
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("sign")
+        .arg("--message")
+        .arg(base64url::encode([1u8, 2, 3, 4]))
+        .arg("--keypair-name")
+        .arg("testkey")
+        .arg("--output-file")
+        .arg(raw_path.to_str().unwrap())
+        .arg("--raw")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["Successfully create signature file"], &output).unwrap();
+
+    let raw = std::fs::read(&raw_path).unwrap();
+    let signature = Signature::from_bytes(&raw.try_into().unwrap());
+    assert!(keypair.verify(&[1u8, 2, 3, 4], &signature).is_ok());
+}
+
+/// - Case:     User lists the keys with a name filter
+/// - Expect:   Only the keys whose name contains the filter are displayed
+/// - Command:  ./pchain_client keys list --filter <FILTER>
+#[test]
+#[serial]
+fn test_keys_list_filter() {
+    let env = TestEnv::new();
+
+    for keypair_name in ["validator-1", "validator-2", "personal"] {
+        let output = Command::new(&env.bin)
+            .arg("keys")
+            .arg("create")
+            .arg("--keypair-name")
+            .arg(keypair_name)
+            .output()
+            .unwrap();
+        let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+        expect_output(&["Successfully create"], &output).unwrap();
+    }
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("list")
+        .arg("--filter")
+        .arg("validator")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["validator-1", "validator-2"], &output).unwrap();
+    assert!(!output.contains("personal"));
+}
+
+/// - Case:     User lists the keys with a name filter in JSON format
+/// - Expect:   Name and public key of the keys whose name contains the filter, without private keys
+/// - Command:  ./pchain_client keys list --filter <FILTER> --json
+#[test]
+#[serial]
+fn test_keys_list_json() {
+    let env = TestEnv::new();
+
+    let mut public_keys = Vec::new();
+    for keypair_name in ["validator-1", "validator-2", "personal"] {
+        let output = Command::new(&env.bin)
+            .arg("keys")
+            .arg("create")
+            .arg("--keypair-name")
+            .arg(keypair_name)
+            .arg("--json")
+            .output()
+            .unwrap();
+        let output: Value = serde_json::from_slice(&output.stdout).unwrap();
+        public_keys.push(output["public_key"].as_str().unwrap().to_string());
+    }
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("list")
+        .arg("--filter")
+        .arg("validator")
+        .arg("--json")
+        .output()
+        .unwrap();
+    let output: Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(
+        output,
+        serde_json::json!([
+            {"name": "validator-1", "public_key": public_keys[0]},
+            {"name": "validator-2", "public_key": public_keys[1]},
+        ])
+    );
+}
+
+/// - Case:     User creates a keypair with JSON output
+/// - Expect:   The name and public key of the created keypair are displayed in JSON
+/// - Command:  ./pchain_client keys create --keypair-name <KEYPAIR_NAME> --json
+#[test]
+#[serial]
+fn test_keys_create_json() {
+    let env = TestEnv::new();
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("create")
+        .arg("--keypair-name")
+        .arg("testkey")
+        .arg("--json")
+        .output()
+        .unwrap();
+    let output: Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(output["name"].as_str().unwrap(), "testkey");
+    let public_key = output["public_key"].as_str().unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("list")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["testkey", public_key], &output).unwrap();
+}
+
+/// - Case:     User signs a Transaction file without submitting it
+/// - Expect:   Display the transaction hash and signature. The signed transaction is written to the output file
+/// - Command:  ./pchain_client keys sign-transaction --file <FILE> --keypair-name <KEYPAIR_NAME> --output-file <OUTPUT_FILE>
+#[test]
+#[serial]
+fn test_keys_sign_transaction() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.json");
+    let signed_tx_path = env.cli_home.path().join("tx.signed");
+
+    let mut osrng = OsRng {};
+    let keypair = Keypair::generate(&mut osrng);
+    let public = base64url::encode(keypair.verifying_key().as_bytes());
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("import")
+        .arg("--public")
+        .arg(&public)
+        .arg("--private")
+        .arg(base64url::encode(keypair.as_bytes()))
+        .arg("--keypair-name")
+        .arg("testkey")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&["Successfully add keypair with name testkey."], &output).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("create")
+        .arg("--destination")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--v2")
+        .arg("--nonce")
+        .arg("0")
+        .arg("--gas-limit")
+        .arg("100000000")
+        .arg("--max-base-fee-per-gas")
+        .arg("8")
+        .arg("--priority-fee-per-gas")
+        .arg("0")
+        .arg("transfer")
+        .arg("--recipient")
+        .arg(&public)
+        .arg("--amount")
+        .arg("100")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&["Successfully create Transaction file"], &output).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("sign-transaction")
+        .arg("--file")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--keypair-name")
+        .arg("testkey")
+        .arg("--output-file")
+        .arg(signed_tx_path.to_str().unwrap())
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    let signed_tx =
+        TransactionV1OrV2::deserialize(&std::fs::read(&signed_tx_path).unwrap()).unwrap();
+    let tx = match signed_tx {
+        TransactionV1OrV2::V2(tx) => tx,
+        TransactionV1OrV2::V1(_) => panic!("expected TransactionV2"),
+    };
+    assert_eq!(tx.signer, keypair.verifying_key().to_bytes());
+    expect_output(
+        &[
+            &format!("Transaction hash: {}", base64url::encode(tx.hash)),
+            &format!("Signature: {}", base64url::encode(tx.signature)),
+            "Successfully create Signed transaction file",
+        ],
+        &output,
+    )
+    .unwrap();
+}
+
+/// - Case:     User creates a keypair with the password piped to stdin
+/// - Expect:   Keypair is created with the correct password. Display error of wrong password otherwise
+/// - Command:  echo <PASSWORD> | ./pchain_client keys create --keypair-name <KEYPAIR_NAME> --password-stdin
+#[test]
+#[serial]
+fn test_keys_create_password_stdin() {
+    let env = TestEnv::new();
+
+    // password hash file holds a 32-byte salt followed by the argon2 hash of the Base64 encoded password
+    let salt = [7u8; 32];
+    let hash = argon2::hash_raw(
+        base64url::encode("correct horse").as_bytes(),
+        &salt,
+        &argon2::Config::default(),
+    )
+    .unwrap();
+    env.add_file("hash", &[&salt[..], &hash[..]].concat());
+
+    let create_key = |keypair_name: &str, password: &str| {
+        let mut child = Command::new(&env.bin)
+            .arg("keys")
+            .arg("create")
+            .arg("--keypair-name")
+            .arg(keypair_name)
+            .arg("--password-stdin")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(format!("{password}\n").as_bytes())
+            .unwrap();
+        let output = child.wait_with_output().unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let output = create_key("testkey", "wrong horse");
+    expect_output(&["Wrong password"], &output).unwrap();
+
+    let output = create_key("testkey", "correct horse");
+    expect_output(&["Successfully create testkey with public key"], &output).unwrap();
+}
+
+/// - Case:     User imports an external keypair whose signer command prints a signature, signs with it and exports it
+/// - Expect:   Message is signed by the signer command. Export is refused as there is no private key
+/// - Command:
+///   - ./pchain_client keys import-external --public <PUBLIC_KEY> --signer-command <COMMAND> --keypair-name <KEYPAIR_NAME>
+///   - ./pchain_client keys sign --message <MESSAGE> --keypair-name <KEYPAIR_NAME>
+///   - ./pchain_client keys export --keypair-name <KEYPAIR_NAME>
+#[cfg(unix)]
+#[test]
+#[serial]
+fn test_keys_import_external() {
+    use ed25519_dalek::Signer;
+
+    let env = TestEnv::new();
+
+    let mut osrng = OsRng {};
+    let keypair = Keypair::generate(&mut osrng);
+    let public = base64url::encode(keypair.verifying_key().as_bytes());
+    let message = [1u8, 2, 3, 4];
+    let signature = base64url::encode(keypair.sign(&message).to_bytes());
+
+    // the signer command stands in for an HSM which signs the message
+    let import_external = |keypair_name: &str, signer_command: &str| {
+        let output = Command::new(&env.bin)
+            .arg("keys")
+            .arg("import-external")
+            .arg("--public")
+            .arg(&public)
+            .arg("--signer-command")
+            .arg(signer_command)
+            .arg("--keypair-name")
+            .arg(keypair_name)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    let sign = |keypair_name: &str| {
+        let output = Command::new(&env.bin)
+            .arg("keys")
+            .arg("sign")
+            .arg("--message")
+            .arg(base64url::encode(message))
+            .arg("--keypair-name")
+            .arg(keypair_name)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let output = import_external("hsm", &format!("cat > /dev/null; echo {signature}"));
+    expect_output(&["Successfully add keypair with name hsm."], &output).unwrap();
+
+    let output = sign("hsm");
+    expect_output(
+        &["Message: AQIDBA", &format!("Ciphertext: {signature}")],
+        &output,
+    )
+    .unwrap();
+
+    // a signer command which does not sign for the public key
+    let other_signature =
+        base64url::encode(Keypair::generate(&mut osrng).sign(&message).to_bytes());
+    let output = import_external("other", &format!("echo {other_signature}"));
+    expect_output(&["Successfully add keypair with name other."], &output).unwrap();
+
+    let output = sign("other");
+    expect_output(
+        &[&format!(
+            "is not a valid signature of public key <{public}>"
+        )],
+        &output,
+    )
+    .unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("export")
+        .arg("--keypair-name")
+        .arg("hsm")
+        .arg("--destination")
+        .arg(env.cli_home.path().join("hsm.json"))
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&["has no private key to export"], &output).unwrap();
+    assert!(!env.cli_home.path().join("hsm.json").exists());
+}
+
+/// - Case:     User lists keypairs with `--compact-address`
+/// - Expect:   Public keys are abbreviated to their first and last 4 characters
+/// - Command:
+///   - ./pchain_client keys import --public <PUBLIC> --private <PRIVATE> --keypair-name <KEYPAIR_NAME>
+///   - ./pchain_client keys list --compact-address
+#[test]
+#[serial]
+fn test_keys_list_compact_address() {
+    let env = TestEnv::new();
+    let (public, private) = {
+        let mut osrng = OsRng {};
+        let keypair = Keypair::generate(&mut osrng);
+        (
+            base64url::encode(keypair.verifying_key().as_bytes()),
+            base64url::encode(keypair.as_bytes()),
+        )
+    };
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("import")
+        .arg("--public")
+        .arg(&public)
+        .arg("--private")
+        .arg(&private)
+        .arg("--keypair-name")
+        .arg("testkey")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&["Successfully add keypair with name testkey."], &output).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("list")
+        .arg("--compact-address")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    let compact = format!("{}…{}", &public[..4], &public[public.len() - 4..]);
+    expect_output(&["testkey", &compact], &output).unwrap();
+    assert!(!output.contains(&public));
+}
+
+/// - Case:     User changes the password of the keypair file with the passwords piped to stdin
+/// - Expect:   Keypairs are readable with the new password only. A wrong current password leaves the keypair file as it was.
+///             A change which stopped before all its files were moved in place is completed or undone on the next run
+/// - Command:
+///   - printf "<PASSWORD>\n<NEW_PASSWORD>\n" | ./pchain_client keys change-password --password-stdin
+///   - echo <NEW_PASSWORD> | ./pchain_client keys list --password-stdin
+#[test]
+#[serial]
+fn test_keys_change_password() {
+    let env = TestEnv::new();
+
+    let salt = [7u8; 32];
+    let hash = argon2::hash_raw(
+        base64url::encode("correct horse").as_bytes(),
+        &salt,
+        &argon2::Config::default(),
+    )
+    .unwrap();
+    env.add_file("hash", &[&salt[..], &hash[..]].concat());
+    env.add_file("keypair", &[]);
+
+    let run_with_stdin = |args: &[&str], stdin: &str| {
+        let mut child = Command::new(&env.bin)
+            .args(args)
+            .arg("--password-stdin")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(stdin.as_bytes())
+            .unwrap();
+        let output = child.wait_with_output().unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let output = run_with_stdin(
+        &["keys", "create", "--keypair-name", "testkey"],
+        "correct horse\n",
+    );
+    expect_output(&["Successfully create testkey with public key"], &output).unwrap();
+
+    let keypair_file = std::fs::read(env.cli_home.path().join("keypair")).unwrap();
+    let output = run_with_stdin(
+        &["keys", "change-password"],
+        "wrong horse\nBattery-Staple-9\n",
+    );
+    expect_output(&["Wrong password"], &output).unwrap();
+    assert_eq!(
+        std::fs::read(env.cli_home.path().join("keypair")).unwrap(),
+        keypair_file
+    );
+
+    let output = run_with_stdin(
+        &["keys", "change-password"],
+        "correct horse\nBattery-Staple-9\n",
+    );
+    expect_output(&["Password is changed"], &output).unwrap();
+
+    let output = run_with_stdin(&["keys", "list"], "Battery-Staple-9\n");
+    expect_output(&["testkey"], &output).unwrap();
+
+    let output = run_with_stdin(&["keys", "list"], "correct horse\n");
+    expect_output(&["Wrong password"], &output).unwrap();
+
+    // stopped after moving the new keypair file in place, but before the new hash file
+    let hash_path = env.cli_home.path().join("hash");
+    let new_hash_path = env.cli_home.path().join("hash.new");
+    std::fs::rename(&hash_path, &new_hash_path).unwrap();
+    std::fs::write(&hash_path, [&salt[..], &hash[..]].concat()).unwrap();
+    let output = run_with_stdin(&["keys", "list"], "Battery-Staple-9\n");
+    expect_output(
+        &["An interrupted password change is completed", "testkey"],
+        &output,
+    )
+    .unwrap();
+    assert!(!new_hash_path.exists());
+
+    // stopped before moving any of the new files in place
+    let new_keypair_path = env.cli_home.path().join("keypair.new");
+    std::fs::write(&new_keypair_path, b"partial").unwrap();
+    std::fs::write(&new_hash_path, b"partial").unwrap();
+    let output = run_with_stdin(&["keys", "list"], "Battery-Staple-9\n");
+    expect_output(
+        &["An interrupted password change is undone", "testkey"],
+        &output,
+    )
+    .unwrap();
+    assert!(!new_keypair_path.exists());
+    assert!(!new_hash_path.exists());
+}
+
+/// - Case:     User verifies a signature of a message, and of another message
+/// - Expect:   The signature is valid for the signed message only
+/// - Command:  ./pchain_client keys verify --message <MESSAGE> --signature <SIGNATURE> --public <PUBLIC_KEY>
+#[test]
+#[serial]
+fn test_keys_verify() {
+    use ed25519_dalek::Signer;
+
+    let env = TestEnv::new();
+
+    let mut osrng = OsRng {};
+    let keypair = Keypair::generate(&mut osrng);
+    let public = base64url::encode(keypair.verifying_key().as_bytes());
+    let signature = base64url::encode(keypair.sign(&[1u8, 2, 3, 4]).to_bytes());
+
+    let verify = |message: &[u8], signature: &str| {
+        let output = Command::new(&env.bin)
+            .arg("keys")
+            .arg("verify")
+            .arg("--message")
+            .arg(base64url::encode(message))
+            .arg("--signature")
+            .arg(signature)
+            .arg("--public")
+            .arg(&public)
+            .output()
+            .unwrap();
+        (
+            output.status.success(),
+            String::from_utf8_lossy(&output.stdout).to_string(),
+        )
+    };
+
+    let (success, output) = verify(&[1u8, 2, 3, 4], &signature);
+    assert!(success);
+    expect_output(&["Signature is valid"], &output).unwrap();
+
+    let (success, output) = verify(&[4u8, 3, 2, 1], &signature);
+    assert!(!success);
+    expect_output(&["Signature verification failed"], &output).unwrap();
+
+    let (success, output) = verify(&[1u8, 2, 3, 4], "AAAA");
+    assert!(!success);
+    expect_output(&["Incorrect length of signature"], &output).unwrap();
+}
+
+/// - Case:     User creates a keypair derived from a mnemonic phrase
+/// - Expect:   A 24-word phrase is displayed once with a warning, and is not saved in the keypair file
+/// - Command:  ./pchain_client keys create --keypair-name <KEYPAIR_NAME> --mnemonic
+#[test]
+#[serial]
+fn test_keys_create_mnemonic() {
+    let env = TestEnv::new();
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("create")
+        .arg("--keypair-name")
+        .arg("testkey")
+        .arg("--mnemonic")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &[
+            "Successfully create testkey with public key",
+            "shown only this once and is not saved",
+        ],
+        &output,
+    )
+    .unwrap();
+    let phrase = output
+        .lines()
+        .find_map(|line| line.strip_prefix("Mnemonic phrase: "))
+        .unwrap();
+    assert_eq!(phrase.split(' ').count(), 24);
+
+    let keypair_file = std::fs::read(env.cli_home.path().join("keypair")).unwrap();
+    assert!(!String::from_utf8_lossy(&keypair_file).contains(phrase));
+}
+
+/// - Case:     User restores a keypair from the mnemonic phrase it was created with, on another machine
+/// - Expect:   The restored keypair has the same public key. An invalid phrase is refused
+/// - Command:
+///   - ./pchain_client keys create --keypair-name <KEYPAIR_NAME> --mnemonic --json
+///   - ./pchain_client keys import-mnemonic --phrase <PHRASE> --keypair-name <KEYPAIR_NAME>
+#[test]
+#[serial]
+fn test_keys_import_mnemonic() {
+    let (phrase, public_key) = {
+        let env = TestEnv::new();
+        let output = Command::new(&env.bin)
+            .arg("keys")
+            .arg("create")
+            .arg("--keypair-name")
+            .arg("testkey")
+            .arg("--mnemonic")
+            .arg("--json")
+            .output()
+            .unwrap();
+        let created: Value = serde_json::from_slice(&output.stdout).unwrap();
+        (
+            created["mnemonic"].as_str().unwrap().to_string(),
+            created["public_key"].as_str().unwrap().to_string(),
+        )
+    };
+
+    let env = TestEnv::new();
+    let import_mnemonic = |phrase: &str| {
+        let output = Command::new(&env.bin)
+            .arg("keys")
+            .arg("import-mnemonic")
+            .arg("--phrase")
+            .arg(phrase)
+            .arg("--keypair-name")
+            .arg("restored")
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let words: Vec<&str> = phrase.split(' ').collect();
+    let output = import_mnemonic(&words[..23].join(" "));
+    expect_output(
+        &["Invalid mnemonic phrase. The phrase has 23 words"],
+        &output,
+    )
+    .unwrap();
+
+    let output = import_mnemonic(&phrase);
+    expect_output(&["Successfully add keypair with name restored."], &output).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("list")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&["restored", &public_key], &output).unwrap();
+}
+
+/// - Case:     User creates two keypairs, and then exports all keypairs in plain text, encrypted, and to a missing directory
+/// - Expect:   A JSON array of both keypairs with a warning, an age encrypted file, and an error message
+/// - Command:
+///   - ./pchain_client keys export-all --destination <DESTINATION>
+///   - ./pchain_client keys export-all --destination <DESTINATION> --encrypted
+#[test]
+#[serial]
+fn test_keys_export_all() {
+    let env = TestEnv::new();
+    let plain_path = env.cli_home.path().join("keypairs.json");
+    let encrypted_path = env.cli_home.path().join("keypairs.age");
+
+    for keypair_name in ["first", "second"] {
+        let output = Command::new(&env.bin)
+            .arg("keys")
+            .arg("create")
+            .arg("--keypair-name")
+            .arg(keypair_name)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+    }
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("export-all")
+        .arg("--destination")
+        .arg(plain_path.to_str().unwrap())
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &[
+            "Warning: The exported file contains the private keys",
+            "2 keypairs are saved at",
+        ],
+        &output,
+    )
+    .unwrap();
+    let keypairs: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&plain_path).unwrap()).unwrap();
+    assert_eq!(keypairs[0]["name"], "first");
+    assert_eq!(keypairs[1]["name"], "second");
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("export-all")
+        .arg("--destination")
+        .arg(encrypted_path.to_str().unwrap())
+        .arg("--encrypted")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    assert!(!output.contains("Warning"));
+    expect_output(&["2 keypairs are saved at"], &output).unwrap();
+    let encrypted = std::fs::read(&encrypted_path).unwrap();
+    assert!(serde_json::from_slice::<serde_json::Value>(&encrypted).is_err());
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("export-all")
+        .arg("--destination")
+        .arg(env.cli_home.path().join("missing").join("keypairs.json"))
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["Error: Invalid path"], &output).unwrap();
+}
+
+/// - Case:     User imports an exported file in which one keypair has a new name and one has a public key which does
+///             not match its keypair, with `--fail-fast` and by default, and then an encrypted exported file
+/// - Expect:   Nothing is imported with `--fail-fast`. By default, the keypair with the new name is imported, keypairs
+///             whose names already exist are skipped with a warning, and invalid keypairs are reported with a non-zero
+///             exit code
+/// - Command:
+///   - ./pchain_client keys import-all --file <FILE>
+#[test]
+#[serial]
+fn test_keys_import_all() {
+    let env = TestEnv::new();
+    let plain_path = env.cli_home.path().join("keypairs.json");
+    let encrypted_path = env.cli_home.path().join("keypairs.age");
+
+    for keypair_name in ["first", "second"] {
+        let output = Command::new(&env.bin)
+            .arg("keys")
+            .arg("create")
+            .arg("--keypair-name")
+            .arg(keypair_name)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+    }
+    for (path, encrypted) in [(&plain_path, false), (&encrypted_path, true)] {
+        let mut command = Command::new(&env.bin);
+        command
+            .arg("keys")
+            .arg("export-all")
+            .arg("--destination")
+            .arg(path.to_str().unwrap());
+        if encrypted {
+            command.arg("--encrypted");
+        }
+        assert!(command.output().unwrap().status.success());
+    }
+
+    let mut keypairs: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&plain_path).unwrap()).unwrap();
+    keypairs[0]["name"] = serde_json::json!("first-copy");
+    let mut broken = keypairs[1].clone();
+    broken["name"] = serde_json::json!("broken");
+    broken["public_key"] = keypairs[0]["public_key"].clone();
+    keypairs.as_array_mut().unwrap().push(broken);
+    std::fs::write(&plain_path, keypairs.to_string()).unwrap();
+    let import_all = |args: &[&str]| {
+        Command::new(&env.bin)
+            .arg("keys")
+            .arg("import-all")
+            .arg("--file")
+            .arg(plain_path.to_str().unwrap())
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    let output = import_all(&["--fail-fast"]);
+    assert!(!output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["Error: Keypair with name broken is invalid."], &output).unwrap();
+    assert!(!output.contains("Imported"));
+
+    let output = import_all(&[]);
+    assert!(!output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &[
+            "Warning: Keypair with name second already exists. It is skipped.",
+            "Error: Keypair with name broken is invalid. Public key does not match the one derived from the keypair.",
+            "Imported 1, skipped 1 \\(already exist\\) and 1 \\(invalid\\).",
+            "Error: 1 of 3 items failed: broken.",
+        ],
+        &output,
+    )
+    .unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("list")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["first", "second", "first-copy"], &output).unwrap();
+    assert!(!output.contains("broken"));
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("import-all")
+        .arg("--file")
+        .arg(encrypted_path.to_str().unwrap())
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &["Imported 0, skipped 2 \\(already exist\\) and 0 \\(invalid\\)."],
+        &output,
+    )
+    .unwrap();
+}
+
+/// - Case:     User imports a keypair with a long name, shows it, and then shows a keypair which does not exist
+/// - Expect:   Full name and public key without the private key, and then an error message
+/// - Command:  ./pchain_client keys show --keypair-name <KEYPAIR_NAME>
+#[test]
+#[serial]
+fn test_keys_show() {
+    let env = TestEnv::new();
+    let keypair_name =
+        "a-keypair-name-which-is-longer-than-the-fifty-characters-shown-by-keys-list";
+
+    let mut osrng = OsRng {};
+    let keypair = Keypair::generate(&mut osrng);
+    let private = base64url::encode(keypair.as_bytes());
+    let public = base64url::encode(keypair.verifying_key().as_bytes());
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("import")
+        .arg("--public")
+        .arg(&public)
+        .arg("--private")
+        .arg(&private)
+        .arg("--keypair-name")
+        .arg(keypair_name)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("show")
+        .arg("--keypair-name")
+        .arg(keypair_name)
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    assert_eq!(
+        output,
+        format!("Keypair name: {}\nPublic key: {}\n", keypair_name, public)
+    );
+    assert!(!output.contains(&private));
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("show")
+        .arg("--keypair-name")
+        .arg("missing")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["Keypair name missing provided does not exist"], &output).unwrap();
+}
+
+/// - Case:     User derives the address of a private key, and then of private keys with an invalid length or encoding
+/// - Expect:   The public key of the private key without saving a keypair, and then error messages which do not
+///             repeat the private key
+/// - Command:  ./pchain_client keys address --private <PRIVATE_KEY>
+#[test]
+#[serial]
+fn test_keys_address() {
+    let env = TestEnv::new();
+
+    let mut osrng = OsRng {};
+    let keypair = Keypair::generate(&mut osrng);
+    let private = base64url::encode(keypair.as_bytes());
+    let public = base64url::encode(keypair.verifying_key().as_bytes());
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("address")
+        .arg("--private")
+        .arg(&private)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    assert_eq!(output, format!("Public key: {}\n", public));
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("list")
+        .arg("--json")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    assert!(!output.contains(&public));
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("address")
+        .arg("--private")
+        .arg(base64url::encode(&keypair.as_bytes()[..16]))
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &["Error: Invalid Ed25519 keypair. The private key is 16 bytes long but should be 32 bytes long."],
+        &output,
+    )
+    .unwrap();
+
+    let invalid_private = format!("{}!", private);
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("address")
+        .arg("--private")
+        .arg(&invalid_private)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &["Error: Fail to decode \"private key\" from a Base64URL string."],
+        &output,
+    )
+    .unwrap();
+    assert!(!output.contains(&private));
+}
+
+/// - Case:     User audits the keypairs, then imports the same keypair under another name and audits them again
+/// - Expect:   No issue at first, and then the duplicate public key is reported with a non-zero exit code
+/// - Command:  ./pchain_client keys audit
+#[test]
+#[serial]
+fn test_keys_audit() {
+    let env = TestEnv::new();
+
+    let mut osrng = OsRng {};
+    let keypair = Keypair::generate(&mut osrng);
+    let private = base64url::encode(keypair.as_bytes());
+    let public = base64url::encode(keypair.verifying_key().as_bytes());
+    let import = |keypair_name: &str| {
+        let output = Command::new(&env.bin)
+            .arg("keys")
+            .arg("import")
+            .arg("--public")
+            .arg(&public)
+            .arg("--private")
+            .arg(&private)
+            .arg("--keypair-name")
+            .arg(keypair_name)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+    };
+
+    import("first");
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("audit")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["No issue is found in 1 keypairs."], &output).unwrap();
+
+    import("second");
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("audit")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &[
+            &format!(
+                "Keypairs first, second have the same public key {}.",
+                public
+            ),
+            "Error: 1 issue\\(s\\) are found in 2 keypairs.",
+        ],
+        &output,
+    )
+    .unwrap();
+}
+
+/// - Case:     User lists keypairs with `--status-line`, then runs a command with a missing argument, a command
+///             which fails, and a command given `--status-line` as the value of an option
+/// - Expect:   A trailing status line on stderr, derived from the exit code of the command, also for usage errors.
+///             No status line for an option value
+/// - Command:  ./pchain_client keys list --status-line
+#[test]
+#[serial]
+fn test_keys_status_line() {
+    let env = TestEnv::new();
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("list")
+        .arg("--status-line")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "STATUS ok\n");
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("show")
+        .arg("--status-line")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).ends_with("STATUS error code=2\n"));
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("show")
+        .arg("--keypair-name")
+        .arg("missing")
+        .arg("--status-line")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let code = output.status.code().unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        format!("STATUS error code={code}\n")
+    );
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("sign")
+        .arg("--keypair-name")
+        .arg("missing")
+        .arg("--message")
+        .arg("--status-line")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+}
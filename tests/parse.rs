@@ -1,6 +1,8 @@
 use std::process::Command;
 
 use common::{expect_output, TestEnv};
+use pchain_types::cryptography::Keypair;
+use rand_chacha::rand_core::OsRng;
 use serial_test::serial;
 
 mod common;
@@ -58,6 +60,30 @@ fn test_parse_base64_encoding_encode() {
     assert_eq!(&output, "AAECAw\n")
 }
 
+/// - Case:     User parses on first run, without password setup
+/// - Expect:   Show the result without prompting for password or creating the hash file
+/// - Command:  ./pchain_client parse base64-encoding --encode --value <VALUE>
+#[test]
+#[serial]
+fn test_parse_without_password_setup() {
+    let env = TestEnv::new();
+    let hash_file = env.cli_home.path().join("hash");
+    std::fs::remove_file(&hash_file).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("parse")
+        .arg("base64-encoding")
+        .arg("--encode")
+        .arg("--value")
+        .arg("[0, 1, 2, 3]")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    assert_eq!(&output, "AAECAw\n");
+    assert!(!hash_file.exists());
+}
+
 /// - Case:     User parses base64url string input into decoded bytes
 /// - Expect:   Show the result of decoded bytes
 /// - Command:  ./pchain_client parse base64-encoding --decode --value <VALUE>
@@ -150,3 +176,386 @@ fn test_parse_call_result_from_schema() {
 
     assert_eq!(&output, "[0]: 0\n[1]: true\n[2]: 770\n");
 }
+
+/// - Case:     User specifies the schema inline, and parses base64url string into a decoded data
+/// - Expect:   Show the value of the decoded data
+/// - Command:  ./pchain_client parse call-result --value <VALUE> --schema-json <SCHEMA_JSON>
+#[test]
+#[serial]
+fn test_parse_call_result_from_schema_json() {
+    let env = TestEnv::new();
+
+    let output = Command::new(&env.bin)
+        .arg("parse")
+        .arg("call-result")
+        .arg("--value")
+        .arg("AAECAw") // [0, 1, 2, 3]
+        .arg("--schema-json")
+        .arg(r#"[{"argument_type": "u8"}, {"argument_type": "bool"}, {"argument_type": "u16"}]"#)
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    assert_eq!(&output, "[0]: 0\n[1]: true\n[2]: 770\n");
+}
+
+/// - Case:     User specifies a schema file which is valid JSON but not a valid schema
+/// - Expect:   Error message pointing at the malformed node
+/// - Command:  ./pchain_client parse call-result --value <VALUE> --schema-file <SCHEMA_FILE>
+#[test]
+#[serial]
+fn test_parse_call_result_from_malformed_schema() {
+    let env = TestEnv::new();
+    let test_file = env.add_file(
+        "test.json",
+        serde_json::json!([
+            {"argument_type": "u8"},
+            {"argument_name": "flag", "type": "bool"},
+        ])
+        .to_string()
+        .as_bytes(),
+    );
+
+    let output = Command::new(&env.bin)
+        .arg("parse")
+        .arg("call-result")
+        .arg("--value")
+        .arg("AAECAw") // [0, 1, 2, 3]
+        .arg("--schema-file")
+        .arg(test_file.as_os_str().to_str().unwrap())
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    assert_eq!(
+        &output,
+        "Error: Invalid call result schema at `$[1]`. Missing field `argument_type`.\n"
+    );
+}
+
+/// - Case:     User decodes a borsh serialized value with an inline schema, and then with a schema which does not fit it
+/// - Expect:   Show the value of the decoded data, and then an error message
+/// - Command:  ./pchain_client parse decode --value <VALUE> --schema-json <SCHEMA_JSON>
+#[test]
+#[serial]
+fn test_parse_decode() {
+    let env = TestEnv::new();
+
+    let output = Command::new(&env.bin)
+        .arg("parse")
+        .arg("decode")
+        .arg("--value")
+        .arg("AAECAw") // [0, 1, 2, 3]
+        .arg("--schema-json")
+        .arg(r#"[{"argument_name": "version", "argument_type": "u8"}, {"argument_type": "bool"}, {"argument_type": "u16"}]"#)
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    assert_eq!(&output, "version: 0\n[1]: true\n[2]: 770\n");
+
+    let output = Command::new(&env.bin)
+        .arg("parse")
+        .arg("decode")
+        .arg("--value")
+        .arg("AAE") // [0, 1]
+        .arg("--schema-json")
+        .arg(r#"[{"argument_type": "u8"}, {"argument_type": "u64"}]"#)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    assert!(output.starts_with("Error: Cannot decode the value with the schema."));
+}
+
+/// - Case:     User lists the supported data types
+/// - Expect:   Both call argument and call result types are shown
+/// - Command:  ./pchain_client parse list-types
+#[test]
+#[serial]
+fn test_parse_list_types() {
+    let env = TestEnv::new();
+
+    let output = Command::new(&env.bin)
+        .arg("parse")
+        .arg("list-types")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    let (argument_types, result_types) = output.split_once("\n\n").unwrap();
+    assert!(argument_types.contains("\n  Vec<Vec<String>>\n"));
+    assert!(argument_types.contains("\n  Custom\n"));
+    assert!(result_types.contains("\n  [u8;32]\n"));
+    assert!(!result_types.contains("Vec<Vec<String>>"));
+}
+
+/// - Case:     User inspects a valid 64-byte keypair
+/// - Expect:   Show the derived public key and validity
+/// - Command:  ./pchain_client parse keypair-info --keypair <KEYPAIR>
+#[test]
+#[serial]
+fn test_parse_keypair_info() {
+    let env = TestEnv::new();
+
+    let mut osrng = OsRng {};
+    let keypair = Keypair::generate(&mut osrng);
+    let public = base64url::encode(keypair.verifying_key().as_bytes());
+
+    let output = Command::new(&env.bin)
+        .arg("parse")
+        .arg("keypair-info")
+        .arg("--keypair")
+        .arg(base64url::encode(keypair.to_keypair_bytes()))
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&[&format!("Public Key: {public}"), "Valid: true"], &output).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("parse")
+        .arg("keypair-info")
+        .arg("--keypair")
+        .arg(base64url::encode([1u8; 32]))
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["Error: Invalid Ed25519 keypair."], &output).unwrap();
+}
+
+/// - Case:     User inspects a keypair with a non-default output encoding
+/// - Expect:   Show the public key in hexadecimal or standard Base64
+/// - Command:  ./pchain_client parse keypair-info --keypair <KEYPAIR> --output-encoding <hex|base64>
+#[test]
+#[serial]
+fn test_parse_keypair_info_output_encoding() {
+    let env = TestEnv::new();
+
+    let mut osrng = OsRng {};
+    let keypair = Keypair::generate(&mut osrng);
+    let public_hex: String = keypair
+        .verifying_key()
+        .as_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+
+    let output = Command::new(&env.bin)
+        .arg("parse")
+        .arg("keypair-info")
+        .arg("--keypair")
+        .arg(base64url::encode(keypair.to_keypair_bytes()))
+        .arg("--output-encoding")
+        .arg("hex")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&[&format!("Public Key: {public_hex}\n")], &output).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("parse")
+        .arg("keypair-info")
+        .arg("--keypair")
+        .arg(base64url::encode(keypair.to_keypair_bytes()))
+        .arg("--output-encoding")
+        .arg("base64")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    // a 32-byte public key is 43 Base64 characters followed by one padding character
+    expect_output(&["Public Key: [A-Za-z0-9+/]{43}=\n"], &output).unwrap();
+}
+
+/// - Case:     User inspects the serialized bytes of call arguments
+/// - Expect:   Show the length, hex and base64 encoding of each argument
+/// - Command:  ./pchain_client parse call-arguments --file <FILE>
+#[test]
+#[serial]
+fn test_parse_call_arguments() {
+    let env = TestEnv::new();
+    let arguments_path = env.add_file(
+        "arguments.json",
+        br#"{"arguments": [
+            {"argument_type": "u32", "argument_value": "1"},
+            {"argument_type": "String", "argument_value": "\"ab\""}
+        ]}"#,
+    );
+
+    let output = Command::new(&env.bin)
+        .arg("parse")
+        .arg("call-arguments")
+        .arg("--file")
+        .arg(arguments_path.to_str().unwrap())
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &[
+            r#""argument_type": "u32""#,
+            r#""length": 4"#,
+            r#""hex": "01000000""#,
+            r#""argument_type": "String""#,
+            r#""length": 6"#,
+            r#""hex": "020000006162""#,
+        ],
+        &output,
+    )
+    .unwrap();
+}
+
+/// - Case:     User parses the contract addresses of the deploy commands in a transaction file
+/// - Expect:   Ask for the signer, and show the contract address of each deploy command once it is provided, derived
+///             from the signer, the nonce and the index of the command
+/// - Command:  ./pchain_client parse contract-address from-tx --file <FILE> [--keypair-name <KEYPAIR_NAME>]
+#[test]
+#[serial]
+fn test_parse_contract_address_from_tx() {
+    let env = TestEnv::new().with_rpc_config();
+    let contract_path = env.add_file("contract.wasm", b"\0asm\x01\0\0\0");
+    let tx_path = env.cli_home.path().join("tx.json");
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("create")
+        .arg("--destination")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--v2")
+        .arg("--nonce")
+        .arg("3")
+        .arg("--gas-limit")
+        .arg("100000000")
+        .arg("--max-base-fee-per-gas")
+        .arg("8")
+        .arg("--priority-fee-per-gas")
+        .arg("0")
+        .arg("deploy")
+        .arg("--contract-code")
+        .arg(contract_path.to_str().unwrap())
+        .arg("--cbi-version")
+        .arg("0")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["Successfully create Transaction file"], &output).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("parse")
+        .arg("contract-address")
+        .arg("from-tx")
+        .arg("--file")
+        .arg(tx_path.to_str().unwrap())
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["does not record the signer"], &output).unwrap();
+
+    // a fixed keypair, so that the contract address is known: SHA256(signer | nonce (u64 LE) | index (u32 LE))
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("import")
+        .arg("--public")
+        .arg("ebVWLo_mVPlAeLES6KmLp5AfhTrmlb7X4OORC60ElmQ")
+        .arg("--private")
+        .arg("AQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eHyA")
+        .arg("--keypair-name")
+        .arg("deployer")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["Successfully add keypair with name deployer."], &output).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("parse")
+        .arg("contract-address")
+        .arg("from-tx")
+        .arg("--file")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--keypair-name")
+        .arg("deployer")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &["Command 0: Contract Address: O9D4S8808UR5OjEs_07DG6H7MZ8L-_HY65vO0s6FJPo"],
+        &output,
+    )
+    .unwrap();
+}
+
+/// - Case:     User parses a signed transaction
+/// - Expect:   Display the decoded transaction with its signer and nonce
+/// - Command:  ./pchain_client parse transaction --value <VALUE>
+#[test]
+#[serial]
+fn test_parse_transaction() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.signed");
+
+    let mut osrng = OsRng {};
+    let keypair = Keypair::generate(&mut osrng);
+    let public = base64url::encode(keypair.verifying_key().as_bytes());
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("import")
+        .arg("--public")
+        .arg(&public)
+        .arg("--private")
+        .arg(base64url::encode(keypair.as_bytes()))
+        .arg("--keypair-name")
+        .arg("testkey")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&["Successfully add keypair with name testkey."], &output).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("create")
+        .arg("--destination")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--sign-with")
+        .arg("testkey")
+        .arg("--v2")
+        .arg("--nonce")
+        .arg("7")
+        .arg("--gas-limit")
+        .arg("100000000")
+        .arg("--max-base-fee-per-gas")
+        .arg("8")
+        .arg("--priority-fee-per-gas")
+        .arg("0")
+        .arg("transfer")
+        .arg("--recipient")
+        .arg(&public)
+        .arg("--amount")
+        .arg("100")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&["Successfully create Signed transaction file"], &output).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("parse")
+        .arg("transaction")
+        .arg("--value")
+        .arg(base64url::encode(std::fs::read(&tx_path).unwrap()))
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    let tx: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(tx["signer"], public);
+    assert_eq!(tx["nonce"], 7);
+}
@@ -0,0 +1,1364 @@
+use pchain_types::{cryptography::Keypair, rpc::TransactionV1OrV2, serialization::Deserializable};
+use rand_chacha::rand_core::OsRng;
+use serial_test::serial;
+use std::process::Command;
+
+use crate::common::{create_transfer_tx, expect_output, TestEnv};
+
+mod common;
+
+/// - Case:     User creates a deploy transaction with a file which is not a WebAssembly module
+/// - Expect:   Transaction file is not created. Display error of invalid WebAssembly module
+/// - Command:  ./pchain_client transaction create --v2 ... deploy --contract-code <CONTRACT_CODE> --cbi-version <CBI_VERSION>
+#[test]
+#[serial]
+fn test_transaction_create_deploy_invalid_wasm() {
+    let env = TestEnv::new().with_rpc_config();
+    let contract_path = env.add_file("contract.wasm", b"not a wasm module");
+    let tx_path = env.cli_home.path().join("tx.json");
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("create")
+        .arg("--destination")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--v2")
+        .arg("--nonce")
+        .arg("0")
+        .arg("--gas-limit")
+        .arg("100000000")
+        .arg("--max-base-fee-per-gas")
+        .arg("8")
+        .arg("--priority-fee-per-gas")
+        .arg("0")
+        .arg("deploy")
+        .arg("--contract-code")
+        .arg(contract_path.to_str().unwrap())
+        .arg("--cbi-version")
+        .arg("0")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["is not a valid WebAssembly module"], &output).unwrap();
+    assert!(!tx_path.exists());
+}
+
+/// - Case:     User creates a deploy transaction with a WebAssembly module
+/// - Expect:   Transaction file is created
+/// - Command:  ./pchain_client transaction create --v2 ... deploy --contract-code <CONTRACT_CODE> --cbi-version <CBI_VERSION>
+#[test]
+#[serial]
+fn test_transaction_create_deploy() {
+    let env = TestEnv::new().with_rpc_config();
+    let contract_path = env.add_file("contract.wasm", b"\0asm\x01\0\0\0");
+    let tx_path = env.cli_home.path().join("tx.json");
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("create")
+        .arg("--destination")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--v2")
+        .arg("--nonce")
+        .arg("0")
+        .arg("--gas-limit")
+        .arg("100000000")
+        .arg("--max-base-fee-per-gas")
+        .arg("8")
+        .arg("--priority-fee-per-gas")
+        .arg("0")
+        .arg("deploy")
+        .arg("--contract-code")
+        .arg(contract_path.to_str().unwrap())
+        .arg("--cbi-version")
+        .arg("0")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["Successfully create Transaction file"], &output).unwrap();
+    assert!(tx_path.exists());
+}
+
+/// - Case:     User creates a deploy transaction with an unsupported CBI version and `--cbi-check`
+/// - Expect:   Transaction file is not created without `--force`. Display warning of unsupported CBI version
+/// - Command:  ./pchain_client transaction create --v2 ... deploy --contract-code <CONTRACT_CODE> --cbi-version <CBI_VERSION> --cbi-check
+#[test]
+#[serial]
+fn test_transaction_create_deploy_cbi_check() {
+    let env = TestEnv::new().with_rpc_config();
+    let contract_path = env.add_file("contract.wasm", b"\0asm\x01\0\0\0");
+    let tx_path = env.cli_home.path().join("tx.json");
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("create")
+        .arg("--destination")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--v2")
+        .arg("--nonce")
+        .arg("0")
+        .arg("--gas-limit")
+        .arg("100000000")
+        .arg("--max-base-fee-per-gas")
+        .arg("8")
+        .arg("--priority-fee-per-gas")
+        .arg("0")
+        .arg("deploy")
+        .arg("--contract-code")
+        .arg(contract_path.to_str().unwrap())
+        .arg("--cbi-version")
+        .arg("99")
+        .arg("--cbi-check")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["Warning: CBI version 99 is not supported"], &output).unwrap();
+    assert!(!tx_path.exists());
+}
+
+/// - Case:     User creates a transfer transaction and signs it with an imported keypair
+/// - Expect:   Borsh-serialized signed transaction is saved, with the keypair as signer
+/// - Command:  ./pchain_client transaction create --sign-with <KEYPAIR_NAME> --v2 ... transfer --recipient <RECIPIENT> --amount <AMOUNT>
+#[test]
+#[serial]
+fn test_transaction_create_sign_with() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.signed");
+
+    let mut osrng = OsRng {};
+    let keypair = Keypair::generate(&mut osrng);
+    let public = base64url::encode(keypair.verifying_key().as_bytes());
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("import")
+        .arg("--public")
+        .arg(&public)
+        .arg("--private")
+        .arg(base64url::encode(keypair.as_bytes()))
+        .arg("--keypair-name")
+        .arg("testkey")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["Successfully add keypair with name testkey."], &output).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("create")
+        .arg("--destination")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--sign-with")
+        .arg("testkey")
+        .arg("--v2")
+        .arg("--nonce")
+        .arg("0")
+        .arg("--gas-limit")
+        .arg("100000000")
+        .arg("--max-base-fee-per-gas")
+        .arg("8")
+        .arg("--priority-fee-per-gas")
+        .arg("0")
+        .arg("transfer")
+        .arg("--recipient")
+        .arg(&public)
+        .arg("--amount")
+        .arg("100")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["Successfully create Signed transaction file"], &output).unwrap();
+
+    let signed_tx = TransactionV1OrV2::deserialize(&std::fs::read(&tx_path).unwrap()).unwrap();
+    match signed_tx {
+        TransactionV1OrV2::V2(tx) => {
+            assert_eq!(tx.signer, keypair.verifying_key().to_bytes());
+            assert_eq!(tx.nonce, 0);
+        }
+        TransactionV1OrV2::V1(_) => panic!("expected TransactionV2"),
+    }
+}
+
+/// - Case:     User creates call transactions from a directory of call argument files, and then from the largest nonce
+/// - Expect:   One transaction file is created per argument file, with incrementing nonces. The file whose nonce would
+///             not fit in 64 bits is reported instead of wrapping around
+/// - Command:  ./pchain_client transaction create --v2 ... call --target <TARGET> --method <METHOD> --arguments <DIRECTORY>
+#[test]
+#[serial]
+fn test_transaction_create_call_arguments_dir() {
+    let env = TestEnv::new().with_rpc_config();
+    let arguments_dir = env.cli_home.path().join("arguments");
+    let output_dir = env.cli_home.path().join("txs");
+    std::fs::create_dir(&arguments_dir).unwrap();
+    std::fs::create_dir(&output_dir).unwrap();
+    for (file_name, value) in [("a.json", "1"), ("b.json", "2")] {
+        let arguments = serde_json::json!({
+            "arguments": [{"argument_type": "u32", "argument_value": value}]
+        });
+        std::fs::write(arguments_dir.join(file_name), arguments.to_string()).unwrap();
+    }
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("create")
+        .arg("--destination")
+        .arg(output_dir.to_str().unwrap())
+        .arg("--v2")
+        .arg("--nonce")
+        .arg("5")
+        .arg("--gas-limit")
+        .arg("100000000")
+        .arg("--max-base-fee-per-gas")
+        .arg("8")
+        .arg("--priority-fee-per-gas")
+        .arg("0")
+        .arg("call")
+        .arg("--target")
+        .arg("kRPL8cXI73DNgVSSQz9WfIi-mAAvFvdXKfZ9UPBEv_A")
+        .arg("--method")
+        .arg("set_value")
+        .arg("--arguments")
+        .arg(arguments_dir.to_str().unwrap())
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["a_tx.json", "b_tx.json"], &output).unwrap();
+
+    for (file_name, nonce) in [("a_tx.json", 5), ("b_tx.json", 6)] {
+        let tx = std::fs::read_to_string(output_dir.join(file_name)).unwrap();
+        let tx: serde_json::Value = serde_json::from_str(&tx).unwrap();
+        assert_eq!(tx["nonce"], nonce);
+    }
+
+    std::fs::remove_dir_all(&output_dir).unwrap();
+    std::fs::create_dir(&output_dir).unwrap();
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("create")
+        .arg("--destination")
+        .arg(output_dir.to_str().unwrap())
+        .arg("--v2")
+        .arg("--nonce")
+        .arg(u64::MAX.to_string())
+        .arg("--gas-limit")
+        .arg("100000000")
+        .arg("--max-base-fee-per-gas")
+        .arg("8")
+        .arg("--priority-fee-per-gas")
+        .arg("0")
+        .arg("--collect-errors")
+        .arg("call")
+        .arg("--target")
+        .arg("kRPL8cXI73DNgVSSQz9WfIi-mAAvFvdXKfZ9UPBEv_A")
+        .arg("--method")
+        .arg("set_value")
+        .arg("--arguments")
+        .arg(arguments_dir.to_str().unwrap())
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&["1 of 2 items failed: .*b.json"], &output).unwrap();
+    assert!(!output_dir.join("b_tx.json").exists());
+
+    let tx = std::fs::read_to_string(output_dir.join("a_tx.json")).unwrap();
+    let tx: serde_json::Value = serde_json::from_str(&tx).unwrap();
+    assert_eq!(tx["nonce"], u64::MAX);
+}
+
+/// - Case:     User creates call transactions from a directory in which one argument file is malformed, and then with
+///             fees whose cost overflows
+/// - Expect:   `--fail-fast`, the default, stops at the malformed file. `--collect-errors` creates the others and reports
+///             the malformed one, and reports every file whose cost overflows instead of stopping at the first
+/// - Command:  ./pchain_client transaction create --v2 ... [--fail-fast | --collect-errors] call --target <TARGET> --method <METHOD> --arguments <DIRECTORY>
+#[test]
+#[serial]
+fn test_transaction_create_batch_error_policy() {
+    let env = TestEnv::new().with_rpc_config();
+    let arguments_dir = env.cli_home.path().join("arguments");
+    let output_dir = env.cli_home.path().join("txs");
+    std::fs::create_dir(&arguments_dir).unwrap();
+    std::fs::create_dir(&output_dir).unwrap();
+    for (file_name, value) in [("a.json", "1"), ("b.json", "not a number"), ("c.json", "3")] {
+        let arguments = serde_json::json!({
+            "arguments": [{"argument_type": "u32", "argument_value": value}]
+        });
+        std::fs::write(arguments_dir.join(file_name), arguments.to_string()).unwrap();
+    }
+
+    let create_txs_with_fee = |policy: &[&str], max_base_fee_per_gas: &str| {
+        Command::new(&env.bin)
+            .arg("transaction")
+            .arg("create")
+            .arg("--destination")
+            .arg(output_dir.to_str().unwrap())
+            .arg("--v2")
+            .arg("--nonce")
+            .arg("5")
+            .arg("--gas-limit")
+            .arg("100000000")
+            .arg("--max-base-fee-per-gas")
+            .arg(max_base_fee_per_gas)
+            .arg("--priority-fee-per-gas")
+            .arg("0")
+            .args(policy)
+            .arg("call")
+            .arg("--target")
+            .arg("kRPL8cXI73DNgVSSQz9WfIi-mAAvFvdXKfZ9UPBEv_A")
+            .arg("--method")
+            .arg("set_value")
+            .arg("--arguments")
+            .arg(arguments_dir.to_str().unwrap())
+            .output()
+            .unwrap()
+    };
+    let create_txs = |policy: &[&str]| create_txs_with_fee(policy, "8");
+
+    for policy in [&[][..], &["--fail-fast"][..]] {
+        std::fs::remove_dir_all(&output_dir).unwrap();
+        std::fs::create_dir(&output_dir).unwrap();
+        let output = create_txs(policy);
+        assert!(!output.status.success());
+        assert!(output_dir.join("a_tx.json").exists());
+        assert!(!output_dir.join("c_tx.json").exists());
+    }
+
+    let output = create_txs_with_fee(&["--collect-errors"], &u64::MAX.to_string());
+    assert!(!output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&["3 of 3 items failed: "], &output).unwrap();
+
+    let output = create_txs(&["--collect-errors"]);
+    assert!(!output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&["c_tx.json", "1 of 3 items failed: .*b.json"], &output).unwrap();
+    assert!(!output_dir.join("b_tx.json").exists());
+
+    let tx = std::fs::read_to_string(output_dir.join("c_tx.json")).unwrap();
+    let tx: serde_json::Value = serde_json::from_str(&tx).unwrap();
+    assert_eq!(tx["nonce"], 7);
+}
+
+/// - Case:     User creates a call transaction with `--verbose`
+/// - Expect:   Transaction file is created. Display the type, value and serialized bytes of each argument
+/// - Command:  ./pchain_client transaction create --v2 ... call --target <TARGET> --method <METHOD> --arguments <FILE> --verbose
+#[test]
+#[serial]
+fn test_transaction_create_call_verbose() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.json");
+    let arguments = serde_json::json!({
+        "arguments": [
+            {"argument_type": "u32", "argument_value": "1"},
+            {"argument_type": "String", "argument_value": "\"ab\""},
+        ]
+    });
+    let arguments_path = env.add_file("arguments.json", arguments.to_string().as_bytes());
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("create")
+        .arg("--destination")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--v2")
+        .arg("--nonce")
+        .arg("0")
+        .arg("--gas-limit")
+        .arg("100000000")
+        .arg("--max-base-fee-per-gas")
+        .arg("8")
+        .arg("--priority-fee-per-gas")
+        .arg("0")
+        .arg("call")
+        .arg("--target")
+        .arg("kRPL8cXI73DNgVSSQz9WfIi-mAAvFvdXKfZ9UPBEv_A")
+        .arg("--method")
+        .arg("set_value")
+        .arg("--arguments")
+        .arg(arguments_path.to_str().unwrap())
+        .arg("--verbose")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &[
+            "Successfully create Transaction file",
+            r#""argument_type": "u32""#,
+            r#""argument_value": "1""#,
+            r#""hex": "01000000""#,
+            r#""argument_type": "String""#,
+            r#""hex": "020000006162""#,
+        ],
+        &output,
+    )
+    .unwrap();
+}
+
+/// - Case:     User creates a transaction with zero max base fee per gas
+/// - Expect:   Transaction file is not created unless `--allow-low-base-fee` is given
+/// - Command:  ./pchain_client transaction create --max-base-fee-per-gas 0 [--allow-low-base-fee] ... transfer --recipient <RECIPIENT> --amount <AMOUNT>
+#[test]
+#[serial]
+fn test_transaction_create_zero_base_fee() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.json");
+
+    let create_tx = |allow_low_base_fee: bool| {
+        let mut command = Command::new(&env.bin);
+        command
+            .arg("transaction")
+            .arg("create")
+            .arg("--destination")
+            .arg(tx_path.to_str().unwrap())
+            .arg("--v2")
+            .arg("--nonce")
+            .arg("0")
+            .arg("--gas-limit")
+            .arg("100000000")
+            .arg("--max-base-fee-per-gas")
+            .arg("0")
+            .arg("--priority-fee-per-gas")
+            .arg("0");
+        if allow_low_base_fee {
+            command.arg("--allow-low-base-fee");
+        }
+        let output = command
+            .arg("transfer")
+            .arg("--recipient")
+            .arg("kRPL8cXI73DNgVSSQz9WfIi-mAAvFvdXKfZ9UPBEv_A")
+            .arg("--amount")
+            .arg("100")
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let output = create_tx(false);
+    expect_output(&["is below the minimum of 1 Grays"], &output).unwrap();
+    assert!(!tx_path.exists());
+
+    let output = create_tx(true);
+    expect_output(&["Successfully create Transaction file"], &output).unwrap();
+    assert!(tx_path.exists());
+}
+
+/// - Case:     User forces a transaction with zero max base fee per gas, with and without `--abort-on-warning`
+/// - Expect:   A warning is displayed. Transaction file is not created under `--abort-on-warning`, and the program exits with non-zero code
+/// - Command:  ./pchain_client transaction create --max-base-fee-per-gas 0 --allow-low-base-fee [--abort-on-warning] ... transfer --recipient <RECIPIENT> --amount <AMOUNT>
+#[test]
+#[serial]
+fn test_transaction_create_abort_on_warning() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.json");
+
+    let create_tx = |abort_on_warning: bool| {
+        let mut command = Command::new(&env.bin);
+        if abort_on_warning {
+            command.arg("--abort-on-warning");
+        }
+        command
+            .arg("transaction")
+            .arg("create")
+            .arg("--destination")
+            .arg(tx_path.to_str().unwrap())
+            .arg("--v2")
+            .arg("--nonce")
+            .arg("0")
+            .arg("--gas-limit")
+            .arg("100000000")
+            .arg("--max-base-fee-per-gas")
+            .arg("0")
+            .arg("--priority-fee-per-gas")
+            .arg("0")
+            .arg("--allow-low-base-fee")
+            .arg("transfer")
+            .arg("--recipient")
+            .arg("kRPL8cXI73DNgVSSQz9WfIi-mAAvFvdXKfZ9UPBEv_A")
+            .arg("--amount")
+            .arg("100")
+            .output()
+            .unwrap()
+    };
+
+    let output = create_tx(true);
+    assert!(!output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(
+        &[
+            r"Warning: max_base_fee_per_gas \(0\) is below the minimum of 1 Grays",
+            "Aborted on the warning above because of `--abort-on-warning`",
+        ],
+        &output,
+    )
+    .unwrap();
+    assert!(!tx_path.exists());
+
+    let output = create_tx(false);
+    assert!(output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(
+        &[
+            r"Warning: max_base_fee_per_gas \(0\) is below the minimum of 1 Grays",
+            "Successfully create Transaction file",
+        ],
+        &output,
+    )
+    .unwrap();
+    assert!(tx_path.exists());
+}
+
+/// - Case:     User merges a Transaction file into another one
+/// - Expect:   Commands of the source file are appended to the target file. Files of different versions are not merged
+/// - Command:  ./pchain_client transaction merge --into <INTO> --from <FROM>
+#[test]
+#[serial]
+fn test_transaction_merge() {
+    let env = TestEnv::new().with_rpc_config();
+    let into_path = env.cli_home.path().join("into.json");
+    let from_path = env.cli_home.path().join("from.json");
+    let v1_path = env.cli_home.path().join("v1.json");
+
+    for (path, version) in [
+        (&into_path, "--v2"),
+        (&from_path, "--v2"),
+        (&v1_path, "--v1"),
+    ] {
+        create_transfer_tx(&env, path, &[version]);
+    }
+
+    let merge = |from: &std::path::Path| {
+        let output = Command::new(&env.bin)
+            .arg("transaction")
+            .arg("merge")
+            .arg("--into")
+            .arg(into_path.to_str().unwrap())
+            .arg("--from")
+            .arg(from.to_str().unwrap())
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let output = merge(&from_path);
+    expect_output(&["Successfully update Transaction file"], &output).unwrap();
+    let tx: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&into_path).unwrap()).unwrap();
+    assert_eq!(tx["commands"].as_array().unwrap().len(), 2);
+
+    let output = merge(&v1_path);
+    expect_output(&["different transaction versions"], &output).unwrap();
+    let tx: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&into_path).unwrap()).unwrap();
+    assert_eq!(tx["commands"].as_array().unwrap().len(), 2);
+}
+
+/// - Case:     User submits a Transaction file which nonce is not the expected nonce
+/// - Expect:   Transaction is not submitted. Display error of unexpected nonce
+/// - Command:  ./pchain_client transaction submit --file <FILE> --keypair-name <KEYPAIR_NAME> --expected-nonce <EXPECTED_NONCE>
+#[test]
+#[serial]
+fn test_transaction_submit_unexpected_nonce() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.json");
+
+    create_transfer_tx(&env, &tx_path, &["--nonce", "3"]);
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("submit")
+        .arg("--file")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--keypair-name")
+        .arg("testkey")
+        .arg("--expected-nonce")
+        .arg("4")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &[r"Nonce of the transaction \(3\) is not the expected nonce \(4\)"],
+        &output,
+    )
+    .unwrap();
+}
+
+/// - Case:     User creates a Transaction which maximum cost does not fit in 64 bits
+/// - Expect:   Transaction file is not created. Display error of overflowing maximum fee or cost
+/// - Command:  ./pchain_client transaction create --v2 ... transfer --recipient <RECIPIENT> --amount <AMOUNT>
+#[test]
+#[serial]
+fn test_transaction_create_cost_overflow() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.json");
+
+    let create = |max_base_fee_per_gas: &str, amount: &str| {
+        let output = Command::new(&env.bin)
+            .arg("transaction")
+            .arg("create")
+            .arg("--destination")
+            .arg(tx_path.to_str().unwrap())
+            .arg("--v2")
+            .arg("--nonce")
+            .arg("0")
+            .arg("--gas-limit")
+            .arg("100000000")
+            .arg("--max-base-fee-per-gas")
+            .arg(max_base_fee_per_gas)
+            .arg("--priority-fee-per-gas")
+            .arg("0")
+            .arg("transfer")
+            .arg("--recipient")
+            .arg("kRPL8cXI73DNgVSSQz9WfIi-mAAvFvdXKfZ9UPBEv_A")
+            .arg("--amount")
+            .arg(amount)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let output = create("1000000000000", "100");
+    expect_output(&["The maximum fee, gas_limit \\(100000000\\)"], &output).unwrap();
+
+    let output = create("8", &u64::MAX.to_string());
+    expect_output(&["plus the maximum fee \\(800000000\\) is larger"], &output).unwrap();
+    assert!(!tx_path.exists());
+}
+
+/// - Case:     User submits a Transaction file with `--wait-receipt --decode-with` a malformed call result schema
+/// - Expect:   Transaction is not submitted. Display error of invalid call result schema
+/// - Command:  ./pchain_client transaction submit --file <FILE> --keypair-name <KEYPAIR_NAME> --wait-receipt --decode-with <SCHEMA_FILE>
+#[test]
+#[serial]
+fn test_transaction_submit_decode_with_malformed_schema() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.json");
+    let schema_path = env.add_file(
+        "schema.json",
+        serde_json::json!([
+            {"argument_type": "u8"},
+            {"argument_name": "flag", "type": "bool"},
+        ])
+        .to_string()
+        .as_bytes(),
+    );
+
+    create_transfer_tx(&env, &tx_path, &[]);
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("submit")
+        .arg("--file")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--keypair-name")
+        .arg("testkey")
+        .arg("--wait-receipt")
+        .arg("--decode-with")
+        .arg(schema_path.to_str().unwrap())
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &[r"Error: Invalid call result schema at `\$\[1\]`. Missing field `argument_type`."],
+        &output,
+    )
+    .unwrap();
+}
+
+/// - Case:     User submits a Transaction file with both `--hash-only` and `--wait-receipt`
+/// - Expect:   The options are refused together, as `--hash-only` prints nothing but the transaction hash
+/// - Command:  ./pchain_client transaction submit --file <FILE> --keypair-name <KEYPAIR_NAME> --hash-only --wait-receipt
+#[test]
+#[serial]
+fn test_transaction_submit_hash_only_with_wait_receipt() {
+    let env = TestEnv::new();
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("submit")
+        .arg("--file")
+        .arg("tx.json")
+        .arg("--keypair-name")
+        .arg("testkey")
+        .arg("--hash-only")
+        .arg("--wait-receipt")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let output = String::from_utf8_lossy(&output.stderr).to_string();
+    expect_output(&["cannot be used with"], &output).unwrap();
+}
+
+/// - Case:     User submits a Transaction file with an expected signer which is not the address of the keypair
+/// - Expect:   Transaction is not signed nor submitted. Display error of unexpected signer
+/// - Command:  ./pchain_client transaction submit --file <FILE> --keypair-name <KEYPAIR_NAME> --expected-signer <ADDRESS>
+#[test]
+#[serial]
+fn test_transaction_submit_unexpected_signer() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.json");
+
+    let mut osrng = OsRng {};
+    let keypair = Keypair::generate(&mut osrng);
+    let signer = base64url::encode(keypair.verifying_key().as_bytes());
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("import")
+        .arg("--public")
+        .arg(&signer)
+        .arg("--private")
+        .arg(base64url::encode(keypair.as_bytes()))
+        .arg("--keypair-name")
+        .arg("signer")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&["Successfully add keypair"], &output).unwrap();
+
+    create_transfer_tx(&env, &tx_path, &[]);
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("submit")
+        .arg("--file")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--keypair-name")
+        .arg("signer")
+        .arg("--expected-signer")
+        .arg("kRPL8cXI73DNgVSSQz9WfIi-mAAvFvdXKfZ9UPBEv_A")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &[&format!(
+            "Keypair <signer> has address {signer}, not the expected signer kRPL8cXI73DNgVSSQz9WfIi-mAAvFvdXKfZ9UPBEv_A"
+        )],
+        &output,
+    )
+    .unwrap();
+}
+
+/// - Case:     User creates a Transaction file with a note, and shows the file
+/// - Expect:   The note is saved in the file and displayed by `transaction show`
+/// - Command:
+///   - ./pchain_client transaction create --note <TEXT> ... transfer --recipient <RECIPIENT> --amount <AMOUNT>
+///   - ./pchain_client transaction show --file <FILE>
+#[test]
+#[serial]
+fn test_transaction_create_note() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.json");
+
+    create_transfer_tx(&env, &tx_path, &["--note", "rent for March"]);
+
+    let tx: serde_json::Value = serde_json::from_slice(&std::fs::read(&tx_path).unwrap()).unwrap();
+    assert_eq!(tx["note"], "rent for March");
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("show")
+        .arg("--file")
+        .arg(tx_path.to_str().unwrap())
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&[r#""note": "rent for March""#], &output).unwrap();
+}
+
+/// - Case:     User submits a Transaction file whose recipient addresses were edited into invalid values
+/// - Expect:   Every invalid address is reported with its command index, and the Transaction is not submitted
+/// - Command:  ./pchain_client transaction submit --file <FILE> --keypair-name <NAME>
+#[test]
+#[serial]
+fn test_transaction_submit_invalid_addresses() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.json");
+
+    create_transfer_tx(&env, &tx_path, &[]);
+
+    let mut tx: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&tx_path).unwrap()).unwrap();
+    let commands = tx["commands"].as_array_mut().unwrap();
+    let valid_command = commands[0].clone();
+    commands[0]["Transfer"]["recipient"] = "AAAAAAAAAAAAAAAAAAAAAA".into();
+    commands.push(valid_command.clone());
+    commands.push(valid_command);
+    commands[2]["Transfer"]["recipient"] = "not*base64url".into();
+    std::fs::write(&tx_path, serde_json::to_vec(&tx).unwrap()).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("submit")
+        .arg("--file")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--keypair-name")
+        .arg("testkey")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &[
+            "Error: The transaction file contains invalid addresses. Transaction is not submitted.",
+            "Command 0: recipient <AAAAAAAAAAAAAAAAAAAAAA> decodes to 16 bytes instead of 32",
+            "Command 2: recipient <not\\*base64url> is not valid Base64url",
+        ],
+        &output,
+    )
+    .unwrap();
+    assert!(!output.contains("Command 1:"));
+}
+
+/// - Case:     User replaces a pending Transaction with a fee below the minimum, with fees that overflow, with both
+///             `--v1` and `--file`, and with a keypair which does not exist
+/// - Expect:   Each is refused with an error message before anything is submitted
+/// - Command:  ./pchain_client transaction replace --keypair-name <NAME> --nonce <NONCE> --gas-limit <GAS_LIMIT> ...
+#[test]
+#[serial]
+fn test_transaction_replace_refused() {
+    let env = TestEnv::new().with_rpc_config();
+    let replace = |args: &[&str]| {
+        let output = Command::new(&env.bin)
+            .arg("transaction")
+            .arg("replace")
+            .arg("--keypair-name")
+            .arg("missing")
+            .arg("--nonce")
+            .arg("0")
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        String::from_utf8_lossy(&output.stdout).to_string()
+            + &String::from_utf8_lossy(&output.stderr)
+    };
+
+    let output = replace(&[
+        "--gas-limit",
+        "100000000",
+        "--max-base-fee-per-gas",
+        "0",
+        "--priority-fee-per-gas",
+        "0",
+    ]);
+    expect_output(
+        &["Error: max_base_fee_per_gas \\(0\\) is below the minimum of 1 Grays."],
+        &output,
+    )
+    .unwrap();
+
+    let output = replace(&[
+        "--gas-limit",
+        "18446744073709551615",
+        "--max-base-fee-per-gas",
+        "8",
+        "--priority-fee-per-gas",
+        "0",
+    ]);
+    expect_output(&["Error: The maximum fee, gas_limit"], &output).unwrap();
+
+    let output = replace(&[
+        "--gas-limit",
+        "100000000",
+        "--max-base-fee-per-gas",
+        "8",
+        "--priority-fee-per-gas",
+        "0",
+        "--v1",
+        "--file",
+        "tx.json",
+    ]);
+    expect_output(&["cannot be used with"], &output).unwrap();
+
+    let output = replace(&[
+        "--gas-limit",
+        "100000000",
+        "--max-base-fee-per-gas",
+        "8",
+        "--priority-fee-per-gas",
+        "0",
+    ]);
+    expect_output(&["Keypair name missing provided does not exist"], &output).unwrap();
+}
+
+/// - Case:     User removes every command from a Transaction file, then submits it and merges it with another empty one
+/// - Expect:   The Transaction is not submitted and the merged file is not written, both with an error message
+/// - Command:
+///   - ./pchain_client transaction submit --file <FILE> --keypair-name <NAME>
+///   - ./pchain_client transaction merge --into <FILE> --from <FILE>
+#[test]
+#[serial]
+fn test_transaction_without_commands() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.json");
+
+    create_transfer_tx(&env, &tx_path, &[]);
+
+    let mut tx: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&tx_path).unwrap()).unwrap();
+    tx["commands"] = serde_json::json!([]);
+    std::fs::write(&tx_path, serde_json::to_vec(&tx).unwrap()).unwrap();
+    let empty_tx = std::fs::read(&tx_path).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("submit")
+        .arg("--file")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--keypair-name")
+        .arg("testkey")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &["Error: The transaction has no commands, and would only spend gas."],
+        &output,
+    )
+    .unwrap();
+
+    let from_path = env.add_file("from.json", &empty_tx);
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("merge")
+        .arg("--into")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--from")
+        .arg(from_path.to_str().unwrap())
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &["Error: The transaction has no commands, and would only spend gas."],
+        &output,
+    )
+    .unwrap();
+    assert!(!output.contains("Successfully update"));
+}
+
+/// - Case:     User asks for an explanation of a Transaction instead of submitting it
+/// - Expect:   Signer, commands and fees are described, and the Transaction is not submitted
+/// - Command:  ./pchain_client transaction submit --file <FILE> --keypair-name <NAME> --explain
+#[test]
+#[serial]
+fn test_transaction_submit_explain() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.json");
+
+    let mut osrng = OsRng {};
+    let keypair = Keypair::generate(&mut osrng);
+    let signer = base64url::encode(keypair.verifying_key().as_bytes());
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("import")
+        .arg("--public")
+        .arg(&signer)
+        .arg("--private")
+        .arg(base64url::encode(keypair.as_bytes()))
+        .arg("--keypair-name")
+        .arg("signer")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&["Successfully add keypair"], &output).unwrap();
+
+    create_transfer_tx(&env, &tx_path, &[]);
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("submit")
+        .arg("--file")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--keypair-name")
+        .arg("signer")
+        .arg("--explain")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &[
+            &format!("would be signed by keypair <signer> with address {signer}, using nonce 0."),
+            "It runs 1 command\\(s\\) in order:",
+            "  0. Transfer 100 Grays to kRPL8cXI73DNgVSSQz9WfIi-mAAvFvdXKfZ9UPBEv_A",
+            "The commands spend 100 Grays from the balance of the signer.",
+            "Gas fees are at most 800000000 Grays",
+            "Nothing is submitted.",
+        ],
+        &output,
+    )
+    .unwrap();
+}
+
+/// - Case:     User asks for an explanation of a Transaction on first run, without password setup
+/// - Expect:   First-run password setup is started, as the address of the signer is read from the keypair file
+/// - Command:  ./pchain_client transaction submit --file <FILE> --keypair-name <NAME> --explain
+#[test]
+#[serial]
+fn test_transaction_submit_explain_without_password_setup() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.json");
+    create_transfer_tx(&env, &tx_path, &[]);
+    std::fs::remove_file(env.cli_home.path().join("hash")).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("submit")
+        .arg("--file")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--keypair-name")
+        .arg("signer")
+        .arg("--explain")
+        .stdin(std::process::Stdio::null())
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(&["First time to use ParallelChain Client CLI"], &output).unwrap();
+    assert!(!output.contains("hash file"));
+}
+
+/// - Case:     User re-submits with `--idempotent` a Transaction whose hash is in the ledger of submitted transactions
+/// - Expect:   Transaction is not submitted again and a notice is displayed
+/// - Command:
+///   - ./pchain_client keys sign-transaction --file <FILE> --keypair-name <NAME>
+///   - ./pchain_client transaction submit --file <FILE> --keypair-name <NAME> --idempotent
+#[test]
+#[serial]
+fn test_transaction_submit_idempotent() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.json");
+
+    let mut osrng = OsRng {};
+    let keypair = Keypair::generate(&mut osrng);
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("import")
+        .arg("--public")
+        .arg(base64url::encode(keypair.verifying_key().as_bytes()))
+        .arg("--private")
+        .arg(base64url::encode(keypair.as_bytes()))
+        .arg("--keypair-name")
+        .arg("signer")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&["Successfully add keypair"], &output).unwrap();
+
+    create_transfer_tx(&env, &tx_path, &[]);
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("sign-transaction")
+        .arg("--file")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--keypair-name")
+        .arg("signer")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    let tx_hash = output
+        .lines()
+        .find_map(|line| line.strip_prefix("Transaction hash: "))
+        .unwrap()
+        .to_string();
+
+    // the ledger as left behind by an earlier run which submitted the transaction
+    env.add_file("submitted_transactions", format!("{tx_hash}\n").as_bytes());
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("submit")
+        .arg("--file")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--keypair-name")
+        .arg("signer")
+        .arg("--idempotent")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+
+    expect_output(
+        &[&format!(
+            "Transaction {tx_hash} is already submitted according to the ledger"
+        )],
+        &output,
+    )
+    .unwrap();
+}
+
+/// - Case:     User sends a transfer in one command with an invalid recipient, or with a keypair that does not exist
+/// - Expect:   The transfer is refused before anything is saved or submitted
+/// - Command:  ./pchain_client transaction send --keypair-name <NAME> --gas-limit <GAS_LIMIT> --max-base-fee-per-gas <FEE> --priority-fee-per-gas <FEE> --save <PATH> transfer --recipient <ADDRESS> --amount <AMOUNT>
+#[test]
+#[serial]
+fn test_transaction_send() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.json");
+
+    let send = |keypair_name: &str, recipient: &str| {
+        let output = Command::new(&env.bin)
+            .arg("transaction")
+            .arg("send")
+            .arg("--keypair-name")
+            .arg(keypair_name)
+            .arg("--gas-limit")
+            .arg("100000000")
+            .arg("--max-base-fee-per-gas")
+            .arg("8")
+            .arg("--priority-fee-per-gas")
+            .arg("0")
+            .arg("--save")
+            .arg(tx_path.to_str().unwrap())
+            .arg("transfer")
+            .arg("--recipient")
+            .arg(recipient)
+            .arg("--amount")
+            .arg("100")
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let output = send("testkey", "AAAAAAAAAAAAAAAAAAAAAA");
+    expect_output(&["Fail to decode \"target\" address"], &output).unwrap();
+    assert!(!tx_path.exists());
+
+    let output = send("nokey", "kRPL8cXI73DNgVSSQz9WfIi-mAAvFvdXKfZ9UPBEv_A");
+    expect_output(&["Keypair name nokey provided does not exist"], &output).unwrap();
+    assert!(!tx_path.exists());
+}
+
+/// - Case:     User submits a valid Transaction file with a keypair name which does not exist
+/// - Expect:   Error of keypair not found and non-zero exit code, before any request to Fullnode RPC
+/// - Command:
+///   - ./pchain_client transaction create --destination <FILE> --v2 ... transfer ...
+///   - ./pchain_client transaction submit --file <FILE> --keypair-name does-not-exist
+#[test]
+#[serial]
+fn test_transaction_submit_keypair_not_found() {
+    let env = TestEnv::new();
+    // nothing listens on the discard port, so a request to Fullnode RPC could never succeed
+    env.add_file("config.toml", b"url = \"http://127.0.0.1:9\"\n");
+    let tx_path = env.cli_home.path().join("tx.json");
+
+    create_transfer_tx(&env, &tx_path, &[]);
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("submit")
+        .arg("--file")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--keypair-name")
+        .arg("does-not-exist")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+    expect_output(
+        &["Error: Keypair name does-not-exist provided does not exist"],
+        &stdout,
+    )
+    .unwrap();
+    assert!(!stdout.contains("Fullnode"));
+    assert!(!stderr.contains("panicked"));
+}
+
+/// - Case:     User exports a Transaction file as a signed, borsh-serialized transaction
+/// - Expect:   The saved bytes deserialize to the transaction of the file, signed by the keypair
+/// - Command:  ./pchain_client transaction export-binary --file <FILE> --keypair-name <KEYPAIR_NAME> --destination <DESTINATION>
+#[test]
+#[serial]
+fn test_transaction_export_binary() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.json");
+    let binary_path = env.cli_home.path().join("tx.bin");
+
+    let mut osrng = OsRng {};
+    let keypair = Keypair::generate(&mut osrng);
+    let public = base64url::encode(keypair.verifying_key().as_bytes());
+
+    let output = Command::new(&env.bin)
+        .arg("keys")
+        .arg("import")
+        .arg("--public")
+        .arg(&public)
+        .arg("--private")
+        .arg(base64url::encode(keypair.as_bytes()))
+        .arg("--keypair-name")
+        .arg("testkey")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&["Successfully add keypair with name testkey."], &output).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("create")
+        .arg("--destination")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--v1")
+        .arg("--nonce")
+        .arg("3")
+        .arg("--gas-limit")
+        .arg("100000000")
+        .arg("--max-base-fee-per-gas")
+        .arg("8")
+        .arg("--priority-fee-per-gas")
+        .arg("0")
+        .arg("transfer")
+        .arg("--recipient")
+        .arg(&public)
+        .arg("--amount")
+        .arg("100")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&["Successfully create Transaction file"], &output).unwrap();
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("export-binary")
+        .arg("--file")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--keypair-name")
+        .arg("testkey")
+        .arg("--destination")
+        .arg(binary_path.to_str().unwrap())
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&output.stdout).to_string();
+    expect_output(&["Successfully create Signed transaction file"], &output).unwrap();
+
+    let signed_tx = TransactionV1OrV2::deserialize(&std::fs::read(&binary_path).unwrap()).unwrap();
+    match signed_tx {
+        TransactionV1OrV2::V1(tx) => {
+            assert_eq!(tx.signer, keypair.verifying_key().to_bytes());
+            assert_eq!(tx.nonce, 3);
+            assert_eq!(tx.commands.len(), 1);
+        }
+        TransactionV1OrV2::V2(_) => panic!("expected TransactionV1"),
+    }
+}
+
+/// - Case:     User creates a transaction with both `--auto-version` and `--v2`
+/// - Expect:   Error message about the conflicting versions. Fullnode RPC is not queried
+/// - Command:  ./pchain_client transaction create --auto-version --v2 ... transfer --recipient <RECIPIENT> --amount <AMOUNT>
+#[test]
+#[serial]
+fn test_transaction_create_auto_version_conflicts() {
+    let env = TestEnv::new();
+    env.add_file("config.toml", b"url = \"http://127.0.0.1:9\"\n");
+    let tx_path = env.cli_home.path().join("tx.json");
+
+    let output = Command::new(&env.bin)
+        .arg("transaction")
+        .arg("create")
+        .arg("--destination")
+        .arg(tx_path.to_str().unwrap())
+        .arg("--auto-version")
+        .arg("--v2")
+        .arg("--nonce")
+        .arg("0")
+        .arg("--gas-limit")
+        .arg("100000")
+        .arg("--max-base-fee-per-gas")
+        .arg("8")
+        .arg("--priority-fee-per-gas")
+        .arg("0")
+        .arg("transfer")
+        .arg("--recipient")
+        .arg("kRPL7ZwHw-CQ6eU_8c2WxrYZUL7k_IWFrm5LRZUgGfY")
+        .arg("--amount")
+        .arg("100")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+    expect_output(&["--auto-version.* cannot be used with .*--v2"], &stderr).unwrap();
+    assert!(!stderr.contains("panicked"));
+    assert!(!tx_path.exists());
+}
+
+/// - Case:     User creates Transaction files with co-signers, then submits them
+/// - Expect:   Co-signers are saved in the file. Transaction is not submitted. Display error of unknown or duplicated
+///             co-signer, or that a transaction carries only one signature if all keypairs are valid
+/// - Command:  ./pchain_client transaction create ... --co-signer <KEYPAIR_NAME> ...
+#[test]
+#[serial]
+fn test_transaction_submit_co_signers() {
+    let env = TestEnv::new().with_rpc_config();
+    let tx_path = env.cli_home.path().join("tx.json");
+
+    let mut osrng = OsRng {};
+    for keypair_name in ["signer", "cosigner"] {
+        let keypair = Keypair::generate(&mut osrng);
+        let output = Command::new(&env.bin)
+            .arg("keys")
+            .arg("import")
+            .arg("--public")
+            .arg(base64url::encode(keypair.verifying_key().as_bytes()))
+            .arg("--private")
+            .arg(base64url::encode(keypair.as_bytes()))
+            .arg("--keypair-name")
+            .arg(keypair_name)
+            .output()
+            .unwrap();
+        let output = String::from_utf8_lossy(&output.stdout).to_string();
+        expect_output(&["Successfully add keypair"], &output).unwrap();
+    }
+
+    let submit = |co_signer: &str| {
+        create_transfer_tx(&env, &tx_path, &["--co-signer", co_signer]);
+        let tx = std::fs::read_to_string(&tx_path).unwrap();
+        assert!(tx.contains("co_signers"));
+
+        let output = Command::new(&env.bin)
+            .arg("transaction")
+            .arg("submit")
+            .arg("--file")
+            .arg(tx_path.to_str().unwrap())
+            .arg("--keypair-name")
+            .arg("signer")
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    expect_output(
+        &["Keypair name unknown provided does not exist"],
+        &submit("unknown"),
+    )
+    .unwrap();
+    expect_output(
+        &["Keypair signer signs the transaction more than once"],
+        &submit("signer"),
+    )
+    .unwrap();
+    expect_output(
+        &["All 2 keypairs which sign the transaction are valid"],
+        &submit("cosigner"),
+    )
+    .unwrap();
+}